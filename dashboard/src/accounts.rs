@@ -0,0 +1,243 @@
+use std::{collections::BTreeMap, path::Path};
+
+use crate::{
+    grid::{FundSpec, Side},
+    logline,
+    market::AssetRef,
+    state::BotStatus,
+};
+
+/// How far back in the bot log to look for the last funds status block.
+const MAX_STATUS_BYTES: u64 = 256 * 1024;
+const UNSET_ACCOUNT: &str = "(no preferredAccount)";
+
+/// The `total.chain: Buy X | Sell Y` line `modules/order/logger.js` prints with the
+/// funds status. Buy is asset B, sell is asset A.
+#[derive(Clone, Copy, Debug)]
+pub struct ChainTotals {
+    pub buy: f64,
+    pub sell: f64,
+}
+
+/// Last `total.chain` reading in the log, if the bot has printed one.
+pub fn read_chain_totals(path: &Path) -> Option<ChainTotals> {
+    logline::read_tail(path, MAX_STATUS_BYTES)
+        .iter()
+        .rev()
+        .map(|raw| logline::strip_escapes(raw))
+        .find_map(|text| {
+            let (_, rest) = text.split_once("total.chain:")?;
+            let (buy, sell) = rest.split_once('|')?;
+            Some(ChainTotals {
+                buy: amount_after(buy, "Buy")?,
+                sell: amount_after(sell, "Sell")?,
+            })
+        })
+}
+
+fn amount_after(text: &str, label: &str) -> Option<f64> {
+    let (_, rest) = text.split_once(label)?;
+    rest.split_whitespace().next()?.parse::<f64>().ok()
+}
+
+/// Grouping name for an asset: its symbol, else its id.
+pub fn asset_key(asset: &AssetRef) -> String {
+    if asset.symbol.is_empty() {
+        asset.id.clone()
+    } else {
+        asset.symbol.clone()
+    }
+}
+
+/// One asset on one account, summed over the bots trading it there.
+#[derive(Clone, Debug, Default)]
+pub struct AssetAllocation {
+    pub asset: String,
+    /// `botFunds` percentages of active bots, per bot.
+    pub percent_shares: Vec<(String, f64)>,
+    /// Absolute `botFunds` of active bots.
+    pub absolute: f64,
+    /// Persisted ACTIVE/PARTIAL slot sizes.
+    pub committed: f64,
+    /// Persisted VIRTUAL slot sizes.
+    pub virtual_reserved: f64,
+    /// Largest `total.chain` any bot on the account last logged for the asset.
+    pub chain_total: Option<f64>,
+}
+
+impl AssetAllocation {
+    pub fn percent_total(&self) -> f64 {
+        self.percent_shares.iter().map(|(_, p)| p).sum()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AccountSummary {
+    pub account: String,
+    pub bots: Vec<String>,
+    pub assets: Vec<AssetAllocation>,
+}
+
+/// Groups bots by `preferredAccount` and sums allocations per asset. Allocations
+/// only count active bots; committed and virtual sizes come from every persisted
+/// grid, since a stopped bot's orders stay on chain.
+pub fn summarize(bots: &[BotStatus]) -> (Vec<AccountSummary>, Vec<String>) {
+    let mut accounts: BTreeMap<String, (Vec<String>, BTreeMap<String, AssetAllocation>)> =
+        BTreeMap::new();
+    for bot in bots {
+        let account = bot
+            .account
+            .clone()
+            .unwrap_or_else(|| String::from(UNSET_ACCOUNT));
+        let (names, assets) = accounts.entry(account).or_default();
+        names.push(bot.name.clone());
+
+        // Buy orders spend asset B, sell orders asset A.
+        for (side, asset, chain_total) in [
+            (Side::Buy, &bot.asset_b, bot.chain_totals.map(|t| t.buy)),
+            (Side::Sell, &bot.asset_a, bot.chain_totals.map(|t| t.sell)),
+        ] {
+            let key = asset_key(asset);
+            let entry = assets.entry(key.clone()).or_insert_with(|| AssetAllocation {
+                asset: key,
+                ..AssetAllocation::default()
+            });
+            if bot.active {
+                match bot.config.bot_funds(side) {
+                    FundSpec::Percent(p) => entry.percent_shares.push((bot.name.clone(), p)),
+                    FundSpec::Absolute(a) => entry.absolute += a,
+                }
+            }
+            for slot in bot.grid.iter().filter(|s| s.side() == Some(side)) {
+                match slot.state.as_deref() {
+                    Some("active" | "partial") => entry.committed += slot.size,
+                    Some("virtual") => entry.virtual_reserved += slot.size,
+                    _ => {}
+                }
+            }
+            if let Some(total) = chain_total {
+                entry.chain_total = Some(entry.chain_total.map_or(total, |t: f64| t.max(total)));
+            }
+        }
+    }
+
+    let mut alerts = Vec::new();
+    let summaries = accounts
+        .into_iter()
+        .map(|(account, (bots, assets))| {
+            for a in assets.values() {
+                if a.percent_total() > 100.0 {
+                    let shares = a
+                        .percent_shares
+                        .iter()
+                        .map(|(bot, p)| format!("{bot} {p}%"))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    alerts.push(format!(
+                        "Account {account}: {} botFunds add up to {:.0}% ({shares}); bots will compete for the same balance.",
+                        a.asset,
+                        a.percent_total()
+                    ));
+                }
+                if let Some(chain) = a.chain_total.filter(|c| a.committed > *c * (1.0 + 1e-9)) {
+                    alerts.push(format!(
+                        "Account {account}: committed {} {:.8} exceeds the recorded chain total {chain:.8}.",
+                        a.asset, a.committed
+                    ));
+                }
+            }
+            AccountSummary {
+                account,
+                bots,
+                assets: assets.into_values().collect(),
+            }
+        })
+        .collect();
+    (summaries, alerts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+    use std::fs;
+
+    use crate::testutil::ScratchDir;
+
+    fn bot(name: &str, account: Option<&str>, funds: Value, slots: Value) -> BotStatus {
+        let mut bot = BotStatus::stub(name, name);
+        bot.account = account.map(str::to_string);
+        bot.config = serde_json::from_value(json!({ "botFunds": funds })).unwrap();
+        bot.grid = serde_json::from_value(slots).unwrap();
+        bot
+    }
+
+    #[test]
+    fn summarize_flags_overallocated_percentages_and_commitments_over_the_chain_total() {
+        let mut a = bot(
+            "a",
+            Some("alice"),
+            json!({ "buy": "60%", "sell": 1000 }),
+            json!([
+                { "type": "buy", "state": "active", "size": 30 },
+                { "type": "buy", "state": "partial", "size": 5 },
+                { "type": "buy", "state": "virtual", "size": 10 },
+                { "type": "sell", "state": "active", "size": 2 },
+            ]),
+        );
+        a.chain_totals = Some(ChainTotals { buy: 30.0, sell: 5.0 });
+        let mut b = bot("b", Some("alice"), json!({ "buy": "50%" }), json!([]));
+        b.chain_totals = Some(ChainTotals { buy: 40.0, sell: 0.0 });
+        // Inactive: its percentage is left out, but its orders still count.
+        let mut c = bot(
+            "c",
+            Some("alice"),
+            json!({ "buy": "70%" }),
+            json!([{ "type": "buy", "state": "active", "size": 8 }]),
+        );
+        c.active = false;
+        let d = bot("d", None, json!({ "buy": "90%" }), json!([]));
+
+        let (summaries, alerts) = summarize(&[a, b, c, d]);
+        assert_eq!(
+            summaries.iter().map(|s| s.account.as_str()).collect::<Vec<_>>(),
+            ["(no preferredAccount)", "alice"]
+        );
+        let alice = &summaries[1];
+        assert_eq!(alice.bots, ["a", "b", "c"]);
+        let usd = alice.assets.iter().find(|x| x.asset == "USD").unwrap();
+        assert_eq!(usd.percent_shares, [(String::from("a"), 60.0), (String::from("b"), 50.0)]);
+        assert_eq!((usd.committed, usd.virtual_reserved), (43.0, 10.0));
+        assert_eq!(usd.chain_total, Some(40.0));
+        let bts = alice.assets.iter().find(|x| x.asset == "BTS").unwrap();
+        assert_eq!(bts.absolute, 1000.0);
+        assert_eq!(bts.percent_total(), 100.0);
+        assert_eq!(bts.committed, 2.0);
+
+        assert_eq!(
+            alerts,
+            [
+                "Account alice: USD botFunds add up to 110% (a 60%, b 50%); bots will compete for the same balance.",
+                "Account alice: committed USD 43.00000000 exceeds the recorded chain total 40.00000000.",
+            ]
+        );
+    }
+
+    #[test]
+    fn read_chain_totals_takes_the_last_coloured_status_line() {
+        let dir = ScratchDir::new("accounts");
+        let path = dir.join("bot.log");
+        // The two formats logger.js prints, with its buy/sell colours.
+        let log = [
+            "  total.chain: \x1b[32mBuy 1.50000\x1b[0m | \x1b[31mSell 2.25000\x1b[0m",
+            "total.chain: \x1b[32mBuy 100.12345\x1b[0m USD | \x1b[31mSell 7.00000\x1b[0m BTS",
+            "total.grid: \x1b[32mBuy 99.00000\x1b[0m USD | \x1b[31mSell 6.00000\x1b[0m BTS",
+        ]
+        .join("\n");
+        fs::write(&path, log).unwrap();
+
+        let totals = read_chain_totals(&path).unwrap();
+        assert_eq!((totals.buy, totals.sell), (100.12345, 7.0));
+        assert!(read_chain_totals(&dir.join("missing.log")).is_none());
+    }
+}
@@ -19,12 +19,31 @@ impl Risk {
     }
 }
 
+/// Files a script removes: every file under `dir`, or only those with
+/// `extension`, as the `find` calls in `scripts/clear-*.sh` select them.
+#[derive(Clone, Copy, Debug)]
+pub struct DeleteScope {
+    pub dir: &'static str,
+    pub extension: Option<&'static str>,
+}
+
+const LOG_FILES: DeleteScope = DeleteScope {
+    dir: "profiles/logs",
+    extension: Some("log"),
+};
+const ORDER_FILES: DeleteScope = DeleteScope {
+    dir: "profiles/orders",
+    extension: None,
+};
+
 #[derive(Clone, Debug)]
 pub struct DashboardAction {
     pub name: &'static str,
     pub command: &'static str,
     pub args: &'static [&'static str],
     pub risk: Risk,
+    /// What the action deletes, for the Danger preview.
+    pub deletes: &'static [DeleteScope],
 }
 
 impl DashboardAction {
@@ -63,48 +82,56 @@ pub fn dashboard_actions() -> Vec<DashboardAction> {
             command: "bash",
             args: &["scripts/check-update.sh"],
             risk: Risk::Safe,
+            deletes: &[],
         },
         DashboardAction {
             name: "Validate Bots Config",
             command: "node",
             args: &["scripts/validate_bots.js"],
             risk: Risk::Safe,
+            deletes: &[],
         },
         DashboardAction {
             name: "Analyze Orders",
             command: "node",
             args: &["scripts/analyze-orders.js"],
             risk: Risk::Safe,
+            deletes: &[],
         },
         DashboardAction {
             name: "Analyze Repo",
             command: "node",
             args: &["scripts/analyze-git.js"],
             risk: Risk::Safe,
+            deletes: &[],
         },
         DashboardAction {
             name: "Create Bot Symlinks",
             command: "bash",
             args: &["scripts/create-bot-symlinks.sh"],
             risk: Risk::Confirm,
+            deletes: &[],
         },
         DashboardAction {
             name: "Clear Logs",
             command: "bash",
             args: &["scripts/clear-logs.sh"],
             risk: Risk::Danger,
+            deletes: &[LOG_FILES],
         },
         DashboardAction {
             name: "Clear Orders",
             command: "bash",
             args: &["scripts/clear-orders.sh"],
             risk: Risk::Danger,
+            deletes: &[ORDER_FILES],
         },
         DashboardAction {
             name: "Clear All",
             command: "bash",
             args: &["scripts/clear-all.sh"],
             risk: Risk::Danger,
+            deletes: &[ORDER_FILES, LOG_FILES],
         },
     ]
 }
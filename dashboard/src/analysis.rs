@@ -0,0 +1,403 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde_json::Value;
+
+use crate::grid::{self, GridConfig};
+
+const AMA_FITTING_DIR: &str = "analysis/ama_fitting";
+const BOT_FITTING_DIR: &str = "analysis/bot_fitting";
+/// Winners written by `optimizer_high_resolution.js`, in its summary order.
+const AMA_WINNERS: [&str; 4] = [
+    "bestAreaMaxDist",
+    "bestProdMaxDist",
+    "bestAreaMaxDistCapped",
+    "bestProdMaxDistCapped",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResultKind {
+    AmaOptimization,
+    BotFitting,
+}
+
+impl ResultKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            ResultKind::AmaOptimization => "AMA optimization",
+            ResultKind::BotFitting => "bot fitting backtest",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ResultRow {
+    pub label: String,
+    /// One value per `ResultTable::columns`; `None` when the file lacks it.
+    pub values: Vec<Option<f64>>,
+    /// Grid parameters the row suggests, in percent.
+    pub spread_percent: Option<f64>,
+    pub increment_percent: Option<f64>,
+}
+
+/// One result file flattened into a table of parameter sets and metrics.
+#[derive(Clone, Debug)]
+pub struct ResultTable {
+    pub path: PathBuf,
+    pub kind: ResultKind,
+    pub data_label: String,
+    pub columns: Vec<&'static str>,
+    pub rows: Vec<ResultRow>,
+}
+
+impl ResultTable {
+    pub fn name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+}
+
+/// Finds `optimization_results_*.json` and `bot_fitting_results_*.json`; files that
+/// fail to parse are returned as errors so they still show up in the list.
+pub fn load_result_tables() -> Vec<Result<ResultTable, (PathBuf, String)>> {
+    let mut tables = Vec::new();
+    for (dir, prefix, kind) in [
+        (AMA_FITTING_DIR, "optimization_results_", ResultKind::AmaOptimization),
+        (BOT_FITTING_DIR, "bot_fitting_results_", ResultKind::BotFitting),
+    ] {
+        let Ok(entries) = fs::read_dir(dir) else {
+            continue;
+        };
+        let mut paths = entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| {
+                p.extension().is_some_and(|ext| ext == "json")
+                    && p.file_name()
+                        .is_some_and(|n| n.to_string_lossy().starts_with(prefix))
+            })
+            .collect::<Vec<_>>();
+        paths.sort();
+        tables.extend(paths.into_iter().map(|path| {
+            parse_table(&path, kind).map_err(|err| (path.clone(), err))
+        }));
+    }
+    tables
+}
+
+fn parse_table(path: &Path, kind: ResultKind) -> Result<ResultTable, String> {
+    let raw = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let json = serde_json::from_str::<Value>(&raw).map_err(|e| e.to_string())?;
+    match kind {
+        ResultKind::AmaOptimization => ama_table(path, &json),
+        ResultKind::BotFitting => bot_fitting_table(path, &json),
+    }
+}
+
+fn ama_table(path: &Path, json: &Value) -> Result<ResultTable, String> {
+    let meta = json.get("meta").ok_or_else(|| String::from("no meta"))?;
+    let rows = AMA_WINNERS
+        .iter()
+        .filter_map(|key| {
+            let r = meta.get(*key).filter(|r| r.is_object())?;
+            Some(ResultRow {
+                label: key.to_string(),
+                values: vec![
+                    num(r, &["er"]),
+                    num(r, &["fast"]),
+                    num(r, &["slow"]),
+                    num(r, &["area", "total"]),
+                    num(r, &["area", "above"]),
+                    num(r, &["area", "below"]),
+                    num(r, &["area", "maxDist"]).map(|d| d * 100.0),
+                    num(r, &["repos"]),
+                    num(r, &["bandFactorPct"]),
+                    num(r, &["areaMaxDist"]),
+                    num(r, &["prodMaxDist"]),
+                ],
+                spread_percent: None,
+                increment_percent: None,
+            })
+        })
+        .collect::<Vec<_>>();
+    if rows.is_empty() {
+        return Err(String::from("no best* winners in meta"));
+    }
+    Ok(ResultTable {
+        path: path.to_path_buf(),
+        kind: ResultKind::AmaOptimization,
+        data_label: str_at(meta, "dataLabel"),
+        columns: vec![
+            "ER", "Fast", "Slow", "Area", "Above", "Below", "MaxDist%", "Repos%", "Band%",
+            "Area/MaxD", "Prod/MaxD",
+        ],
+        rows,
+    })
+}
+
+fn bot_fitting_table(path: &Path, json: &Value) -> Result<ResultTable, String> {
+    let results = json
+        .get("results")
+        .and_then(Value::as_array)
+        .ok_or_else(|| String::from("no results array"))?;
+    let rows = results
+        .iter()
+        .map(|r| {
+            let strategy = r.get("strategy").unwrap_or(&Value::Null);
+            let best = r.get("best").unwrap_or(&Value::Null);
+            // The backtest stores the increment as a fraction and the spread in percent.
+            let increment_percent = num(best, &["incrementPct"]).map(|i| i * 100.0);
+            let spread_percent = num(best, &["spreadPct"]);
+            ResultRow {
+                label: str_at(strategy, "name"),
+                values: vec![
+                    num(strategy, &["er"]),
+                    num(strategy, &["fast"]),
+                    num(strategy, &["slow"]),
+                    spread_percent,
+                    increment_percent,
+                    num(best, &["maxMinRatio"]),
+                    num(best, &["matchedPairs"]),
+                    num(best, &["fillEfficiency"]),
+                    num(best, &["totalNetCapturePct"]),
+                    num(best, &["riskPenalty"]),
+                    num(best, &["score"]),
+                ],
+                spread_percent,
+                increment_percent,
+            }
+        })
+        .collect::<Vec<_>>();
+    let meta = json.get("meta").unwrap_or(&Value::Null);
+    Ok(ResultTable {
+        path: path.to_path_buf(),
+        kind: ResultKind::BotFitting,
+        data_label: str_at(meta, "dataPath"),
+        columns: vec![
+            "ER", "Fast", "Slow", "Spread%", "Incr%", "Ratio", "Pairs", "FillEff%", "Net%",
+            "Risk", "Score",
+        ],
+        rows,
+    })
+}
+
+fn num(value: &Value, path: &[&str]) -> Option<f64> {
+    let mut current = value;
+    for key in path {
+        current = current.get(key)?;
+    }
+    grid::number(current)
+}
+
+fn str_at(value: &Value, key: &str) -> String {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .unwrap_or("-")
+        .to_string()
+}
+
+/// A suggested grid parameter next to the live config value.
+#[derive(Clone, Debug)]
+pub struct ParamComparison {
+    pub name: &'static str,
+    pub live: f64,
+    pub suggested: Option<f64>,
+}
+
+impl ParamComparison {
+    pub fn delta_percent(&self) -> Option<f64> {
+        let suggested = self.suggested?;
+        (self.live != 0.0).then(|| (suggested / self.live - 1.0) * 100.0)
+    }
+}
+
+pub fn compare(row: &ResultRow, config: &GridConfig) -> Vec<ParamComparison> {
+    vec![
+        ParamComparison {
+            name: "incrementPercent",
+            live: config.increment_percent(),
+            suggested: row.increment_percent,
+        },
+        ParamComparison {
+            name: "targetSpreadPercent",
+            live: config.target_spread_percent(),
+            suggested: row.spread_percent,
+        },
+    ]
+}
+
+/// Analysis tab state: which file is shown, its sort column and the chosen row.
+/// Tables are reloaded while the tab is open; the selection is kept by index.
+#[derive(Debug, Default)]
+pub struct AnalysisState {
+    pub tables: Vec<Result<ResultTable, (PathBuf, String)>>,
+    pub selected_table: usize,
+    pub sort_column: Option<usize>,
+    pub descending: bool,
+    pub selected_row: usize,
+}
+
+impl AnalysisState {
+    pub fn reload(&mut self) {
+        self.tables = load_result_tables();
+        self.selected_table = self.selected_table.min(self.tables.len().saturating_sub(1));
+        self.clamp();
+    }
+
+    pub fn table(&self) -> Option<&Result<ResultTable, (PathBuf, String)>> {
+        self.tables.get(self.selected_table)
+    }
+
+    /// Row indices of the current table in display order.
+    pub fn sorted_rows(&self) -> Vec<usize> {
+        let Some(Ok(table)) = self.table() else {
+            return vec![];
+        };
+        let mut order = (0..table.rows.len()).collect::<Vec<_>>();
+        if let Some(col) = self.sort_column {
+            // Missing values sort last in either direction.
+            order.sort_by(|a, b| {
+                let (a, b) = (table.rows[*a].values[col], table.rows[*b].values[col]);
+                match (a, b) {
+                    (Some(a), Some(b)) if self.descending => b.total_cmp(&a),
+                    (Some(a), Some(b)) => a.total_cmp(&b),
+                    (Some(_), None) => std::cmp::Ordering::Less,
+                    (None, Some(_)) => std::cmp::Ordering::Greater,
+                    (None, None) => std::cmp::Ordering::Equal,
+                }
+            });
+        }
+        order
+    }
+
+    pub fn selected(&self) -> Option<&ResultRow> {
+        let Some(Ok(table)) = self.table() else {
+            return None;
+        };
+        let index = *self.sorted_rows().get(self.selected_row)?;
+        table.rows.get(index)
+    }
+
+    fn clamp(&mut self) {
+        let (rows, columns) = match self.table() {
+            Some(Ok(table)) => (table.rows.len(), table.columns.len()),
+            _ => (0, 0),
+        };
+        self.selected_row = self.selected_row.min(rows.saturating_sub(1));
+        if self.sort_column.is_some_and(|c| c >= columns) {
+            self.sort_column = None;
+        }
+    }
+
+    pub fn next_table(&mut self) {
+        if !self.tables.is_empty() {
+            self.selected_table = (self.selected_table + 1) % self.tables.len();
+        }
+        self.sort_column = None;
+        self.selected_row = 0;
+    }
+
+    pub fn next_row(&mut self) {
+        self.selected_row += 1;
+        self.clamp();
+    }
+
+    pub fn prev_row(&mut self) {
+        self.selected_row = self.selected_row.saturating_sub(1);
+    }
+
+    /// Steps the sort through each column, then back to file order.
+    pub fn cycle_sort(&mut self) {
+        let columns = match self.table() {
+            Some(Ok(table)) => table.columns.len(),
+            _ => 0,
+        };
+        self.sort_column = match self.sort_column {
+            None if columns > 0 => Some(0),
+            Some(c) if c + 1 < columns => Some(c + 1),
+            _ => None,
+        };
+        self.selected_row = 0;
+    }
+
+    pub fn toggle_order(&mut self) {
+        self.descending = !self.descending;
+        self.selected_row = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Trimmed from `analysis/bot_fitting/bot_fitting_results_lp_pool_133_1h.json`,
+    /// plus a row whose backtest found no best parameters.
+    fn bot_fitting_json() -> Value {
+        json!({
+            "meta": { "dataPath": "market_adapter/data/lp_pool_133_1h.json", "activeOrders": 5 },
+            "results": [
+                {
+                    "strategy": { "id": "bestAreaMaxDist", "name": "MAX AREA/MAXDIST", "er": 185, "fast": 10, "slow": 100 },
+                    "best": {
+                        "spreadPct": 0.8, "incrementPct": 0.002, "maxMinRatio": 1.5, "matchedPairs": 184,
+                        "fillEfficiency": 49.46236559139785, "totalNetCapturePct": 73.60000000000001,
+                        "riskPenalty": 176.72077965046125, "score": -140.31647857519243
+                    }
+                },
+                {
+                    "strategy": { "id": "bestProdMaxDist", "name": "MAX PROD/MAXDIST", "er": 120, "fast": 4, "slow": 60 },
+                    "best": { "spreadPct": 1.2, "incrementPct": 0.004, "score": 12.5 }
+                },
+                { "strategy": { "id": "none", "name": "NO RESULT" }, "best": null }
+            ]
+        })
+    }
+
+    fn table() -> ResultTable {
+        bot_fitting_table(Path::new("bot_fitting_results_x.json"), &bot_fitting_json()).unwrap()
+    }
+
+    #[test]
+    fn bot_fitting_rows_report_the_increment_in_percent() {
+        let table = table();
+        assert_eq!(table.data_label, "market_adapter/data/lp_pool_133_1h.json");
+        assert_eq!(table.columns.len(), table.rows[0].values.len());
+        let row = &table.rows[0];
+        assert_eq!(row.label, "MAX AREA/MAXDIST");
+        assert_eq!((row.spread_percent, row.increment_percent), (Some(0.8), Some(0.2)));
+        assert_eq!(row.values[..5], [Some(185.0), Some(10.0), Some(100.0), Some(0.8), Some(0.2)]);
+        assert_eq!(row.values[10], Some(-140.31647857519243));
+        assert_eq!(table.rows[1].values[5], None);
+        assert!(table.rows[2].values.iter().all(Option::is_none));
+
+        assert!(bot_fitting_table(Path::new("x.json"), &json!({ "meta": {} })).is_err());
+    }
+
+    #[test]
+    fn sorted_rows_put_missing_values_last_in_both_directions() {
+        let mut state = AnalysisState {
+            tables: vec![Ok(table())],
+            ..AnalysisState::default()
+        };
+        assert_eq!(state.sorted_rows(), [0, 1, 2]);
+
+        // Score: -140.3, 12.5, missing.
+        state.sort_column = Some(10);
+        assert_eq!(state.sorted_rows(), [0, 1, 2]);
+        state.toggle_order();
+        assert_eq!(state.sorted_rows(), [1, 0, 2]);
+        assert_eq!(state.selected().unwrap().label, "MAX PROD/MAXDIST");
+
+        // Ratio: only the first row has one.
+        state.sort_column = Some(5);
+        assert_eq!(state.sorted_rows(), [0, 1, 2]);
+        state.toggle_order();
+        assert_eq!(state.sorted_rows(), [0, 1, 2]);
+    }
+}
@@ -0,0 +1,154 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+
+use crate::logline::{self, Segment};
+
+/// Converts one log line with ANSI escapes into styled spans. SGR sequences
+/// (`ESC [ ... m`) become styles; every other CSI, OSC or C0 control is dropped so
+/// cursor movement or title changes in a log cannot disturb the terminal.
+pub fn to_line(raw: &str) -> Line<'static> {
+    let mut spans = Vec::new();
+    let mut style = Style::default();
+    for segment in logline::segments(raw) {
+        match segment {
+            Segment::Text(text) => spans.push(Span::styled(text, style)),
+            Segment::Sgr(params) => style = apply_sgr(style, &params),
+        }
+    }
+    Line::from(spans)
+}
+
+fn apply_sgr(mut style: Style, params: &str) -> Style {
+    // `ESC[m` is a reset, as is an empty parameter.
+    let codes = params
+        .split(';')
+        .map(|p| p.parse::<u16>().unwrap_or(0))
+        .collect::<Vec<_>>();
+    let mut i = 0;
+    while i < codes.len() {
+        match codes[i] {
+            0 => style = Style::default(),
+            1 => style = style.add_modifier(Modifier::BOLD),
+            2 => style = style.add_modifier(Modifier::DIM),
+            3 => style = style.add_modifier(Modifier::ITALIC),
+            4 => style = style.add_modifier(Modifier::UNDERLINED),
+            7 => style = style.add_modifier(Modifier::REVERSED),
+            9 => style = style.add_modifier(Modifier::CROSSED_OUT),
+            22 => style = style.remove_modifier(Modifier::BOLD | Modifier::DIM),
+            23 => style = style.remove_modifier(Modifier::ITALIC),
+            24 => style = style.remove_modifier(Modifier::UNDERLINED),
+            27 => style = style.remove_modifier(Modifier::REVERSED),
+            29 => style = style.remove_modifier(Modifier::CROSSED_OUT),
+            code @ 30..=37 => style = style.fg(basic(code - 30, false)),
+            code @ 90..=97 => style = style.fg(basic(code - 90, true)),
+            code @ 40..=47 => style = style.bg(basic(code - 40, false)),
+            code @ 100..=107 => style = style.bg(basic(code - 100, true)),
+            39 => style.fg = None,
+            49 => style.bg = None,
+            code @ (38 | 48) => {
+                let (color, used) = extended(&codes[i + 1..]);
+                if let Some(color) = color {
+                    style = if code == 38 {
+                        style.fg(color)
+                    } else {
+                        style.bg(color)
+                    };
+                }
+                i += used;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    style
+}
+
+/// `5;n` (256-colour palette) or `2;r;g;b` (truecolour). Returns the colour and
+/// how many parameters it consumed.
+fn extended(rest: &[u16]) -> (Option<Color>, usize) {
+    match rest {
+        [5, n, ..] => (Some(Color::Indexed((*n).min(255) as u8)), 2),
+        [2, r, g, b, ..] => (
+            Some(Color::Rgb(
+                (*r).min(255) as u8,
+                (*g).min(255) as u8,
+                (*b).min(255) as u8,
+            )),
+            4,
+        ),
+        _ => (None, rest.len()),
+    }
+}
+
+fn basic(index: u16, bright: bool) -> Color {
+    match (index, bright) {
+        (0, false) => Color::Black,
+        (1, false) => Color::Red,
+        (2, false) => Color::Green,
+        (3, false) => Color::Yellow,
+        (4, false) => Color::Blue,
+        (5, false) => Color::Magenta,
+        (6, false) => Color::Cyan,
+        (7, false) => Color::Gray,
+        (0, true) => Color::DarkGray,
+        (1, true) => Color::LightRed,
+        (2, true) => Color::LightGreen,
+        (3, true) => Color::LightYellow,
+        (4, true) => Color::LightBlue,
+        (5, true) => Color::LightMagenta,
+        (6, true) => Color::LightCyan,
+        _ => Color::White,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn basic_and_bright_colours() {
+        let style = apply_sgr(Style::default(), "31;102");
+        assert_eq!((style.fg, style.bg), (Some(Color::Red), Some(Color::LightGreen)));
+        let style = apply_sgr(style, "39;49");
+        assert_eq!((style.fg, style.bg), (None, None));
+        assert_eq!(apply_sgr(Style::default(), "97").fg, Some(Color::White));
+    }
+
+    #[test]
+    fn palette_and_truecolour() {
+        assert_eq!(apply_sgr(Style::default(), "38;5;208").fg, Some(Color::Indexed(208)));
+        let style = apply_sgr(Style::default(), "48;2;10;20;300;1");
+        assert_eq!(style.bg, Some(Color::Rgb(10, 20, 255)));
+        // The parameter after the colour is still applied.
+        assert!(style.add_modifier.contains(Modifier::BOLD));
+        assert_eq!(extended(&[5, 9, 1]), (Some(Color::Indexed(9)), 2));
+        assert_eq!(extended(&[2, 1, 2]), (None, 3));
+    }
+
+    #[test]
+    fn bold_dim_and_resets() {
+        let style = apply_sgr(Style::default(), "1;2;4;31");
+        assert!(style.add_modifier.contains(Modifier::BOLD | Modifier::DIM | Modifier::UNDERLINED));
+        let style = apply_sgr(style, "22");
+        assert!(!style.add_modifier.intersects(Modifier::BOLD | Modifier::DIM));
+        assert!(style.add_modifier.contains(Modifier::UNDERLINED));
+        assert_eq!(style.fg, Some(Color::Red));
+        assert_eq!(apply_sgr(style, ""), Style::default());
+        assert_eq!(apply_sgr(style, "0"), Style::default());
+    }
+
+    #[test]
+    fn to_line_styles_text_and_drops_other_sequences() {
+        let line = to_line("\u{1b}]0;title\u{7}\u{1b}[1;32mok\u{1b}[0m \u{1b}[2Kdone\u{1b}]8;;x\u{1b}\\!");
+        let spans = line
+            .spans
+            .iter()
+            .map(|s| (s.content.as_ref(), s.style))
+            .collect::<Vec<_>>();
+        let green = Style::default().fg(Color::Green).add_modifier(Modifier::BOLD);
+        assert_eq!(spans, [("ok", green), (" done!", Style::default())]);
+        assert_eq!(logline::strip_escapes("\u{1b}[1;32mok\u{1b}[0m \u{1b}]0;t\u{7}done\r"), "ok done");
+    }
+}
@@ -1,41 +1,147 @@
-use std::time::{Duration, Instant};
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
 
 use anyhow::Result;
 use crossterm::event::KeyCode;
 use ratatui::widgets::ListState;
 
-use crate::{actions::{self, DashboardAction, Risk}, state};
+use crate::{
+    actions::{self, DashboardAction, Risk},
+    analysis::AnalysisState,
+    backups::{self, BackupState, Retention},
+    chart::ChartState,
+    consistency::Cleanup,
+    cred_probe::{CredProbe, ProbeScheduler},
+    cycles::CycleState,
+    dryrun::{self, DeletionPlan},
+    fees::{FeeLimits, FeeTracker},
+    logline::Level,
+    logview::{LogInput, LogViewer},
+    partials::PartialTracker,
+    preview::PreviewState,
+    reset::BotReset,
+    restart::RestartTracker,
+    runtime::{self, ProcessTracker, TrackerLimits},
+    scroll::LogScroll,
+    settings::LogColorMode,
+    spread::{SpreadLimits, SpreadTracker},
+    state,
+    timeline::TimelineState,
+};
 
 const DANGER_CONFIRM_TOKEN: &str = "DELETE";
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Tab {
     Overview,
     BotDetail,
+    Grid,
+    Chart,
+    Cycles,
+    Fees,
+    Preview,
+    Analysis,
+    Timeline,
+    Accounts,
+    Exposure,
+    System,
+    Consistency,
+    Backups,
     Scripts,
     Alerts,
 }
 
 impl Tab {
+    pub const ALL: [Tab; 16] = [
+        Tab::Overview,
+        Tab::BotDetail,
+        Tab::Grid,
+        Tab::Chart,
+        Tab::Cycles,
+        Tab::Fees,
+        Tab::Preview,
+        Tab::Analysis,
+        Tab::Timeline,
+        Tab::Accounts,
+        Tab::Exposure,
+        Tab::System,
+        Tab::Consistency,
+        Tab::Backups,
+        Tab::Scripts,
+        Tab::Alerts,
+    ];
+
     pub fn title(self) -> &'static str {
         match self {
             Tab::Overview => "Overview",
             Tab::BotDetail => "Bot Detail",
+            Tab::Grid => "Grid",
+            Tab::Chart => "Chart",
+            Tab::Cycles => "Cycles",
+            Tab::Fees => "Fees",
+            Tab::Preview => "Preview",
+            Tab::Analysis => "Analysis",
+            Tab::Timeline => "Timeline",
+            Tab::Accounts => "Accounts",
+            Tab::Exposure => "Exposure",
+            Tab::System => "System",
+            Tab::Consistency => "Consistency",
+            Tab::Backups => "Backups",
             Tab::Scripts => "Scripts",
             Tab::Alerts => "Alerts",
         }
     }
+
+    pub fn index(self) -> usize {
+        Tab::ALL.iter().position(|t| *t == self).unwrap_or(0)
+    }
+}
+
+/// What a confirmed action runs.
+#[derive(Clone, Debug)]
+pub enum ActionTarget {
+    /// Index into `App::actions`.
+    Script(usize),
+    Cleanup(Cleanup),
+    ResetBot(BotReset),
+    /// A `profiles/backups` archive to unpack.
+    Restore(PathBuf),
+}
+
+impl ActionTarget {
+    /// What a Danger target must be confirmed with: the bot's name for per-bot
+    /// resets, so the wrong bot cannot be cleared by habit.
+    pub fn confirm_token(&self) -> &str {
+        match self {
+            ActionTarget::ResetBot(reset) => &reset.name,
+            _ => DANGER_CONFIRM_TOKEN,
+        }
+    }
 }
 
 #[derive(Debug)]
 pub enum PendingAction {
-    Confirm { action_index: usize },
-    Danger { action_index: usize, typed: String },
+    Confirm {
+        target: ActionTarget,
+    },
+    /// Dry run of a Danger target; `Enter` moves on to token entry.
+    Review {
+        target: ActionTarget,
+        plan: DeletionPlan,
+    },
+    Danger {
+        target: ActionTarget,
+        plan: DeletionPlan,
+        typed: String,
+    },
 }
 
 #[derive(Debug)]
 pub struct App {
     pub snapshot: state::Snapshot,
+    cache: state::SnapshotCache,
     pub selected_bot: usize,
     pub selected_action: usize,
     pub tab: Tab,
@@ -44,12 +150,31 @@ pub struct App {
     pub bot_list_state: ListState,
     pub action_list_state: ListState,
     pub pending_action: Option<PendingAction>,
+    pub preview: PreviewState,
+    process_tracker: ProcessTracker,
+    pub spread_tracker: SpreadTracker,
+    pub partial_tracker: PartialTracker,
+    pub fee_tracker: FeeTracker,
+    pub restart_tracker: RestartTracker,
+    pub cred_probe: ProbeScheduler,
+    pub log_view: Option<LogViewer>,
+    pub log_colors: LogColorMode,
+    pub timeline: TimelineState,
+    pub chart: ChartState,
+    pub cycles: CycleState,
+    pub analysis: AnalysisState,
+    /// Asset the Exposure tab values holdings in.
+    pub exposure_reference: String,
+    /// Selected Consistency finding.
+    pub consistency_row: usize,
+    pub backups: BackupState,
     last_auto_refresh: Instant,
 }
 
 impl App {
     pub fn new() -> Result<Self> {
-        let snapshot = state::load_snapshot()?;
+        let mut cache = state::SnapshotCache::default();
+        let snapshot = state::load_snapshot(&mut cache)?;
         let actions = actions::dashboard_actions();
 
         let mut bot_list_state = ListState::default();
@@ -62,8 +187,15 @@ impl App {
             action_list_state.select(Some(0));
         }
 
-        Ok(Self {
+        let cred_probe = ProbeScheduler::new(
+            CredProbe::new(Duration::from_millis(snapshot.settings.cred_probe_timeout_ms)),
+            Duration::from_secs(snapshot.settings.cred_probe_interval_secs),
+        );
+        let log_colors = snapshot.settings.log_colors;
+        let exposure_reference = snapshot.settings.exposure_reference_asset.clone();
+        let mut app = Self {
             snapshot,
+            cache,
             selected_bot: 0,
             selected_action: 0,
             tab: Tab::Overview,
@@ -72,8 +204,26 @@ impl App {
             bot_list_state,
             action_list_state,
             pending_action: None,
+            preview: PreviewState::default(),
+            process_tracker: ProcessTracker::default(),
+            spread_tracker: SpreadTracker::default(),
+            partial_tracker: PartialTracker::default(),
+            fee_tracker: FeeTracker::default(),
+            restart_tracker: RestartTracker::default(),
+            cred_probe,
+            log_view: None,
+            log_colors,
+            timeline: TimelineState::default(),
+            chart: ChartState::default(),
+            cycles: CycleState::default(),
+            analysis: AnalysisState::default(),
+            exposure_reference,
+            consistency_row: 0,
+            backups: BackupState::default(),
             last_auto_refresh: Instant::now(),
-        })
+        };
+        app.update_trackers();
+        Ok(app)
     }
 
     pub fn tick(&mut self) {
@@ -83,6 +233,10 @@ impl App {
         if let Err(err) = self.reload_snapshot(false) {
             self.last_output = format!("Auto-refresh failed: {err}");
         }
+        if let Some(viewer) = self.log_view.as_mut().filter(|v| v.view.follow) {
+            viewer.reload();
+        }
+        self.rebuild_tab_data();
         self.last_auto_refresh = Instant::now();
     }
 
@@ -91,7 +245,8 @@ impl App {
     }
 
     fn reload_snapshot(&mut self, announce: bool) -> Result<()> {
-        self.snapshot = state::load_snapshot()?;
+        self.snapshot = state::load_snapshot(&mut self.cache)?;
+        self.update_trackers();
         if self.snapshot.bots.is_empty() {
             self.selected_bot = 0;
             self.bot_list_state.select(None);
@@ -99,12 +254,69 @@ impl App {
             self.selected_bot = self.selected_bot.min(self.snapshot.bots.len() - 1);
             self.bot_list_state.select(Some(self.selected_bot));
         }
+        self.consistency_row = self
+            .consistency_row
+            .min(self.snapshot.consistency.findings.len().saturating_sub(1));
         if announce {
             self.last_output = String::from("Refreshed status data.");
         }
         Ok(())
     }
 
+    /// Feeds each new snapshot to the cross-poll trackers and appends their alerts.
+    fn update_trackers(&mut self) {
+        let settings = &self.snapshot.settings;
+        let limits = TrackerLimits {
+            memory_growth_percent: settings.memory_growth_alert_percent,
+            window: Duration::from_secs(settings.process_watch_window_secs),
+        };
+        let alerts = self
+            .process_tracker
+            .observe(&self.snapshot.runtime, limits, Instant::now());
+        self.snapshot.warnings += alerts.len();
+        self.snapshot.alerts.extend(alerts);
+
+        let limits = SpreadLimits {
+            wide_factor: settings.spread_wide_alert_factor,
+            wide_after: Duration::from_secs(settings.spread_wide_alert_secs),
+        };
+        let alerts = self.spread_tracker.observe(
+            &self.snapshot.bots,
+            limits,
+            Instant::now(),
+            runtime::now_ms(),
+        );
+        self.snapshot.warnings += alerts.len();
+        self.snapshot.alerts.extend(alerts);
+
+        self.partial_tracker.observe(
+            &self.snapshot.bots,
+            settings.dust_threshold_percent,
+            runtime::now_ms(),
+        );
+
+        let limits = FeeLimits {
+            proceeds_share_percent: settings.fee_proceeds_alert_percent,
+            owed_growth_ms: settings.fees_owed_growth_alert_secs as i64 * 1000,
+        };
+        let alerts = self
+            .fee_tracker
+            .observe(&self.snapshot.bots, &self.snapshot.exposure, limits, runtime::now_ms());
+        self.snapshot.warnings += alerts.len();
+        self.snapshot.alerts.extend(alerts);
+
+        let alerts = self
+            .restart_tracker
+            .observe(&self.snapshot.bots, self.snapshot.bots_json_modified_ms);
+        self.snapshot.warnings += alerts.len();
+        self.snapshot.alerts.extend(alerts);
+
+        self.cred_probe.poll();
+        let alerts = self.cred_probe.alerts();
+        self.snapshot.warnings += alerts.len();
+        self.snapshot.alerts.extend(alerts);
+    }
+
     pub fn next_bot(&mut self) {
         if self.snapshot.bots.is_empty() {
             self.selected_bot = 0;
@@ -113,6 +325,7 @@ impl App {
         }
         self.selected_bot = (self.selected_bot + 1) % self.snapshot.bots.len();
         self.bot_list_state.select(Some(self.selected_bot));
+        self.rebuild_bot_tab_data();
     }
 
     pub fn prev_bot(&mut self) {
@@ -127,6 +340,7 @@ impl App {
             self.selected_bot - 1
         };
         self.bot_list_state.select(Some(self.selected_bot));
+        self.rebuild_bot_tab_data();
     }
 
     pub fn next_action(&mut self) {
@@ -154,27 +368,97 @@ impl App {
     }
 
     pub fn next_tab(&mut self) {
-        self.tab = match self.tab {
-            Tab::Overview => Tab::BotDetail,
-            Tab::BotDetail => Tab::Scripts,
-            Tab::Scripts => Tab::Alerts,
-            Tab::Alerts => Tab::Overview,
-        };
+        self.tab = Tab::ALL[(self.tab.index() + 1) % Tab::ALL.len()];
+        self.rebuild_tab_data();
     }
 
     pub fn prev_tab(&mut self) {
-        self.tab = match self.tab {
-            Tab::Overview => Tab::Alerts,
-            Tab::BotDetail => Tab::Overview,
-            Tab::Scripts => Tab::BotDetail,
-            Tab::Alerts => Tab::Scripts,
-        };
+        let len = Tab::ALL.len();
+        self.tab = Tab::ALL[(self.tab.index() + len - 1) % len];
+        self.rebuild_tab_data();
+    }
+
+    /// Views that read files beyond the snapshot only do so while they are shown.
+    fn rebuild_tab_data(&mut self) {
+        match self.tab {
+            Tab::Timeline => self.rebuild_timeline(),
+            Tab::Chart => self.rebuild_chart(),
+            Tab::Cycles => self.rebuild_cycles(),
+            Tab::Analysis => self.analysis.reload(),
+            Tab::Backups => self.backups.reload(),
+            _ => {}
+        }
+    }
+
+    /// Rebuilds per-bot views after the selected bot changed.
+    fn rebuild_bot_tab_data(&mut self) {
+        match self.tab {
+            Tab::Chart => self.rebuild_chart(),
+            Tab::Cycles => self.rebuild_cycles(),
+            _ => {}
+        }
+    }
+
+    fn rebuild_cycles(&mut self) {
+        match self.snapshot.bots.get(self.selected_bot) {
+            Some(bot) => self.cycles.rebuild(bot, self.fee_tracker.ledger(&bot.name)),
+            None => self.cycles.data = Err(String::from("no bot selected")),
+        }
+    }
+
+    fn rebuild_chart(&mut self) {
+        match self.snapshot.bots.get(self.selected_bot) {
+            Some(bot) => self.chart.rebuild(
+                bot,
+                &self.snapshot.market_files,
+                self.fee_tracker.ledger(&bot.name),
+            ),
+            None => self.chart.data = Err(String::from("no bot selected")),
+        }
+    }
+
+    fn rebuild_timeline(&mut self) {
+        let sources = self
+            .snapshot
+            .bots
+            .iter()
+            .map(|b| (b.name.clone(), b.identity.out_log.clone()))
+            .collect::<Vec<_>>();
+        self.timeline.rebuild(&sources);
     }
 
     pub fn handle_key(&mut self, code: KeyCode) -> Result<bool> {
         if self.pending_action.is_some() {
             return self.handle_pending_key(code);
         }
+        if self.log_view.is_some() {
+            self.handle_log_view_key(code);
+            return Ok(false);
+        }
+        if matches!(self.tab, Tab::Preview) {
+            if let Some(handled) = self.handle_preview_key(code) {
+                return Ok(handled);
+            }
+        }
+        if matches!(self.tab, Tab::Timeline) && self.handle_timeline_key(code) {
+            return Ok(false);
+        }
+        if matches!(self.tab, Tab::Chart) && self.handle_chart_key(code) {
+            return Ok(false);
+        }
+        if matches!(self.tab, Tab::Analysis) && self.handle_analysis_key(code) {
+            return Ok(false);
+        }
+        if matches!(self.tab, Tab::Consistency) && self.handle_consistency_key(code)? {
+            return Ok(false);
+        }
+        if matches!(self.tab, Tab::Backups) && self.handle_backups_key(code)? {
+            return Ok(false);
+        }
+        if matches!(self.tab, Tab::Exposure) && code == KeyCode::Char('c') {
+            self.cycle_exposure_reference();
+            return Ok(false);
+        }
 
         match code {
             KeyCode::Char('q') => return Ok(true),
@@ -196,12 +480,235 @@ impl App {
             KeyCode::Right | KeyCode::Tab => self.next_tab(),
             KeyCode::Left => self.prev_tab(),
             KeyCode::Char('x') => self.run_selected_action()?,
+            KeyCode::Char('v') => self.open_log_view(),
+            KeyCode::Char('d') => self.request_bot_reset()?,
             _ => {}
         }
 
         Ok(false)
     }
 
+    /// Timeline tab keys. Returns `false` when the key should fall through to the
+    /// global bindings.
+    fn handle_timeline_key(&mut self, code: KeyCode) -> bool {
+        let timeline = &mut self.timeline;
+        if scroll_key(&mut timeline.view, code) {
+            return true;
+        }
+        match code {
+            KeyCode::Char('b') => timeline.cycle_bot(),
+            KeyCode::Char('c') => timeline.cycle_category(),
+            KeyCode::Char('1') => timeline.toggle_level(Level::Debug),
+            KeyCode::Char('2') => timeline.toggle_level(Level::Info),
+            KeyCode::Char('3') => timeline.toggle_level(Level::Warn),
+            KeyCode::Char('4') => timeline.toggle_level(Level::Error),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Chart tab keys. Returns `false` when the key should fall through to the
+    /// global bindings.
+    fn handle_chart_key(&mut self, code: KeyCode) -> bool {
+        let chart = &mut self.chart;
+        match code {
+            KeyCode::Char('+') | KeyCode::Char('=') => chart.zoom_in(),
+            KeyCode::Char('-') => chart.zoom_out(),
+            KeyCode::Char('h') => chart.pan_back(),
+            KeyCode::Char('l') => chart.pan_forward(),
+            KeyCode::Char('0') => chart.reset_view(),
+            KeyCode::Char('b') => chart.show_bounds = !chart.show_bounds,
+            KeyCode::Char('s') => {
+                chart.toggle_source();
+                self.rebuild_chart();
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Analysis tab keys. `j`/`k` pick a result row, so the bot to compare against
+    /// moves with `b`. Returns `false` when the key should fall through.
+    fn handle_analysis_key(&mut self, code: KeyCode) -> bool {
+        match code {
+            KeyCode::Down | KeyCode::Char('j') => self.analysis.next_row(),
+            KeyCode::Up | KeyCode::Char('k') => self.analysis.prev_row(),
+            KeyCode::Char('f') => self.analysis.next_table(),
+            KeyCode::Char('s') => self.analysis.cycle_sort(),
+            KeyCode::Char('o') => self.analysis.toggle_order(),
+            KeyCode::Char('b') => self.next_bot(),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Consistency tab keys: `j`/`k` pick a finding and `x` offers its cleanup.
+    /// Returns `false` when the key should fall through.
+    fn handle_consistency_key(&mut self, code: KeyCode) -> Result<bool> {
+        let findings = &self.snapshot.consistency.findings;
+        match code {
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.consistency_row = (self.consistency_row + 1).min(findings.len().saturating_sub(1));
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.consistency_row = self.consistency_row.saturating_sub(1);
+            }
+            KeyCode::Char('x') => match findings.get(self.consistency_row) {
+                Some(finding) => match finding.cleanup.clone() {
+                    Some(cleanup) => {
+                        let risk = cleanup.risk();
+                        self.request_action(ActionTarget::Cleanup(cleanup), risk)?;
+                    }
+                    None => {
+                        self.last_output =
+                            format!("No cleanup for {} {}.", finding.direction.label(), finding.kind);
+                    }
+                },
+                None => self.last_output = String::from("No finding selected."),
+            },
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Backups tab keys: `j`/`k` pick an archive, `x` restores it and `p` prunes
+    /// by the retention settings. Returns `false` when the key should fall through.
+    fn handle_backups_key(&mut self, code: KeyCode) -> Result<bool> {
+        match code {
+            KeyCode::Down | KeyCode::Char('j') => self.backups.next(),
+            KeyCode::Up | KeyCode::Char('k') => self.backups.prev(),
+            KeyCode::Char('x') => {
+                let Some(archive) = self.backups.selected() else {
+                    self.last_output = String::from("No backup selected.");
+                    return Ok(true);
+                };
+                match backups::check_bots_stopped(&self.snapshot) {
+                    Ok(()) => {
+                        let target = ActionTarget::Restore(archive.path.clone());
+                        self.request_action(target, Risk::Confirm)?;
+                    }
+                    Err(err) => self.last_output = format!("Restore refused: {err}."),
+                }
+            }
+            KeyCode::Char('p') => {
+                let pruned = backups::prune(self.retention(), runtime::now_ms());
+                self.backups.reload();
+                self.last_output = format!("Pruned {pruned} backups.");
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn retention(&self) -> Retention {
+        let settings = &self.snapshot.settings;
+        Retention::new(settings.backup_retention_count, settings.backup_retention_days)
+    }
+
+    /// Steps the Exposure reference through the assets the fleet holds.
+    fn cycle_exposure_reference(&mut self) {
+        let names = self.snapshot.exposure.asset_names();
+        if names.is_empty() {
+            return;
+        }
+        let next = names
+            .iter()
+            .position(|n| *n == self.exposure_reference)
+            .map_or(0, |i| (i + 1) % names.len());
+        self.exposure_reference = names[next].clone();
+    }
+
+    fn open_log_view(&mut self) {
+        let Some(bot) = self.selected_bot() else {
+            self.last_output = String::from("No bot selected.");
+            return;
+        };
+        match &bot.identity.out_log {
+            Some(path) => {
+                self.log_view = Some(LogViewer::open(bot.name.clone(), path.clone()));
+            }
+            None => self.last_output = format!("{}: no log file to view.", bot.name),
+        }
+    }
+
+    fn handle_log_view_key(&mut self, code: KeyCode) {
+        let Some(viewer) = self.log_view.as_mut() else {
+            return;
+        };
+        if viewer.input.is_some() {
+            match code {
+                KeyCode::Enter => viewer.commit_input(),
+                KeyCode::Esc => viewer.cancel_input(),
+                KeyCode::Backspace => viewer.input_pop(),
+                KeyCode::Char(c) if !c.is_control() => viewer.input_push(c),
+                _ => {}
+            }
+            return;
+        }
+
+        if scroll_key(&mut viewer.view, code) {
+            return;
+        }
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => self.log_view = None,
+            KeyCode::Char('f') => viewer.view.toggle_follow(),
+            KeyCode::Char('/') => viewer.begin_input(LogInput::Search(String::new())),
+            KeyCode::Char('t') => viewer.begin_input(LogInput::JumpTo(String::new())),
+            KeyCode::Char('n') => viewer.next_match(),
+            KeyCode::Char('N') => viewer.prev_match(),
+            KeyCode::Char('c') => viewer.cycle_category(),
+            KeyCode::Char('a') => self.log_colors = self.log_colors.toggled(),
+            KeyCode::Char('1') => viewer.toggle_level(Level::Debug),
+            KeyCode::Char('2') => viewer.toggle_level(Level::Info),
+            KeyCode::Char('3') => viewer.toggle_level(Level::Warn),
+            KeyCode::Char('4') => viewer.toggle_level(Level::Error),
+            _ => {}
+        }
+    }
+
+    /// Preview tab keys. Returns `None` when the key should fall through to the
+    /// global bindings (tab switching, quit, refresh).
+    fn handle_preview_key(&mut self, code: KeyCode) -> Option<bool> {
+        if let Some(typed) = &mut self.preview.editing {
+            match code {
+                KeyCode::Enter => self.preview.commit_edit(),
+                KeyCode::Esc => self.preview.editing = None,
+                KeyCode::Backspace => {
+                    typed.pop();
+                }
+                KeyCode::Char(c) if !c.is_control() => typed.push(c),
+                _ => {}
+            }
+            return Some(false);
+        }
+
+        match code {
+            KeyCode::Down | KeyCode::Char('j') => self.preview.select_next(),
+            KeyCode::Up | KeyCode::Char('k') => self.preview.select_prev(),
+            KeyCode::Enter | KeyCode::Char('e') => self.preview.begin_edit(),
+            KeyCode::PageDown => self.preview.scroll_by(10),
+            KeyCode::PageUp => self.preview.scroll_by(-10),
+            KeyCode::Char('l') => self.load_preview_from_selected_bot(),
+            _ => return None,
+        }
+        Some(false)
+    }
+
+    fn load_preview_from_selected_bot(&mut self) {
+        let Some(bot) = self.snapshot.bots.get(self.selected_bot) else {
+            self.last_output = String::from("No bot selected to load into preview.");
+            return;
+        };
+        let fallback_start = bot
+            .divergence
+            .as_ref()
+            .ok()
+            .map(|d| d.ideal.reference_price);
+        self.preview
+            .load_from_bot(&bot.name, &bot.config, fallback_start);
+        self.last_output = format!("Preview loaded from {}.", bot.name);
+    }
+
     pub fn run_selected_action(&mut self) -> Result<()> {
         if self.actions.is_empty() {
             self.last_output = String::from("No actions configured.");
@@ -210,35 +717,103 @@ impl App {
 
         self.selected_action %= self.actions.len();
         self.action_list_state.select(Some(self.selected_action));
-        let action = &self.actions[self.selected_action];
-        match action.risk {
-            Risk::Safe => self.execute_action(self.selected_action)?,
-            Risk::Confirm => {
-                self.pending_action = Some(PendingAction::Confirm {
-                    action_index: self.selected_action,
-                });
-            }
+        let risk = self.actions[self.selected_action].risk;
+        self.request_action(ActionTarget::Script(self.selected_action), risk)
+    }
+
+    /// Offers a Danger reset of the selected bot's orders, once its process is stopped.
+    fn request_bot_reset(&mut self) -> Result<()> {
+        let Some(bot) = self.selected_bot() else {
+            self.last_output = String::from("No bot selected.");
+            return Ok(());
+        };
+        match BotReset::for_bot(bot, &self.snapshot.runtime) {
+            Ok(reset) => self.request_action(ActionTarget::ResetBot(reset), Risk::Danger)?,
+            Err(err) => self.last_output = format!("Reset refused: {err}."),
+        }
+        Ok(())
+    }
+
+    /// Runs `Safe` targets straight away and asks for confirmation otherwise.
+    fn request_action(&mut self, target: ActionTarget, risk: Risk) -> Result<()> {
+        match risk {
+            Risk::Safe => self.execute_action(target)?,
+            Risk::Confirm => self.pending_action = Some(PendingAction::Confirm { target }),
             Risk::Danger => {
-                self.pending_action = Some(PendingAction::Danger {
-                    action_index: self.selected_action,
-                    typed: String::new(),
-                });
+                let plan = self.deletion_plan(&target);
+                self.pending_action = Some(PendingAction::Review { target, plan });
             }
         }
         Ok(())
     }
 
+    /// What a Danger target would remove, as of the current snapshot.
+    fn deletion_plan(&self, target: &ActionTarget) -> DeletionPlan {
+        let (paths, note) = match target {
+            ActionTarget::Cleanup(Cleanup::DeleteProcess { name, .. }) => (
+                vec![],
+                Some(format!("No files deleted; {name} is removed from pm2 (its logs stay).")),
+            ),
+            ActionTarget::ResetBot(reset) if reset.other_entries() > 0 => (
+                vec![],
+                Some(format!(
+                    "No files deleted; bots.{} is removed from {} ({} other entries kept), after a copy goes to profiles/backups.",
+                    reset.bot_key,
+                    reset.orders_file.display(),
+                    reset.other_entries()
+                )),
+            ),
+            _ => (self.affected_paths(target), None),
+        };
+        DeletionPlan::scan(paths, &self.snapshot, note)
+    }
+
+    /// Files a target deletes or rewrites, relative to the repo root.
+    fn affected_paths(&self, target: &ActionTarget) -> Vec<PathBuf> {
+        match target {
+            ActionTarget::Script(index) => self
+                .actions
+                .get(*index)
+                .map(|a| a.deletes.iter().flat_map(dryrun::expand).collect())
+                .unwrap_or_default(),
+            ActionTarget::Cleanup(
+                Cleanup::RemoveOrdersFile(path) | Cleanup::RemoveLog(path),
+            ) => vec![path.clone()],
+            ActionTarget::Cleanup(Cleanup::DeleteProcess { .. }) => vec![],
+            ActionTarget::ResetBot(reset) => vec![reset.orders_file.clone()],
+            ActionTarget::Restore(_) => vec![],
+        }
+    }
+
+    fn target_risk(&self, target: &ActionTarget) -> Risk {
+        match target {
+            ActionTarget::Script(index) => self.actions.get(*index).map_or(Risk::Safe, |a| a.risk),
+            ActionTarget::Cleanup(cleanup) => cleanup.risk(),
+            ActionTarget::ResetBot(_) => Risk::Danger,
+            ActionTarget::Restore(_) => Risk::Confirm,
+        }
+    }
+
+    pub fn target_name(&self, target: &ActionTarget) -> String {
+        match target {
+            ActionTarget::Script(index) => self.actions[*index].name.to_string(),
+            ActionTarget::Cleanup(cleanup) => cleanup.describe(),
+            ActionTarget::ResetBot(reset) => reset.describe(),
+            ActionTarget::Restore(path) => format!("Restore {}", path.display()),
+        }
+    }
+
     fn handle_pending_key(&mut self, code: KeyCode) -> Result<bool> {
         let Some(pending) = &mut self.pending_action else {
             return Ok(false);
         };
 
         match pending {
-            PendingAction::Confirm { action_index } => match code {
+            PendingAction::Confirm { target } => match code {
                 KeyCode::Char('y') | KeyCode::Char('Y') => {
-                    let index = *action_index;
+                    let target = target.clone();
                     self.pending_action = None;
-                    self.execute_action(index)?;
+                    self.execute_action(target)?;
                 }
                 KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
                     self.pending_action = None;
@@ -246,7 +821,23 @@ impl App {
                 }
                 _ => {}
             },
-            PendingAction::Danger { action_index, typed } => match code {
+            PendingAction::Review { target, plan } => match code {
+                KeyCode::Enter => {
+                    let target = target.clone();
+                    let plan = std::mem::take(plan);
+                    self.pending_action = Some(PendingAction::Danger {
+                        target,
+                        plan,
+                        typed: String::new(),
+                    });
+                }
+                KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => {
+                    self.pending_action = None;
+                    self.last_output = String::from("Danger action cancelled.");
+                }
+                _ => {}
+            },
+            PendingAction::Danger { target, typed, .. } => match code {
                 KeyCode::Esc => {
                     self.pending_action = None;
                     self.last_output = String::from("Danger action cancelled.");
@@ -255,19 +846,26 @@ impl App {
                     typed.pop();
                 }
                 KeyCode::Enter => {
-                    if typed == DANGER_CONFIRM_TOKEN {
-                        let index = *action_index;
+                    if typed == target.confirm_token() {
+                        let target = target.clone();
                         self.pending_action = None;
-                        self.execute_action(index)?;
+                        self.execute_action(target)?;
                     } else {
                         self.last_output = format!(
-                            "Confirmation token mismatch. Type {DANGER_CONFIRM_TOKEN} and press Enter."
+                            "Confirmation token mismatch. Type {} and press Enter.",
+                            target.confirm_token()
                         );
                     }
                 }
                 KeyCode::Char(c) => {
-                    if c.is_ascii_alphanumeric() && typed.len() < DANGER_CONFIRM_TOKEN.len() {
-                        typed.push(c.to_ascii_uppercase());
+                    #[allow(clippy::collapsible_match)]
+                    if !c.is_control() && typed.chars().count() < target.confirm_token().chars().count() {
+                        // The generic token is accepted in any case; bot names must match exactly.
+                        if target.confirm_token() == DANGER_CONFIRM_TOKEN {
+                            typed.push(c.to_ascii_uppercase());
+                        } else {
+                            typed.push(c);
+                        }
                     }
                 }
                 _ => {}
@@ -277,24 +875,104 @@ impl App {
         Ok(false)
     }
 
-    fn execute_action(&mut self, index: usize) -> Result<()> {
-        if self.actions.is_empty() {
+    fn execute_action(&mut self, target: ActionTarget) -> Result<()> {
+        if let Some(reason) = self.recheck(&target)? {
+            self.last_output = reason;
             return Ok(());
         }
-        let action = &self.actions[index];
-        match action.execute() {
+        // Every Danger action leaves a copy of what it touches in profiles/backups.
+        let mut backup = None;
+        if matches!(self.target_risk(&target), Risk::Danger) {
+            let name = self.target_name(&target);
+            let label = match &target {
+                ActionTarget::Script(index) => self.actions[*index].name.to_string(),
+                ActionTarget::Cleanup(Cleanup::RemoveOrdersFile(_)) => String::from("remove-orders"),
+                ActionTarget::Cleanup(Cleanup::RemoveLog(_)) => String::from("remove-log"),
+                ActionTarget::ResetBot(reset) => format!("reset-{}", reset.name),
+                _ => name.clone(),
+            };
+            match backups::create(&label, &self.affected_paths(&target), runtime::now_ms()) {
+                Ok(path) => backup = path,
+                Err(err) => {
+                    self.last_output = format!("Backup failed; {name} not run.\n{err}");
+                    return Ok(());
+                }
+            }
+        }
+
+        let result = match &target {
+            ActionTarget::Script(index) => match self.actions.get(*index) {
+                Some(action) => action.execute(),
+                None => return Ok(()),
+            },
+            ActionTarget::Cleanup(cleanup) => cleanup.execute(),
+            ActionTarget::ResetBot(reset) => reset.execute(),
+            ActionTarget::Restore(path) => backups::restore(path, runtime::now_ms()),
+        };
+        let backup = backup.map(|path| format!("Backup: {}\n", path.display()));
+        match result {
             Ok(output) => {
-                self.last_output = output;
+                self.last_output = format!("{}{output}", backup.unwrap_or_default());
                 let _ = self.reload_snapshot(false);
             }
             Err(err) => {
-                self.last_output = err.to_string();
+                self.last_output = format!("{}{err}", backup.unwrap_or_default());
             }
         }
+        backups::prune(self.retention(), runtime::now_ms());
+        self.backups.reload();
         Ok(())
     }
 
+    /// Re-validates a target against a fresh snapshot, since things may have
+    /// changed while the modal was open. Returns why it must not run.
+    fn recheck(&mut self, target: &ActionTarget) -> Result<Option<String>> {
+        if matches!(target, ActionTarget::Script(_)) {
+            return Ok(None);
+        }
+        self.reload_snapshot(false)?;
+        Ok(match target {
+            ActionTarget::Script(_) => None,
+            ActionTarget::Cleanup(cleanup) => (!self.snapshot.consistency.contains(cleanup))
+                .then(|| format!("{} skipped: no longer an orphan.", cleanup.describe())),
+            ActionTarget::ResetBot(reset) => {
+                let current = self
+                    .snapshot
+                    .bots
+                    .iter()
+                    .find(|b| b.name == reset.name)
+                    .ok_or_else(|| format!("{}: no longer in bots.json", reset.name))
+                    .and_then(|bot| BotReset::for_bot(bot, &self.snapshot.runtime));
+                match current {
+                    Ok(current) if current == *reset => None,
+                    Ok(_) => Some(format!(
+                        "{} skipped: the bot's orders file changed.",
+                        reset.describe()
+                    )),
+                    Err(err) => Some(format!("Reset refused: {err}.")),
+                }
+            }
+            ActionTarget::Restore(_) => backups::check_bots_stopped(&self.snapshot)
+                .err()
+                .map(|err| format!("Restore refused: {err}.")),
+        })
+    }
+
     pub fn selected_bot(&self) -> Option<&state::BotStatus> {
         self.snapshot.bots.get(self.selected_bot)
     }
 }
+
+/// Scrolling keys shared by the log viewer and the timeline.
+fn scroll_key(view: &mut LogScroll, code: KeyCode) -> bool {
+    match code {
+        KeyCode::Up | KeyCode::Char('k') => view.scroll_up(1),
+        KeyCode::Down | KeyCode::Char('j') => view.scroll_down(1),
+        KeyCode::PageUp => view.scroll_up(view.page()),
+        KeyCode::PageDown => view.scroll_down(view.page()),
+        KeyCode::Home | KeyCode::Char('g') => view.top(),
+        KeyCode::End | KeyCode::Char('G') => view.bottom(),
+        _ => return false,
+    }
+    true
+}
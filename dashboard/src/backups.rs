@@ -0,0 +1,246 @@
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+    process::Command,
+    time::UNIX_EPOCH,
+};
+
+use anyhow::{anyhow, Result};
+
+use crate::{identity, logline, reset, state::Snapshot};
+
+const BACKUP_DIR: &str = "profiles/backups";
+const DAY_MS: i64 = 86_400_000;
+const MAX_LABEL: usize = 40;
+
+/// A `profiles/backups/backup-{YYYYMMDD-HHMMSS}-{label}.tar.gz` archive.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Archive {
+    pub path: PathBuf,
+    pub label: String,
+    pub size: u64,
+    pub created_ms: i64,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Retention {
+    /// Newest archives always kept.
+    pub keep: usize,
+    /// Older archives beyond `keep` are pruned after this age.
+    pub max_age_ms: i64,
+}
+
+impl Retention {
+    pub fn new(keep: usize, max_age_days: u64) -> Self {
+        Self {
+            keep,
+            max_age_ms: max_age_days as i64 * DAY_MS,
+        }
+    }
+}
+
+/// Archives `files` (paths relative to the repo root, under `profiles/`) before a
+/// Danger action. Returns `None` when there is nothing to save.
+pub fn create(label: &str, files: &[PathBuf], now_ms: i64) -> Result<Option<PathBuf>> {
+    let files = files
+        .iter()
+        .filter(|p| is_profiles_path(p) && p.is_file())
+        .collect::<Vec<_>>();
+    if files.is_empty() {
+        return Ok(None);
+    }
+    fs::create_dir_all(BACKUP_DIR)?;
+    let stamp = logline::format_epoch_ms(now_ms)
+        .chars()
+        .filter(|c| c.is_ascii_digit() || *c == ' ')
+        .take(15)
+        .collect::<String>()
+        .replace(' ', "-");
+    let label = identity::sanitize_key(label);
+    let label = label.get(..MAX_LABEL).unwrap_or(&label).trim_end_matches('-');
+    let mut path = PathBuf::from(format!("{BACKUP_DIR}/backup-{stamp}-{label}.tar.gz"));
+    let mut n = 1;
+    while path.exists() {
+        n += 1;
+        path = PathBuf::from(format!("{BACKUP_DIR}/backup-{stamp}-{label}-{n}.tar.gz"));
+    }
+    let output = Command::new("tar")
+        .arg("-czf")
+        .arg(&path)
+        .arg("--")
+        .args(&files)
+        .output()?;
+    if !output.status.success() {
+        let _ = fs::remove_file(&path);
+        return Err(anyhow!(
+            "tar failed (code {:?})\n{}",
+            output.status.code(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(Some(path))
+}
+
+/// Archives in `profiles/backups`, newest first.
+pub fn list() -> Vec<Archive> {
+    let Ok(entries) = fs::read_dir(BACKUP_DIR) else {
+        return vec![];
+    };
+    let mut archives = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let name = path.file_name()?.to_str()?.to_string();
+            let stem = name.strip_prefix("backup-")?.strip_suffix(".tar.gz")?;
+            let meta = entry.metadata().ok()?;
+            let created_ms = meta
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |d| d.as_millis() as i64);
+            // `YYYYMMDD-HHMMSS-` precedes the label.
+            let label = stem.get(16..).unwrap_or(stem).to_string();
+            Some(Archive {
+                path,
+                label,
+                size: meta.len(),
+                created_ms,
+            })
+        })
+        .collect::<Vec<_>>();
+    archives.sort_by(|a, b| b.created_ms.cmp(&a.created_ms).then(b.path.cmp(&a.path)));
+    archives
+}
+
+/// Archives the retention policy drops; `archives` is newest first.
+pub fn expired(archives: &[Archive], retention: Retention, now_ms: i64) -> Vec<&Archive> {
+    archives
+        .iter()
+        .skip(retention.keep)
+        .filter(|a| now_ms - a.created_ms > retention.max_age_ms)
+        .collect()
+}
+
+/// Removes expired archives and returns how many went.
+pub fn prune(retention: Retention, now_ms: i64) -> usize {
+    let archives = list();
+    expired(&archives, retention, now_ms)
+        .into_iter()
+        .filter(|a| fs::remove_file(&a.path).is_ok())
+        .count()
+}
+
+/// Unpacks an archive over `profiles/`. Entries outside `profiles/` refuse the
+/// whole archive; the files it would overwrite are archived first.
+pub fn restore(archive: &Path, now_ms: i64) -> Result<String> {
+    let output = Command::new("tar").arg("-tzf").arg(archive).output()?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "{}: unreadable archive\n{}",
+            archive.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    let entries = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(PathBuf::from)
+        .collect::<Vec<_>>();
+    if let Some(bad) = entries.iter().find(|p| !is_profiles_path(p)) {
+        return Err(anyhow!(
+            "{}: entry {} is outside profiles/; not restored",
+            archive.display(),
+            bad.display()
+        ));
+    }
+    let previous = create("pre-restore", &entries, now_ms)?;
+
+    let output = Command::new("tar").arg("-xzf").arg(archive).output()?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "[confirm] restore {} failed (code {:?})\n{}",
+            archive.display(),
+            output.status.code(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    let files = entries.iter().filter(|p| !p.to_string_lossy().ends_with('/')).count();
+    let mut body = format!("restored {files} files");
+    if let Some(previous) = previous {
+        body.push_str(&format!("; overwritten files saved to {}", previous.display()));
+    }
+    Ok(format!("[confirm] Restore {}\n{body}", archive.display()))
+}
+
+/// Restores are only offered while no bot can write its files back.
+pub fn check_bots_stopped(snapshot: &Snapshot) -> Result<(), String> {
+    snapshot
+        .bots
+        .iter()
+        .try_for_each(|bot| reset::check_stopped(bot, &snapshot.runtime))
+}
+
+/// Relative, inside `profiles/`, without `..`.
+fn is_profiles_path(path: &Path) -> bool {
+    path.starts_with("profiles")
+        && path
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+}
+
+#[derive(Debug, Default)]
+pub struct BackupState {
+    pub archives: Vec<Archive>,
+    pub selected: usize,
+}
+
+impl BackupState {
+    pub fn reload(&mut self) {
+        self.archives = list();
+        self.selected = self.selected.min(self.archives.len().saturating_sub(1));
+    }
+
+    pub fn selected(&self) -> Option<&Archive> {
+        self.archives.get(self.selected)
+    }
+
+    pub fn next(&mut self) {
+        self.selected = (self.selected + 1).min(self.archives.len().saturating_sub(1));
+    }
+
+    pub fn prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn archive(created_ms: i64) -> Archive {
+        Archive {
+            path: PathBuf::from(format!("{created_ms}")),
+            label: String::from("clear-logs"),
+            size: 1,
+            created_ms,
+        }
+    }
+
+    #[test]
+    fn retention_keeps_newest_and_recent_archives() {
+        let now = 100 * DAY_MS;
+        // Newest first: 1, 10, 40 and 50 days old.
+        let archives = [99, 90, 60, 50].map(|d| archive(d * DAY_MS));
+        let expired = expired(&archives, Retention::new(1, 30), now);
+        assert_eq!(expired, vec![&archives[2], &archives[3]]);
+        // Old archives inside `keep` survive.
+        assert!(super::expired(&archives, Retention::new(4, 30), now).is_empty());
+    }
+
+    #[test]
+    fn only_relative_profiles_paths_are_archived() {
+        assert!(is_profiles_path(Path::new("profiles/orders/a-0.json")));
+        assert!(!is_profiles_path(Path::new("/etc/passwd")));
+        assert!(!is_profiles_path(Path::new("profiles/../bot.js")));
+        assert!(!is_profiles_path(Path::new("modules/x.js")));
+    }
+}
@@ -0,0 +1,188 @@
+use std::sync::Arc;
+
+use crate::{
+    fees::FeeLedger,
+    fills::Fill,
+    grid::Side,
+    market::{self, MarketFile},
+    state::BotStatus,
+};
+
+const HOUR_MS: i64 = 3_600_000;
+const DEFAULT_SPAN_MS: i64 = 7 * 24 * HOUR_MS;
+const MIN_SPAN_MS: i64 = HOUR_MS;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChartSource {
+    Market,
+    Fills,
+}
+
+impl ChartSource {
+    pub fn label(self) -> &'static str {
+        match self {
+            ChartSource::Market => "market",
+            ChartSource::Fills => "fills",
+        }
+    }
+}
+
+/// Everything the chart draws for one bot, prices in the bot's orientation.
+#[derive(Clone, Debug)]
+pub struct ChartData {
+    pub bot: String,
+    pub source_label: String,
+    /// `(ts_ms, price)` oldest first.
+    pub prices: Vec<(i64, f64)>,
+    pub fills: Vec<Fill>,
+    pub buy_levels: Vec<f64>,
+    pub sell_levels: Vec<f64>,
+    pub min_price: Option<f64>,
+    pub max_price: Option<f64>,
+}
+
+impl ChartData {
+    /// Time range of the price series; fills outside it are only drawn when the
+    /// window is over them.
+    fn extent(&self) -> Option<(i64, i64)> {
+        let times = self.prices.iter().map(|(ts, _)| *ts);
+        let (min, max) = times.fold((i64::MAX, i64::MIN), |(lo, hi), ts| (lo.min(ts), hi.max(ts)));
+        (min <= max).then_some((min, max))
+    }
+}
+
+/// Price chart of the selected bot. The window is `span_ms` wide and ends
+/// `pan_ms` before the newest point, so a zero pan follows new data.
+#[derive(Debug)]
+pub struct ChartState {
+    pub source: ChartSource,
+    pub span_ms: i64,
+    pub pan_ms: i64,
+    /// Stretch the price axis to `minPrice`/`maxPrice` rather than fitting the data.
+    pub show_bounds: bool,
+    pub data: Result<ChartData, String>,
+}
+
+impl Default for ChartState {
+    fn default() -> Self {
+        Self {
+            source: ChartSource::Market,
+            span_ms: DEFAULT_SPAN_MS,
+            pan_ms: 0,
+            show_bounds: true,
+            data: Err(String::from("not loaded")),
+        }
+    }
+}
+
+impl ChartState {
+    /// `ledger` is the bot's parsed log from the `FeeTracker`.
+    pub fn rebuild(
+        &mut self,
+        bot: &BotStatus,
+        market_files: &[Arc<MarketFile>],
+        ledger: Option<&FeeLedger>,
+    ) {
+        let fills = ledger.map(|l| l.fills.clone()).unwrap_or_default();
+        let (prices, source_label) = match self.source {
+            ChartSource::Market => {
+                match market::find_series(market_files, &bot.asset_a, &bot.asset_b) {
+                    Some(series) => (series.closes(), series.label()),
+                    None => {
+                        self.data = Err(format!(
+                            "{}: no market_adapter data for {} (press s for fill prices)",
+                            bot.name, bot.pair
+                        ));
+                        return;
+                    }
+                }
+            }
+            ChartSource::Fills => (
+                fills.iter().map(|f| (f.ts_ms, f.price)).collect(),
+                String::from("fill prices"),
+            ),
+        };
+        if prices.is_empty() && fills.is_empty() {
+            self.data = Err(format!("{}: no {} to chart", bot.name, source_label));
+            return;
+        }
+
+        let active_levels = |side: Side| {
+            bot.grid
+                .iter()
+                .filter(|s| s.side() == Some(side))
+                .filter(|s| matches!(s.state.as_deref(), Some("active" | "partial")))
+                .map(|s| s.price)
+                .filter(|p| *p > 0.0)
+                .collect::<Vec<_>>()
+        };
+        self.data = Ok(ChartData {
+            bot: bot.name.clone(),
+            source_label,
+            prices,
+            fills,
+            buy_levels: active_levels(Side::Buy),
+            sell_levels: active_levels(Side::Sell),
+            min_price: bot.grid_center.and_then(|c| bot.config.min_price(c)),
+            max_price: bot.grid_center.and_then(|c| bot.config.max_price(c)),
+        });
+        self.clamp_pan();
+    }
+
+    /// `(start_ms, end_ms)` of the visible window.
+    pub fn window(&self) -> Option<(i64, i64)> {
+        let (_, latest) = self.data.as_ref().ok()?.extent()?;
+        let end = latest - self.pan_ms;
+        Some((end - self.span_ms, end))
+    }
+
+    fn clamp_pan(&mut self) {
+        let max_pan = match self.data.as_ref().ok().and_then(ChartData::extent) {
+            Some((earliest, latest)) => (latest - earliest - self.span_ms).max(0),
+            None => 0,
+        };
+        self.pan_ms = self.pan_ms.clamp(0, max_pan);
+    }
+
+    pub fn zoom_in(&mut self) {
+        self.span_ms = (self.span_ms / 2).max(MIN_SPAN_MS);
+        self.clamp_pan();
+    }
+
+    /// Zooms out until the whole history fits, but no further.
+    pub fn zoom_out(&mut self) {
+        let full = self
+            .data
+            .as_ref()
+            .ok()
+            .and_then(ChartData::extent)
+            .map(|(earliest, latest)| (latest - earliest).max(MIN_SPAN_MS))
+            .unwrap_or(DEFAULT_SPAN_MS);
+        self.span_ms = (self.span_ms * 2).min(full.max(self.span_ms));
+        self.clamp_pan();
+    }
+
+    /// Moves the window back in time by a quarter of its width.
+    pub fn pan_back(&mut self) {
+        self.pan_ms += (self.span_ms / 4).max(1);
+        self.clamp_pan();
+    }
+
+    pub fn pan_forward(&mut self) {
+        self.pan_ms -= (self.span_ms / 4).max(1);
+        self.clamp_pan();
+    }
+
+    pub fn reset_view(&mut self) {
+        self.span_ms = DEFAULT_SPAN_MS;
+        self.pan_ms = 0;
+    }
+
+    pub fn toggle_source(&mut self) {
+        self.source = match self.source {
+            ChartSource::Market => ChartSource::Fills,
+            ChartSource::Fills => ChartSource::Market,
+        };
+        self.pan_ms = 0;
+    }
+}
@@ -0,0 +1,508 @@
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+
+use crate::{
+    actions::Risk,
+    identity::SYSTEM_PROCESSES,
+    runtime::RuntimeStatus,
+    state::BotStatus,
+};
+
+const ORDERS_DIR: &str = "profiles/orders";
+const LOGS_DIR: &str = "profiles/logs";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// Exists on disk or in the runtime but belongs to no `bots.json` entry.
+    Orphan,
+    /// A `bots.json` entry lacks it.
+    Missing,
+}
+
+impl Direction {
+    pub fn label(self) -> &'static str {
+        match self {
+            Direction::Orphan => "orphan",
+            Direction::Missing => "missing",
+        }
+    }
+}
+
+/// Removal offered for an orphan. Each one is re-checked against a fresh report
+/// right before it runs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Cleanup {
+    RemoveOrdersFile(PathBuf),
+    RemoveLog(PathBuf),
+    /// `pm2 delete`; `online` processes are stopped by it.
+    DeleteProcess { name: String, online: bool },
+}
+
+impl Cleanup {
+    /// Orders files hold grid state the bot cannot rebuild from chain data, and an
+    /// online process is still trading, so both need the typed token.
+    pub fn risk(&self) -> Risk {
+        match self {
+            Cleanup::RemoveOrdersFile(_) => Risk::Danger,
+            Cleanup::RemoveLog(_) => Risk::Confirm,
+            Cleanup::DeleteProcess { online, .. } => {
+                if *online {
+                    Risk::Danger
+                } else {
+                    Risk::Confirm
+                }
+            }
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Cleanup::RemoveOrdersFile(path) => format!("Remove orders file {}", path.display()),
+            Cleanup::RemoveLog(path) => format!("Remove log {}", path.display()),
+            Cleanup::DeleteProcess { name, online: true } => {
+                format!("pm2 delete {name} (online: stops it)")
+            }
+            Cleanup::DeleteProcess { name, .. } => format!("pm2 delete {name}"),
+        }
+    }
+
+    pub fn execute(&self) -> Result<String> {
+        let risk = self.risk().label();
+        let name = self.describe();
+        match self {
+            Cleanup::RemoveOrdersFile(path) | Cleanup::RemoveLog(path) => {
+                fs::remove_file(path).map_err(|err| anyhow!("[{risk}] {name} failed\n{err}"))?;
+                Ok(format!("[{risk}] {name}\nremoved"))
+            }
+            Cleanup::DeleteProcess { name: process, .. } => {
+                let output = Command::new("pm2").args(["delete", process]).output()?;
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                if output.status.success() {
+                    Ok(format!("[{risk}] {name}\n{}", stdout.trim()))
+                } else {
+                    Err(anyhow!(
+                        "[{risk}] {name} failed (code {:?})\n{}",
+                        output.status.code(),
+                        stderr.trim()
+                    ))
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Finding {
+    pub direction: Direction,
+    /// `orders file`, `orders key`, `log` or `process`.
+    pub kind: &'static str,
+    pub subject: String,
+    pub detail: String,
+    pub cleanup: Option<Cleanup>,
+}
+
+/// Cross-reference of `bots.json` entries, `profiles/orders/*.json` files and the
+/// bot keys inside them, `profiles/logs/*.log` files and runtime processes.
+#[derive(Clone, Debug, Default)]
+pub struct ConsistencyReport {
+    pub entries: usize,
+    pub orders_files: usize,
+    pub log_files: usize,
+    pub processes: usize,
+    /// Orphans first, then missing pieces.
+    pub findings: Vec<Finding>,
+}
+
+impl ConsistencyReport {
+    pub fn count(&self, direction: Direction) -> usize {
+        self.findings.iter().filter(|f| f.direction == direction).count()
+    }
+
+    pub fn contains(&self, cleanup: &Cleanup) -> bool {
+        self.findings.iter().any(|f| f.cleanup.as_ref() == Some(cleanup))
+    }
+}
+
+pub fn check(bots: &[BotStatus], runtime: &RuntimeStatus) -> ConsistencyReport {
+    check_in(bots, runtime, Path::new(ORDERS_DIR), Path::new(LOGS_DIR))
+}
+
+fn check_in(
+    bots: &[BotStatus],
+    runtime: &RuntimeStatus,
+    orders_dir: &Path,
+    logs_dir: &Path,
+) -> ConsistencyReport {
+    let mut report = ConsistencyReport {
+        entries: bots.len(),
+        ..ConsistencyReport::default()
+    };
+    let mut missing = Vec::new();
+    let keys = bots
+        .iter()
+        .map(|b| b.identity.bot_key.as_str())
+        .collect::<HashSet<_>>();
+
+    // Orders: the file name is the key the bot reads (`{botKey}.json`).
+    let mut found_keys = HashSet::new();
+    let mut unreadable = HashSet::new();
+    for path in list_files(orders_dir, "json") {
+        report.orders_files += 1;
+        let stem = file_stem(&path);
+        let inner = fs::read_to_string(&path)
+            .ok()
+            .and_then(|raw| serde_json::from_str::<Value>(&raw).ok())
+            .map(|v| {
+                v.get("bots")
+                    .and_then(Value::as_object)
+                    .map(|bots| bots.keys().cloned().collect::<Vec<_>>())
+                    .unwrap_or_default()
+            });
+        if !keys.contains(stem.as_str()) {
+            // A moved entry's file is likely that bot's grid under its old key, so
+            // it is never offered for removal.
+            let moved = bots
+                .iter()
+                .find(|b| key_base(&b.identity.bot_key) == key_base(&stem));
+            let (detail, cleanup) = match moved {
+                Some(bot) => (
+                    format!(
+                        "no entry has key {stem}; {} now uses {} (entry moved in bots.json? \
+                         stop the bot and rename the file by hand)",
+                        bot.name, bot.identity.bot_key
+                    ),
+                    None,
+                ),
+                None => (
+                    format!("no entry has key {stem}"),
+                    Some(Cleanup::RemoveOrdersFile(path.clone())),
+                ),
+            };
+            report.findings.push(Finding {
+                direction: Direction::Orphan,
+                kind: "orders file",
+                subject: path.display().to_string(),
+                detail,
+                cleanup,
+            });
+            continue;
+        }
+        let Some(inner) = inner else {
+            unreadable.insert(stem);
+            continue;
+        };
+        for key in inner {
+            if key == stem {
+                found_keys.insert(key);
+            } else {
+                report.findings.push(Finding {
+                    direction: Direction::Orphan,
+                    kind: "orders key",
+                    subject: key,
+                    detail: format!("stray key in {}; the bot ignores it (edit by hand)", path.display()),
+                    cleanup: None,
+                });
+            }
+        }
+    }
+
+    // Logs: pm2.js writes `{name}.log` and `{name}-error.log` per app.
+    let mut claimed_logs = HashSet::new();
+    let names = bots
+        .iter()
+        .filter_map(|b| b.identity.process_name.as_deref())
+        .chain(SYSTEM_PROCESSES);
+    for name in names {
+        claimed_logs.insert(format!("{name}.log"));
+        claimed_logs.insert(format!("{name}-error.log"));
+    }
+    for bot in bots {
+        for path in [&bot.identity.out_log, &bot.identity.error_log].into_iter().flatten() {
+            if let Some(file) = path.file_name() {
+                claimed_logs.insert(file.to_string_lossy().into_owned());
+            }
+        }
+    }
+    for path in list_files(logs_dir, "log") {
+        report.log_files += 1;
+        let file = path
+            .file_name()
+            .map(|f| f.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        if claimed_logs.contains(&file) {
+            continue;
+        }
+        let size = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        let owner = file.trim_end_matches(".log").trim_end_matches("-error");
+        let detail = if runtime.processes.contains_key(owner) {
+            format!("{size} bytes; written by orphan process {owner}")
+        } else {
+            format!("{size} bytes; no entry or process named {owner}")
+        };
+        report.findings.push(Finding {
+            direction: Direction::Orphan,
+            kind: "log",
+            subject: path.display().to_string(),
+            detail,
+            cleanup: Some(Cleanup::RemoveLog(path)),
+        });
+    }
+
+    // Processes.
+    let claimed_processes = bots
+        .iter()
+        .filter_map(|b| b.identity.process_name.as_deref())
+        .chain(SYSTEM_PROCESSES)
+        .collect::<HashSet<_>>();
+    let mut processes = runtime.processes.iter().collect::<Vec<_>>();
+    processes.sort_by(|a, b| a.0.cmp(b.0));
+    report.processes = processes.len();
+    for (name, process) in processes {
+        if claimed_processes.contains(name.as_str()) {
+            continue;
+        }
+        let online = process.status == "online";
+        report.findings.push(Finding {
+            direction: Direction::Orphan,
+            kind: "process",
+            subject: name.clone(),
+            detail: format!("{} {}", runtime.backend, process.status),
+            cleanup: (runtime.backend == "pm2").then(|| Cleanup::DeleteProcess {
+                name: name.clone(),
+                online,
+            }),
+        });
+    }
+
+    // The other direction: what active entries lack.
+    for bot in bots.iter().filter(|b| b.active) {
+        let key = &bot.identity.bot_key;
+        let orders_path = orders_dir.join(format!("{key}.json"));
+        if !orders_path.exists() {
+            missing.push(Finding {
+                direction: Direction::Missing,
+                kind: "orders file",
+                subject: bot.name.clone(),
+                detail: format!("{} absent (grid not persisted yet?)", orders_path.display()),
+                cleanup: None,
+            });
+        } else if unreadable.contains(key) {
+            missing.push(Finding {
+                direction: Direction::Missing,
+                kind: "orders key",
+                subject: bot.name.clone(),
+                detail: format!("{} is not valid JSON", orders_path.display()),
+                cleanup: None,
+            });
+        } else if !found_keys.contains(key) {
+            missing.push(Finding {
+                direction: Direction::Missing,
+                kind: "orders key",
+                subject: bot.name.clone(),
+                detail: format!("{} has no bots.{key}", orders_path.display()),
+                cleanup: None,
+            });
+        }
+        let Some(process_name) = &bot.identity.process_name else {
+            continue;
+        };
+        if runtime.online && !runtime.processes.contains_key(process_name) {
+            missing.push(Finding {
+                direction: Direction::Missing,
+                kind: "process",
+                subject: bot.name.clone(),
+                detail: format!("no {} process {process_name}", runtime.backend),
+                cleanup: None,
+            });
+        }
+        if bot.identity.out_log.is_none() {
+            missing.push(Finding {
+                direction: Direction::Missing,
+                kind: "log",
+                subject: bot.name.clone(),
+                detail: format!("{}/{process_name}.log absent", logs_dir.display()),
+                cleanup: None,
+            });
+        }
+    }
+    report.findings.extend(missing);
+    report
+}
+
+fn list_files(dir: &Path, extension: &str) -> Vec<PathBuf> {
+    let mut files = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.is_file() && p.extension().is_some_and(|e| e == extension))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// `createBotKey` appends the entry index; without it a key survives reordering.
+fn key_base(key: &str) -> &str {
+    match key.rsplit_once('-') {
+        Some((base, index)) if index.chars().all(|c| c.is_ascii_digit()) => base,
+        _ => key,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    use crate::{runtime::ProcessRecord, testutil::ScratchDir};
+
+    fn scratch_dir(tag: &str) -> ScratchDir {
+        let dir = ScratchDir::new(tag);
+        fs::create_dir_all(dir.join("orders")).unwrap();
+        fs::create_dir_all(dir.join("logs")).unwrap();
+        dir
+    }
+
+    fn pm2(names: &[&str]) -> RuntimeStatus {
+        let processes = names
+            .iter()
+            .map(|name| {
+                let record = ProcessRecord {
+                    name: name.to_string(),
+                    status: String::from("online"),
+                    ..ProcessRecord::default()
+                };
+                (name.to_string(), record)
+            })
+            .collect::<HashMap<_, _>>();
+        RuntimeStatus {
+            backend: "pm2",
+            online: true,
+            processes,
+        }
+    }
+
+    fn write_orders(dir: &Path, stem: &str, keys: &[&str]) -> PathBuf {
+        let bots = keys
+            .iter()
+            .map(|k| (k.to_string(), serde_json::json!({ "grid": [] })))
+            .collect::<serde_json::Map<_, _>>();
+        let path = dir.join("orders").join(format!("{stem}.json"));
+        fs::write(&path, serde_json::json!({ "bots": bots }).to_string()).unwrap();
+        path
+    }
+
+    fn finding<'a>(report: &'a ConsistencyReport, direction: Direction, kind: &str) -> Vec<&'a Finding> {
+        report
+            .findings
+            .iter()
+            .filter(|f| f.direction == direction && f.kind == kind)
+            .collect()
+    }
+
+    #[test]
+    fn orphan_orders_file_and_stray_key() {
+        let dir = scratch_dir("consistency-orders");
+        let bots = [BotStatus::stub("alpha", "alpha-0")];
+        write_orders(&dir, "alpha-0", &["alpha-0", "ghost-3"]);
+        let orphan = write_orders(&dir, "gone-1", &["gone-1"]);
+
+        let report = check_in(&bots, &pm2(&["alpha"]), &dir.join("orders"), &dir.join("logs"));
+        assert_eq!(report.orders_files, 2);
+        let files = finding(&report, Direction::Orphan, "orders file");
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].cleanup, Some(Cleanup::RemoveOrdersFile(orphan)));
+        let keys = finding(&report, Direction::Orphan, "orders key");
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].subject, "ghost-3");
+        assert_eq!(keys[0].cleanup, None);
+        assert!(finding(&report, Direction::Missing, "orders key").is_empty());
+    }
+
+    #[test]
+    fn moved_entry_file_is_not_offered_for_removal() {
+        let dir = scratch_dir("consistency-moved");
+        // The entry moved from index 0 to 2; its grid is still under the old key.
+        let bots = [BotStatus::stub("alpha", "alpha-2")];
+        write_orders(&dir, "alpha-0", &["alpha-0"]);
+
+        let report = check_in(&bots, &pm2(&["alpha"]), &dir.join("orders"), &dir.join("logs"));
+        let files = finding(&report, Direction::Orphan, "orders file");
+        assert_eq!(files.len(), 1);
+        assert!(files[0].detail.contains("alpha now uses alpha-2"));
+        assert_eq!(files[0].cleanup, None);
+        assert_eq!(finding(&report, Direction::Missing, "orders file").len(), 1);
+    }
+
+    #[test]
+    fn orphan_log_and_process() {
+        let dir = scratch_dir("consistency-logs");
+        let bots = [BotStatus::stub("alpha", "alpha-0")];
+        write_orders(&dir, "alpha-0", &["alpha-0"]);
+        for file in ["alpha.log", "alpha-error.log", "dexbot-cred.log", "old.log", "stray-error.log"] {
+            fs::write(dir.join("logs").join(file), "x").unwrap();
+        }
+
+        let report = check_in(
+            &bots,
+            &pm2(&["alpha", "dexbot-cred", "stray"]),
+            &dir.join("orders"),
+            &dir.join("logs"),
+        );
+        let logs = finding(&report, Direction::Orphan, "log");
+        let subjects = logs.iter().map(|f| f.subject.as_str()).collect::<Vec<_>>();
+        assert_eq!(
+            subjects,
+            [
+                dir.join("logs/old.log").display().to_string(),
+                dir.join("logs/stray-error.log").display().to_string()
+            ]
+        );
+        assert!(logs[0].detail.contains("no entry or process named old"));
+        assert!(logs[1].detail.contains("orphan process stray"));
+        let processes = finding(&report, Direction::Orphan, "process");
+        assert_eq!(processes.len(), 1);
+        assert_eq!(
+            processes[0].cleanup,
+            Some(Cleanup::DeleteProcess {
+                name: String::from("stray"),
+                online: true
+            })
+        );
+    }
+
+    #[test]
+    fn missing_process_only_when_runtime_answers() {
+        let dir = scratch_dir("consistency-process");
+        let bots = [BotStatus::stub("alpha", "alpha-0")];
+        write_orders(&dir, "alpha-0", &["alpha-0"]);
+
+        let report = check_in(&bots, &pm2(&[]), &dir.join("orders"), &dir.join("logs"));
+        let missing = finding(&report, Direction::Missing, "process");
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].detail, "no pm2 process alpha");
+
+        let offline = RuntimeStatus {
+            online: false,
+            ..pm2(&[])
+        };
+        let report = check_in(&bots, &offline, &dir.join("orders"), &dir.join("logs"));
+        assert!(finding(&report, Direction::Missing, "process").is_empty());
+    }
+}
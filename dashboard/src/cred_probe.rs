@@ -0,0 +1,277 @@
+use std::{
+    fs,
+    io::{BufRead, BufReader, ErrorKind, Write},
+    os::unix::net::UnixStream,
+    path::PathBuf,
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::runtime;
+
+pub const SOCKET_PATH: &str = "/tmp/dexbot-cred-daemon.sock";
+pub const READY_FILE: &str = "/tmp/dexbot-cred-daemon.ready";
+
+/// Any type other than `private-key` is answered with `Unknown credential type`
+/// (`processRequest` in `credential-daemon.js`), so this never touches a key.
+const PROBE_REQUEST: &[u8] = b"{\"type\":\"dashboard-health-probe\"}\n";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProbeHealth {
+    Healthy,
+    Degraded,
+    Down,
+}
+
+impl ProbeHealth {
+    pub fn label(self) -> &'static str {
+        match self {
+            ProbeHealth::Healthy => "ok",
+            ProbeHealth::Degraded => "degraded",
+            ProbeHealth::Down => "down",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ProbeResult {
+    pub health: ProbeHealth,
+    /// Time since the daemon wrote the ready file (its `Date.now()` at listen time).
+    pub ready_age: Option<Duration>,
+    pub socket_exists: bool,
+    pub connected: bool,
+    pub latency: Option<Duration>,
+    pub detail: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct CredProbe {
+    pub ready_file: PathBuf,
+    pub socket: PathBuf,
+    pub timeout: Duration,
+}
+
+impl CredProbe {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            ready_file: PathBuf::from(READY_FILE),
+            socket: PathBuf::from(SOCKET_PATH),
+            timeout,
+        }
+    }
+
+    pub fn run(&self) -> ProbeResult {
+        let ready_age = self.ready_age();
+        let socket_exists = self.socket.exists();
+        let mut result = ProbeResult {
+            health: ProbeHealth::Down,
+            ready_age,
+            socket_exists,
+            connected: false,
+            latency: None,
+            detail: String::new(),
+        };
+
+        if !socket_exists {
+            result.detail = if ready_age.is_some() {
+                String::from("socket missing but ready file present (stale from a crashed daemon?)")
+            } else {
+                String::from("not running (no socket, no ready file)")
+            };
+            return result;
+        }
+
+        let started = Instant::now();
+        let mut stream = match UnixStream::connect(&self.socket) {
+            Ok(stream) => stream,
+            Err(err) => {
+                result.detail = format!("socket refuses connections: {err}");
+                return result;
+            }
+        };
+        result.connected = true;
+        let _ = stream.set_read_timeout(Some(self.timeout));
+        let _ = stream.set_write_timeout(Some(self.timeout));
+        if let Err(err) = stream.write_all(PROBE_REQUEST) {
+            result.detail = format!("write failed: {err}");
+            return result;
+        }
+
+        let mut line = String::new();
+        match BufReader::new(&stream).read_line(&mut line) {
+            Ok(0) => {
+                result.detail = String::from("connection closed without reply");
+                return result;
+            }
+            Ok(_) => {}
+            Err(err) if matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+                result.detail = format!("no reply within {}ms (stuck?)", self.timeout.as_millis());
+                return result;
+            }
+            Err(err) => {
+                result.detail = format!("read failed: {err}");
+                return result;
+            }
+        }
+        result.latency = Some(started.elapsed());
+
+        let replied = serde_json::from_str::<serde_json::Value>(line.trim())
+            .ok()
+            .is_some_and(|v| v.get("success").is_some());
+        (result.health, result.detail) = match (replied, ready_age) {
+            (false, _) => (
+                ProbeHealth::Degraded,
+                format!("unexpected reply: {}", line.trim()),
+            ),
+            (true, None) => (
+                ProbeHealth::Degraded,
+                String::from("answering but ready file missing; bots treat it as unavailable"),
+            ),
+            (true, Some(_)) => (ProbeHealth::Healthy, String::from("answering")),
+        };
+        result
+    }
+
+    fn ready_age(&self) -> Option<Duration> {
+        let raw = fs::read_to_string(&self.ready_file).ok()?;
+        match raw.trim().parse::<i64>() {
+            Ok(written_ms) => {
+                Some(Duration::from_millis((runtime::now_ms() - written_ms).max(0) as u64))
+            }
+            Err(_) => fs::metadata(&self.ready_file)
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|modified| modified.elapsed().ok()),
+        }
+    }
+}
+
+/// Runs the probe off the UI thread: a stuck daemon holds the socket open until the
+/// timeout, which would otherwise freeze rendering for that long.
+#[derive(Debug)]
+pub struct ProbeScheduler {
+    probe: CredProbe,
+    interval: Duration,
+    last_started: Option<Instant>,
+    pending: Option<Receiver<ProbeResult>>,
+    pub latest: Option<ProbeResult>,
+}
+
+impl ProbeScheduler {
+    pub fn new(probe: CredProbe, interval: Duration) -> Self {
+        Self {
+            probe,
+            interval,
+            last_started: None,
+            pending: None,
+            latest: None,
+        }
+    }
+
+    /// Collects a finished probe and starts the next one when due.
+    pub fn poll(&mut self) {
+        if let Some(rx) = &self.pending {
+            match rx.try_recv() {
+                Ok(result) => {
+                    self.latest = Some(result);
+                    self.pending = None;
+                }
+                Err(TryRecvError::Disconnected) => self.pending = None,
+                Err(TryRecvError::Empty) => return,
+            }
+        }
+        if self
+            .last_started
+            .is_some_and(|at| at.elapsed() < self.interval)
+        {
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        let probe = self.probe.clone();
+        thread::spawn(move || {
+            let _ = tx.send(probe.run());
+        });
+        self.pending = Some(rx);
+        self.last_started = Some(Instant::now());
+    }
+
+    pub fn alerts(&self) -> Vec<String> {
+        match &self.latest {
+            Some(r) if r.health != ProbeHealth::Healthy => {
+                vec![format!("Credential daemon probe {}: {}.", r.health.label(), r.detail)]
+            }
+            _ => vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::net::UnixListener;
+
+    use crate::testutil::ScratchDir;
+
+    fn probe_in(tag: &str) -> (ScratchDir, CredProbe) {
+        let dir = ScratchDir::new(&format!("cred-{tag}"));
+        let probe = CredProbe {
+            ready_file: dir.join("daemon.ready"),
+            socket: dir.join("daemon.sock"),
+            timeout: Duration::from_millis(300),
+        };
+        (dir, probe)
+    }
+
+    /// Stand-in daemon: answers one connection with `reply`, or stays silent.
+    fn serve_once(probe: &CredProbe, reply: Option<&'static str>) -> thread::JoinHandle<String> {
+        let listener = UnixListener::bind(&probe.socket).unwrap();
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut request = String::new();
+            BufReader::new(&stream).read_line(&mut request).unwrap();
+            match reply {
+                Some(reply) => (&stream).write_all(reply.as_bytes()).unwrap(),
+                None => thread::sleep(Duration::from_millis(600)),
+            }
+            request
+        })
+    }
+
+    #[test]
+    fn healthy_daemon_gets_a_non_key_request() {
+        let (_dir, probe) = probe_in("ok");
+        fs::write(&probe.ready_file, (runtime::now_ms() - 60_000).to_string()).unwrap();
+        let server = serve_once(
+            &probe,
+            Some("{\"success\":false,\"error\":\"Unknown credential type: dashboard-health-probe\"}\n"),
+        );
+        let result = probe.run();
+        let request = server.join().unwrap();
+        assert_eq!(result.health, ProbeHealth::Healthy);
+        assert!(result.ready_age.unwrap() >= Duration::from_secs(59));
+        assert!(!request.contains("private-key"));
+    }
+
+    #[test]
+    fn silent_daemon_times_out() {
+        let (_dir, probe) = probe_in("stuck");
+        fs::write(&probe.ready_file, runtime::now_ms().to_string()).unwrap();
+        let server = serve_once(&probe, None);
+        let result = probe.run();
+        server.join().unwrap();
+        assert_eq!(result.health, ProbeHealth::Down);
+        assert!(result.connected);
+        assert!(result.detail.contains("no reply"));
+    }
+
+    #[test]
+    fn stale_ready_file_without_socket_is_down() {
+        let (_dir, probe) = probe_in("stale");
+        fs::write(&probe.ready_file, "1700000000000").unwrap();
+        let result = probe.run();
+        assert_eq!(result.health, ProbeHealth::Down);
+        assert!(!result.socket_exists);
+        assert!(result.detail.contains("stale"));
+    }
+}
@@ -0,0 +1,297 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    accounts::asset_key,
+    fees::FeeLedger,
+    fills::{AssetAmount, Fill},
+    grid::Side,
+    state::BotStatus,
+};
+
+const DAY_MS: f64 = 86_400_000.0;
+
+/// A fill closed by an opposite fill one grid level away.
+#[derive(Clone, Debug)]
+pub struct Cycle {
+    /// Side of the opening fill: `Buy` for buy-then-sell.
+    pub opened_by: Side,
+    pub open_ms: i64,
+    pub close_ms: i64,
+    pub buy_price: f64,
+    pub sell_price: f64,
+    /// Matched amount in asset A.
+    pub size: f64,
+    /// Fees of both fills, pro rata to `size`, one amount per asset; empty when
+    /// neither fill was priced.
+    pub fees: Vec<AssetAmount>,
+}
+
+impl Cycle {
+    /// Captured price difference in asset B.
+    pub fn profit(&self) -> f64 {
+        self.size * (self.sell_price - self.buy_price)
+    }
+
+    pub fn profit_percent(&self) -> f64 {
+        (self.sell_price / self.buy_price - 1.0) * 100.0
+    }
+
+    pub fn duration_ms(&self) -> i64 {
+        self.close_ms - self.open_ms
+    }
+}
+
+#[derive(Debug)]
+struct OpenFill {
+    fill: Fill,
+    remaining: f64,
+}
+
+/// Pairs fills into round trips. Each fill closes the most recent open opposite
+/// fill one `incrementPercent` step away (half a step tolerance) on the
+/// profitable side; leftovers stay open. Returns the cycles and the open fills.
+pub fn match_cycles(fills: &[Fill], increment_percent: f64) -> (Vec<Cycle>, usize) {
+    let step = (1.0 + increment_percent / 100.0).ln();
+    let mut open: Vec<OpenFill> = Vec::new();
+    let mut cycles = Vec::new();
+    if step <= 0.0 {
+        return (cycles, fills.len());
+    }
+    for fill in fills.iter().filter(|f| f.size > 0.0 && f.price > 0.0) {
+        let mut remaining = fill.size;
+        while remaining > 0.0 {
+            let adjacent = open.iter().rposition(|o| {
+                let (buy, sell) = match fill.side {
+                    Side::Sell => (o.fill.price, fill.price),
+                    _ => (fill.price, o.fill.price),
+                };
+                o.fill.side != fill.side && ((sell / buy).ln() / step - 1.0).abs() <= 0.5
+            });
+            let Some(index) = adjacent else {
+                break;
+            };
+            let opening = &mut open[index];
+            let size = remaining.min(opening.remaining);
+            let (buy_price, sell_price) = match fill.side {
+                Side::Sell => (opening.fill.price, fill.price),
+                _ => (fill.price, opening.fill.price),
+            };
+            cycles.push(Cycle {
+                opened_by: opening.fill.side,
+                open_ms: opening.fill.ts_ms,
+                close_ms: fill.ts_ms,
+                buy_price,
+                sell_price,
+                size,
+                fees: cycle_fees(&opening.fill, fill, size),
+            });
+            opening.remaining -= size;
+            remaining -= size;
+            if opening.remaining <= f64::EPSILON {
+                open.remove(index);
+            }
+        }
+        if remaining > f64::EPSILON {
+            open.push(OpenFill {
+                fill: fill.clone(),
+                remaining,
+            });
+        }
+    }
+    (cycles, open.len())
+}
+
+fn cycle_fees(open: &Fill, close: &Fill, size: f64) -> Vec<AssetAmount> {
+    let mut fees: Vec<AssetAmount> = Vec::new();
+    for fill in [open, close] {
+        let Some(fee) = &fill.fee else {
+            continue;
+        };
+        let amount = fee.amount * size / fill.size;
+        match fees.iter_mut().find(|f| f.asset == fee.asset) {
+            Some(same) => same.amount += amount,
+            None => fees.push(AssetAmount {
+                amount,
+                asset: fee.asset.clone(),
+            }),
+        }
+    }
+    fees
+}
+
+/// Round trips of one bot over the scanned part of its log.
+#[derive(Clone, Debug)]
+pub struct CycleReport {
+    pub bot: String,
+    /// Asset B, which profits are counted in.
+    pub quote: String,
+    pub fills: usize,
+    pub open_fills: usize,
+    /// Newest first.
+    pub cycles: Vec<Cycle>,
+    pub span_ms: i64,
+    pub increment_percent: f64,
+}
+
+impl CycleReport {
+    pub fn cycles_per_day(&self) -> Option<f64> {
+        (self.span_ms > 0).then(|| self.cycles.len() as f64 / (self.span_ms as f64 / DAY_MS))
+    }
+
+    fn mean(&self, value: impl Fn(&Cycle) -> f64) -> Option<f64> {
+        (!self.cycles.is_empty())
+            .then(|| self.cycles.iter().map(value).sum::<f64>() / self.cycles.len() as f64)
+    }
+
+    pub fn mean_profit(&self) -> Option<f64> {
+        self.mean(Cycle::profit)
+    }
+
+    pub fn mean_profit_percent(&self) -> Option<f64> {
+        self.mean(Cycle::profit_percent)
+    }
+
+    pub fn mean_duration_ms(&self) -> Option<f64> {
+        self.mean(|c| c.duration_ms() as f64)
+    }
+
+    /// Mean fee per cycle for each fee asset, over the cycles charged in it.
+    pub fn mean_fees(&self) -> Vec<AssetAmount> {
+        let mut sums = BTreeMap::<&str, (f64, usize)>::new();
+        for fee in self.cycles.iter().flat_map(|c| &c.fees) {
+            let sum = sums.entry(fee.asset.as_str()).or_default();
+            sum.0 += fee.amount;
+            sum.1 += 1;
+        }
+        sums.into_iter()
+            .map(|(asset, (amount, cycles))| AssetAmount {
+                amount: amount / cycles as f64,
+                asset: asset.to_string(),
+            })
+            .collect()
+    }
+
+    /// Mean profit after the fees paid in the quote asset.
+    pub fn mean_net_profit(&self) -> Option<f64> {
+        self.mean(|c| {
+            let fees = c
+                .fees
+                .iter()
+                .filter(|f| f.asset == self.quote)
+                .map(|f| f.amount)
+                .sum::<f64>();
+            c.profit() - fees
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct CycleState {
+    pub data: Result<CycleReport, String>,
+}
+
+impl Default for CycleState {
+    fn default() -> Self {
+        Self {
+            data: Err(String::from("not loaded")),
+        }
+    }
+}
+
+impl CycleState {
+    /// `ledger` is the bot's parsed log from the `FeeTracker`.
+    pub fn rebuild(&mut self, bot: &BotStatus, ledger: Option<&FeeLedger>) {
+        if bot.identity.out_log.is_none() {
+            self.data = Err(format!("{}: no log file", bot.name));
+            return;
+        }
+        let fills = ledger.map(|l| l.fills.as_slice()).unwrap_or_default();
+        if fills.is_empty() {
+            self.data = Err(format!("{}: no [FILL] lines in the last 2 MiB of the log", bot.name));
+            return;
+        }
+        let increment_percent = bot.config.increment_percent();
+        let (mut cycles, open_fills) = match_cycles(fills, increment_percent);
+        cycles.reverse();
+        self.data = Ok(CycleReport {
+            bot: bot.name.clone(),
+            quote: asset_key(&bot.asset_b),
+            fills: fills.len(),
+            open_fills,
+            cycles,
+            span_ms: fills[fills.len() - 1].ts_ms - fills[0].ts_ms,
+            increment_percent,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(ts_ms: i64, side: Side, size: f64, price: f64) -> Fill {
+        Fill {
+            ts_ms,
+            side,
+            size,
+            price,
+            proceeds: None,
+            fee: Some(AssetAmount {
+                amount: 0.1,
+                asset: String::from("BTS"),
+            }),
+        }
+    }
+
+    #[test]
+    fn matches_adjacent_levels_in_both_directions() {
+        let fills = [
+            fill(0, Side::Buy, 2.0, 100.0),
+            // Two steps up: not adjacent to the buy, stays open.
+            fill(1, Side::Sell, 1.0, 102.01),
+            fill(2, Side::Sell, 1.0, 101.0),
+            fill(3, Side::Buy, 1.0, 101.0),
+            fill(4, Side::Sell, 1.0, 101.0),
+        ];
+        let (cycles, open) = match_cycles(&fills, 1.0);
+        assert_eq!(cycles.len(), 3);
+        assert_eq!(cycles[0].opened_by, Side::Buy);
+        assert!((cycles[0].profit() - 1.0).abs() < 1e-9);
+        // The 101 buy closes the 102.01 sell, one level above it.
+        assert_eq!(cycles[1].opened_by, Side::Sell);
+        assert_eq!(cycles[1].open_ms, 1);
+        // The last sell closes what is left of the first buy.
+        assert_eq!((cycles[2].open_ms, cycles[2].close_ms), (0, 4));
+        assert_eq!(cycles[2].fees.len(), 1);
+        assert!((cycles[2].fees[0].amount - 0.15).abs() < 1e-9);
+        assert_eq!(open, 0);
+    }
+
+    #[test]
+    fn fees_in_different_assets_are_both_kept() {
+        let mut close = fill(1, Side::Sell, 1.0, 101.0);
+        close.fee = Some(AssetAmount {
+            amount: 0.02,
+            asset: String::from("XRP"),
+        });
+        let (cycles, _) = match_cycles(&[fill(0, Side::Buy, 1.0, 100.0), close], 1.0);
+        let fees = cycles[0]
+            .fees
+            .iter()
+            .map(|f| (f.asset.as_str(), f.amount))
+            .collect::<Vec<_>>();
+        assert_eq!(fees, [("BTS", 0.1), ("XRP", 0.02)]);
+
+        let report = CycleReport {
+            bot: String::from("b"),
+            quote: String::from("BTS"),
+            fills: 2,
+            open_fills: 0,
+            cycles,
+            span_ms: 1,
+            increment_percent: 1.0,
+        };
+        assert_eq!(report.mean_fees().len(), 2);
+        assert!((report.mean_net_profit().unwrap() - 0.9).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,266 @@
+use std::collections::HashSet;
+
+use crate::{
+    grid::{self, FundSpec, GridConfig, IdealGrid, Side, SideFunds},
+    state::PersistedSlot,
+};
+
+#[derive(Clone, Debug)]
+pub struct SlotDivergence {
+    pub id: String,
+    pub side: Option<Side>,
+    pub state: String,
+    pub price: f64,
+    pub size: f64,
+    pub ideal_side: Option<Side>,
+    pub ideal_active: bool,
+    pub ideal_size: f64,
+    pub price_error_percent: f64,
+    /// Relative size error (0.1 = 10%); unmatched slots count as 1.0.
+    pub size_error: f64,
+    /// False for PARTIAL slots, which are in transition, and for spread slots, which
+    /// hold no funds; like the bot's per-side metric, neither enters the RMS.
+    pub counted: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct GridDivergence {
+    pub slots: Vec<SlotDivergence>,
+    pub ideal: IdealGrid,
+    pub rms_percent: f64,
+    pub promille: f64,
+    pub worst_slot: Option<(String, f64)>,
+    pub unmatched_persisted: usize,
+    pub unmatched_ideal: usize,
+    pub role_mismatches: usize,
+    pub state_mismatches: usize,
+    pub partial_excluded: usize,
+}
+
+/// Compares a persisted grid against the ideal geometric grid for the same config.
+///
+/// The ideal rail is centred on a numeric `startPrice` when configured, otherwise on
+/// the persisted spread centre. Percentage `botFunds` cannot be resolved without the
+/// wallet balance, so those sides are sized from the persisted side total instead;
+/// the size metric then measures the weighting shape rather than the absolute budget.
+pub fn compute(config: &GridConfig, persisted: &[PersistedSlot]) -> Result<GridDivergence, String> {
+    if persisted.is_empty() {
+        return Err(String::from("no persisted grid"));
+    }
+    let reference = config
+        .start_price()
+        .or_else(|| spread_center(persisted))
+        .ok_or_else(|| String::from("no reference price (startPrice not numeric, no spread)"))?;
+
+    let funds = SideFunds {
+        buy: side_budget(config, persisted, Side::Buy),
+        sell: side_budget(config, persisted, Side::Sell),
+    };
+    let ideal = grid::build_ideal_grid(config, reference, funds)?;
+    let tolerance = (1.0 + config.increment_percent() / 100.0).ln();
+
+    let mut matched_ideal = HashSet::new();
+    let mut slots = Vec::with_capacity(persisted.len());
+    let mut sum_sq = 0.0;
+    let mut samples = 0usize;
+    let mut unmatched_persisted = 0;
+    let mut role_mismatches = 0;
+    let mut state_mismatches = 0;
+    let mut partial_excluded = 0;
+
+    for slot in persisted {
+        let side = slot.side();
+        let state = slot.state.clone().unwrap_or_else(|| String::from("?"));
+        let nearest = ideal
+            .slots
+            .iter()
+            .filter(|_| slot.price > 0.0)
+            .map(|s| (s, (slot.price / s.price).ln().abs()))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .filter(|(_, dist)| *dist <= tolerance)
+            .map(|(s, _)| s);
+
+        let partial = state == "partial";
+        if partial {
+            partial_excluded += 1;
+        }
+        let spread = side == Some(Side::Spread) && nearest.is_none_or(|t| t.side == Side::Spread);
+        let counted = !partial && !spread;
+
+        let row = match nearest {
+            Some(target) => {
+                matched_ideal.insert(target.index);
+                let is_trading_side = matches!(side, Some(Side::Buy | Side::Sell));
+                if side != Some(target.side) {
+                    role_mismatches += 1;
+                }
+                let is_active = matches!(state.as_str(), "active" | "partial");
+                if is_trading_side && side == Some(target.side) && is_active != target.active {
+                    state_mismatches += 1;
+                }
+                let size_error = relative_error(slot.size, target.size);
+                SlotDivergence {
+                    id: slot.label(),
+                    side,
+                    state,
+                    price: slot.price,
+                    size: slot.size,
+                    ideal_side: Some(target.side),
+                    ideal_active: target.active,
+                    ideal_size: target.size,
+                    price_error_percent: (slot.price / target.price - 1.0) * 100.0,
+                    size_error,
+                    counted,
+                }
+            }
+            None => {
+                unmatched_persisted += 1;
+                SlotDivergence {
+                    id: slot.label(),
+                    side,
+                    state,
+                    price: slot.price,
+                    size: slot.size,
+                    ideal_side: None,
+                    ideal_active: false,
+                    ideal_size: 0.0,
+                    price_error_percent: 0.0,
+                    size_error: 1.0,
+                    counted,
+                }
+            }
+        };
+        if row.counted {
+            sum_sq += row.size_error * row.size_error;
+            samples += 1;
+        }
+        slots.push(row);
+    }
+
+    let unmatched_ideal = ideal
+        .slots
+        .iter()
+        .filter(|s| s.side != Side::Spread && !matched_ideal.contains(&s.index))
+        .count();
+    sum_sq += unmatched_ideal as f64;
+    samples += unmatched_ideal;
+
+    let mean_sq = if samples > 0 {
+        sum_sq / samples as f64
+    } else {
+        0.0
+    };
+    let worst_slot = slots
+        .iter()
+        .filter(|s| s.counted)
+        .max_by(|a, b| a.size_error.abs().total_cmp(&b.size_error.abs()))
+        .map(|s| (s.id.clone(), s.size_error.abs() * 100.0));
+
+    Ok(GridDivergence {
+        slots,
+        ideal,
+        rms_percent: mean_sq.sqrt() * 100.0,
+        promille: mean_sq * 1000.0,
+        worst_slot,
+        unmatched_persisted,
+        unmatched_ideal,
+        role_mismatches,
+        state_mismatches,
+        partial_excluded,
+    })
+}
+
+/// Same convention as `calculateGridSideDivergenceMetric`: zero ideal size with a
+/// non-zero persisted size is a full (100%) error.
+fn relative_error(actual: f64, ideal: f64) -> f64 {
+    if ideal > 0.0 {
+        (actual - ideal) / ideal
+    } else if actual > 0.0 {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+fn side_budget(config: &GridConfig, persisted: &[PersistedSlot], side: Side) -> f64 {
+    match config.bot_funds(side) {
+        FundSpec::Absolute(amount) => amount,
+        FundSpec::Percent(_) => persisted
+            .iter()
+            .filter(|s| s.side() == Some(side))
+            .map(|s| s.size)
+            .sum(),
+    }
+}
+
+/// Geometric midpoint between the highest BUY slot and the lowest SELL slot.
+pub fn spread_center(persisted: &[PersistedSlot]) -> Option<f64> {
+    let best_buy = persisted
+        .iter()
+        .filter(|s| s.side() == Some(Side::Buy) && s.price > 0.0)
+        .map(|s| s.price)
+        .max_by(f64::total_cmp)?;
+    let best_sell = persisted
+        .iter()
+        .filter(|s| s.side() == Some(Side::Sell) && s.price > 0.0)
+        .map(|s| s.price)
+        .min_by(f64::total_cmp)?;
+    Some((best_buy * best_sell).sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slot(index: usize, side: Side, price: f64, size: f64) -> PersistedSlot {
+        PersistedSlot {
+            id: Some(format!("slot-{index}")),
+            slot_type: Some(side.label().to_string()),
+            state: Some(String::from("active")),
+            price,
+            size,
+        }
+    }
+
+    #[test]
+    fn rms_matches_the_bot_metric() {
+        let config: GridConfig = serde_json::from_value(serde_json::json!({
+            "startPrice": 1,
+            "minPrice": 0.96,
+            "maxPrice": 1.04,
+            "incrementPercent": 1,
+            "targetSpreadPercent": 2,
+            "weightDistribution": {"buy": 0.5, "sell": 1},
+            "botFunds": {"buy": 100, "sell": 50},
+            "activeOrders": {"buy": 3, "sell": 3},
+        }))
+        .unwrap();
+        let ideal = grid::build_ideal_grid(&config, 1.0, SideFunds { buy: 100.0, sell: 50.0 }).unwrap();
+        let mut persisted = ideal
+            .slots
+            .iter()
+            .map(|s| slot(s.index, s.side, s.price, s.size))
+            .collect::<Vec<_>>();
+        let exact = compute(&config, &persisted).unwrap();
+        assert!(exact.rms_percent < 1e-9);
+
+        // First buy 10% over, last sell 20% under, slot-6 gone. The bot's
+        // `calculateGridSideDivergenceMetric` over both sides gives
+        // 0.4183300132670378 for the same grids.
+        persisted[0].size *= 1.1;
+        persisted[7].size *= 0.8;
+        persisted.remove(6);
+        let div = compute(&config, &persisted).unwrap();
+        assert!((div.rms_percent - 41.83300132670378).abs() < 1e-9, "{}", div.rms_percent);
+        assert_eq!(div.unmatched_ideal, 1);
+        let (worst, error) = div.worst_slot.unwrap();
+        assert_eq!(worst, "slot-7");
+        assert!((error - 20.0).abs() < 1e-9);
+
+        // A PARTIAL slot is in transition and stays out of the metric.
+        persisted[7 - 1].state = Some(String::from("partial"));
+        let div = compute(&config, &persisted).unwrap();
+        assert_eq!(div.partial_excluded, 1);
+        assert!((div.rms_percent - (1.01_f64 / 5.0).sqrt() * 100.0).abs() < 1e-6);
+    }
+}
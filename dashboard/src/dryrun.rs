@@ -0,0 +1,232 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use crate::{actions::DeleteScope, state::Snapshot};
+
+/// One file a Danger action would remove.
+#[derive(Clone, Debug)]
+pub struct PlannedFile {
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified_ms: Option<i64>,
+    /// Running bots or processes holding the file, e.g. `alpha (log)`.
+    pub in_use: Vec<String>,
+}
+
+/// Dry run of a Danger action, shown before its token can be typed.
+#[derive(Clone, Debug, Default)]
+pub struct DeletionPlan {
+    pub files: Vec<PlannedFile>,
+    /// Anything the file list alone does not say (entry-only removal, no files).
+    pub note: Option<String>,
+}
+
+impl DeletionPlan {
+    /// Stats `paths` (missing ones are dropped) and marks the files running bots
+    /// still use: their logs and orders files, and whatever `/proc/{pid}/fd` shows
+    /// open.
+    pub fn scan(paths: Vec<PathBuf>, snapshot: &Snapshot, note: Option<String>) -> Self {
+        Self::stat(paths, &open_files(snapshot), note)
+    }
+
+    /// `users` maps canonical paths to what holds them open.
+    fn stat(paths: Vec<PathBuf>, users: &HashMap<PathBuf, Vec<String>>, note: Option<String>) -> Self {
+        let mut files = paths
+            .into_iter()
+            .filter_map(|path| {
+                let meta = fs::metadata(&path).ok().filter(|m| m.is_file())?;
+                let modified_ms = meta
+                    .modified()
+                    .ok()
+                    .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                    .map(|d| d.as_millis() as i64);
+                let in_use = fs::canonicalize(&path)
+                    .ok()
+                    .and_then(|p| users.get(&p).cloned())
+                    .unwrap_or_default();
+                Some(PlannedFile {
+                    path,
+                    size: meta.len(),
+                    modified_ms,
+                    in_use,
+                })
+            })
+            .collect::<Vec<_>>();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        Self { files, note }
+    }
+
+    pub fn total_size(&self) -> u64 {
+        self.files.iter().map(|f| f.size).sum()
+    }
+
+    pub fn newest_ms(&self) -> Option<i64> {
+        self.files.iter().filter_map(|f| f.modified_ms).max()
+    }
+
+    /// `(directory, files, bytes)` per parent directory.
+    pub fn dirs(&self) -> Vec<(PathBuf, usize, u64)> {
+        let mut dirs = BTreeMap::<PathBuf, (usize, u64)>::new();
+        for file in &self.files {
+            let dir = file.path.parent().map(Path::to_path_buf).unwrap_or_default();
+            let entry = dirs.entry(dir).or_default();
+            entry.0 += 1;
+            entry.1 += file.size;
+        }
+        dirs.into_iter().map(|(dir, (files, size))| (dir, files, size)).collect()
+    }
+
+    pub fn in_use(&self) -> impl Iterator<Item = &PlannedFile> {
+        self.files.iter().filter(|f| !f.in_use.is_empty())
+    }
+}
+
+/// Files a scope matches, recursively.
+pub fn expand(scope: &DeleteScope) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    walk(Path::new(scope.dir), scope.extension, &mut files);
+    files
+}
+
+fn walk(dir: &Path, extension: Option<&str>, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.flatten().map(|e| e.path()) {
+        if path.is_dir() {
+            walk(&path, extension, files);
+        } else if extension.is_none_or(|ext| path.extension().is_some_and(|e| e == ext)) {
+            files.push(path);
+        }
+    }
+}
+
+fn open_files(snapshot: &Snapshot) -> HashMap<PathBuf, Vec<String>> {
+    let mut users = HashMap::<PathBuf, Vec<String>>::new();
+    let mut add = |path: &Path, user: String| {
+        if let Ok(path) = fs::canonicalize(path) {
+            let list = users.entry(path).or_default();
+            if !list.contains(&user) {
+                list.push(user);
+            }
+        }
+    };
+    for bot in &snapshot.bots {
+        if bot.process.as_ref().is_none_or(|p| p.status != "online") {
+            continue;
+        }
+        for path in [&bot.identity.out_log, &bot.identity.error_log].into_iter().flatten() {
+            add(path, format!("{} (log)", bot.name));
+        }
+        let orders = PathBuf::from(format!("profiles/orders/{}.json", bot.identity.bot_key));
+        add(&orders, format!("{} (orders)", bot.name));
+    }
+    for process in snapshot.runtime.processes.values() {
+        let Some(pid) = process.pid.filter(|p| *p > 0 && process.status == "online") else {
+            continue;
+        };
+        let Ok(fds) = fs::read_dir(format!("/proc/{pid}/fd")) else {
+            continue;
+        };
+        for target in fds.flatten().filter_map(|fd| fs::read_link(fd.path()).ok()) {
+            if target.is_absolute() {
+                add(&target, format!("{} (pid {pid})", process.name));
+            }
+        }
+    }
+    users
+}
+
+pub fn format_size(bytes: u64) -> String {
+    match bytes {
+        0..=1023 => format!("{bytes} B"),
+        1024..=1_048_575 => format!("{:.1} KiB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::ScratchDir;
+
+    fn write(path: &Path, bytes: usize) -> PathBuf {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, "x".repeat(bytes)).unwrap();
+        path.to_path_buf()
+    }
+
+    #[test]
+    fn stat_drops_missing_paths_and_marks_files_in_use() {
+        let dir = ScratchDir::new("dryrun-scan");
+        let b = write(&dir.join("b.log"), 30);
+        let a = write(&dir.join("a.log"), 10);
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        let users = HashMap::from([(
+            fs::canonicalize(&b).unwrap(),
+            vec![String::from("alpha (log)")],
+        )]);
+
+        let plan = DeletionPlan::stat(
+            vec![b.clone(), dir.join("gone.log"), dir.join("sub"), a.clone()],
+            &users,
+            None,
+        );
+        let paths = plan.files.iter().map(|f| f.path.clone()).collect::<Vec<_>>();
+        assert_eq!(paths, [a, b.clone()]);
+        assert_eq!(plan.total_size(), 40);
+        assert!(plan.newest_ms().is_some());
+        let in_use = plan.in_use().collect::<Vec<_>>();
+        assert_eq!(in_use.len(), 1);
+        assert_eq!(in_use[0].path, b);
+        assert_eq!(in_use[0].in_use, ["alpha (log)"]);
+    }
+
+    #[test]
+    fn dirs_sum_files_per_parent() {
+        let dir = ScratchDir::new("dryrun-dirs");
+        let paths = vec![
+            write(&dir.join("logs/a.log"), 5),
+            write(&dir.join("logs/b.log"), 7),
+            write(&dir.join("orders/c.json"), 11),
+        ];
+        let plan = DeletionPlan::stat(paths, &HashMap::new(), None);
+        assert_eq!(
+            plan.dirs(),
+            [(dir.join("logs"), 2, 12), (dir.join("orders"), 1, 11)]
+        );
+    }
+
+    #[test]
+    fn expand_walks_subdirectories_and_filters_by_extension() {
+        let dir = ScratchDir::new("dryrun-expand");
+        let log = write(&dir.join("a.log"), 1);
+        let nested = write(&dir.join("old/b.log"), 1);
+        let other = write(&dir.join("c.json"), 1);
+        let root: &'static str = Box::leak(dir.display().to_string().into_boxed_str());
+
+        let mut logs = expand(&DeleteScope {
+            dir: root,
+            extension: Some("log"),
+        });
+        logs.sort();
+        assert_eq!(logs, [log.clone(), nested.clone()]);
+
+        let mut all = expand(&DeleteScope {
+            dir: root,
+            extension: None,
+        });
+        all.sort();
+        assert_eq!(all, [log, other, nested]);
+
+        let missing = expand(&DeleteScope {
+            dir: "/nonexistent/dexbot-dash",
+            extension: None,
+        });
+        assert!(missing.is_empty());
+    }
+}
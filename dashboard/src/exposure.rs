@@ -0,0 +1,271 @@
+use std::{
+    collections::{BTreeMap, HashSet, VecDeque},
+    sync::Arc,
+};
+
+use crate::{
+    accounts::asset_key,
+    grid::Side,
+    market::{AssetRef, MarketFile},
+    state::BotStatus,
+};
+
+/// Amounts of one asset held in grids across the fleet.
+#[derive(Clone, Debug, Default)]
+pub struct AssetExposure {
+    pub asset: String,
+    pub bots: Vec<String>,
+    /// ACTIVE/PARTIAL sizes in buy orders (the asset is the bot's asset B).
+    pub committed_buy: f64,
+    /// ACTIVE/PARTIAL sizes in sell orders (the asset is the bot's asset A).
+    pub committed_sell: f64,
+    pub virtual_buy: f64,
+    pub virtual_sell: f64,
+}
+
+impl AssetExposure {
+    pub fn committed(&self) -> f64 {
+        self.committed_buy + self.committed_sell
+    }
+
+    pub fn reserved(&self) -> f64 {
+        self.virtual_buy + self.virtual_sell
+    }
+
+    pub fn total(&self) -> f64 {
+        self.committed() + self.reserved()
+    }
+}
+
+/// `quote` per `base`, from a market file or, failing that, a bot's grid centre.
+#[derive(Clone, Debug)]
+pub struct Rate {
+    pub base: String,
+    pub quote: String,
+    pub price: f64,
+    pub source: String,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Exposure {
+    pub assets: Vec<AssetExposure>,
+    pub rates: Vec<Rate>,
+}
+
+impl Exposure {
+    /// Price of one `asset` in `reference`, chaining rates when there is no direct
+    /// pair. Returns the price and the route it took.
+    pub fn convert(&self, asset: &str, reference: &str) -> Option<(f64, String)> {
+        if asset == reference {
+            return Some((1.0, String::from("reference")));
+        }
+        let mut seen = HashSet::from([asset.to_string()]);
+        let mut queue = VecDeque::from([(asset.to_string(), 1.0, Vec::<String>::new())]);
+        while let Some((node, price, route)) = queue.pop_front() {
+            for rate in &self.rates {
+                let step = if rate.base == node {
+                    Some((&rate.quote, rate.price))
+                } else if rate.quote == node {
+                    Some((&rate.base, 1.0 / rate.price))
+                } else {
+                    None
+                };
+                let Some((next, factor)) = step else {
+                    continue;
+                };
+                if !seen.insert(next.clone()) {
+                    continue;
+                }
+                let mut route = route.clone();
+                route.push(rate.source.clone());
+                if next == reference {
+                    return Some((price * factor, route.join(" + ")));
+                }
+                queue.push_back((next.clone(), price * factor, route));
+            }
+        }
+        None
+    }
+
+    pub fn asset_names(&self) -> Vec<String> {
+        self.assets.iter().map(|a| a.asset.clone()).collect()
+    }
+}
+
+pub fn summarize(bots: &[BotStatus], market_files: &[Arc<MarketFile>]) -> Exposure {
+    let mut assets: BTreeMap<String, AssetExposure> = BTreeMap::new();
+    for bot in bots {
+        for (side, asset) in [(Side::Buy, &bot.asset_b), (Side::Sell, &bot.asset_a)] {
+            let key = asset_key(asset);
+            let entry = assets.entry(key.clone()).or_insert_with(|| AssetExposure {
+                asset: key,
+                ..AssetExposure::default()
+            });
+            let mut touched = false;
+            for slot in bot.grid.iter().filter(|s| s.side() == Some(side)) {
+                let (committed, reserved) = match side {
+                    Side::Buy => (&mut entry.committed_buy, &mut entry.virtual_buy),
+                    _ => (&mut entry.committed_sell, &mut entry.virtual_sell),
+                };
+                match slot.state.as_deref() {
+                    Some("active" | "partial") => *committed += slot.size,
+                    Some("virtual") => *reserved += slot.size,
+                    _ => continue,
+                }
+                touched = true;
+            }
+            if touched && !entry.bots.contains(&bot.name) {
+                entry.bots.push(bot.name.clone());
+            }
+        }
+    }
+    assets.retain(|_, a| !a.bots.is_empty());
+
+    Exposure {
+        assets: assets.into_values().collect(),
+        rates: rates(bots, market_files),
+    }
+}
+
+/// Latest close of every market file whose two assets both appear in a bot pair,
+/// then grid centres for pairs no file covers.
+fn rates(bots: &[BotStatus], market_files: &[Arc<MarketFile>]) -> Vec<Rate> {
+    let known = bots
+        .iter()
+        .flat_map(|b| [&b.asset_a, &b.asset_b])
+        .collect::<Vec<_>>();
+    let resolve = |asset: &AssetRef| known.iter().find(|k| k.matches(asset)).map(|k| asset_key(k));
+
+    let mut rates = Vec::new();
+    for file in market_files {
+        let (Some(base), Some(quote), Some(last)) = (
+            resolve(&file.asset_a),
+            resolve(&file.asset_b),
+            file.candles.last(),
+        ) else {
+            continue;
+        };
+        let covered = rates
+            .iter()
+            .any(|r: &Rate| r.base == base && r.quote == quote);
+        if base != quote && !covered && last.close > 0.0 {
+            let source = file
+                .path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            rates.push(Rate {
+                base,
+                quote,
+                price: last.close,
+                source,
+            });
+        }
+    }
+    for bot in bots {
+        let (base, quote) = (asset_key(&bot.asset_a), asset_key(&bot.asset_b));
+        let covered = rates.iter().any(|r| {
+            (r.base == base && r.quote == quote) || (r.base == quote && r.quote == base)
+        });
+        if let (false, Some(center)) = (covered || base == quote, bot.grid_center) {
+            rates.push(Rate {
+                base,
+                quote,
+                price: center,
+                source: format!("{} grid centre", bot.name),
+            });
+        }
+    }
+    rates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    use crate::market::Candle;
+
+    fn asset(symbol: &str) -> AssetRef {
+        AssetRef {
+            id: String::new(),
+            symbol: symbol.to_string(),
+        }
+    }
+
+    fn rate(base: &str, quote: &str, price: f64, source: &str) -> Rate {
+        Rate {
+            base: base.to_string(),
+            quote: quote.to_string(),
+            price,
+            source: source.to_string(),
+        }
+    }
+
+    fn market_file(name: &str, a: &str, b: &str, close: f64) -> Arc<MarketFile> {
+        Arc::new(MarketFile {
+            path: PathBuf::from(format!("market_adapter/data/{name}")),
+            asset_a: asset(a),
+            asset_b: asset(b),
+            interval_seconds: 3600,
+            candles: vec![
+                Candle { ts_ms: 0, close: close * 2.0 },
+                Candle { ts_ms: 3_600_000, close },
+            ],
+        })
+    }
+
+    fn bot(name: &str, a: &str, b: &str, center: Option<f64>) -> BotStatus {
+        let mut bot = BotStatus::stub(name, name);
+        bot.asset_a = asset(a);
+        bot.asset_b = asset(b);
+        bot.grid_center = center;
+        bot
+    }
+
+    #[test]
+    fn convert_follows_direct_inverted_and_chained_rates() {
+        let exposure = Exposure {
+            assets: Vec::new(),
+            rates: vec![
+                rate("XRP", "BTS", 2.0, "xrp.json"),
+                rate("BTS", "USD", 0.05, "usd.json"),
+            ],
+        };
+        assert_eq!(exposure.convert("XRP", "BTS"), Some((2.0, String::from("xrp.json"))));
+        assert_eq!(exposure.convert("BTS", "XRP"), Some((0.5, String::from("xrp.json"))));
+        assert_eq!(
+            exposure.convert("XRP", "USD"),
+            Some((0.1, String::from("xrp.json + usd.json")))
+        );
+        assert_eq!(exposure.convert("USD", "USD"), Some((1.0, String::from("reference"))));
+        assert_eq!(exposure.convert("EUR", "USD"), None);
+    }
+
+    #[test]
+    fn rates_prefer_market_files_and_fall_back_to_grid_centres() {
+        let bots = [
+            bot("xrp", "XRP", "BTS", Some(2.5)),
+            bot("usd", "BTS", "USD", Some(0.04)),
+            bot("eur", "EUR", "USD", Some(1.1)),
+        ];
+        let files = [
+            market_file("xrp-bts.json", "XRP", "BTS", 2.0),
+            // Quotes the usd bot's pair the other way round; still covers it.
+            market_file("usd-bts.json", "USD", "BTS", 20.0),
+            // No bot trades these.
+            market_file("eth-btc.json", "ETH", "BTC", 0.05),
+        ];
+        let rates = rates(&bots, &files)
+            .into_iter()
+            .map(|r| (r.base, r.quote, r.price, r.source))
+            .collect::<Vec<_>>();
+        let expected = [
+            ("XRP", "BTS", 2.0, "xrp-bts.json"),
+            ("USD", "BTS", 20.0, "usd-bts.json"),
+            ("EUR", "USD", 1.1, "eur grid centre"),
+        ]
+        .map(|(b, q, p, s)| (b.to_string(), q.to_string(), p, s.to_string()));
+        assert_eq!(rates, expected);
+    }
+}
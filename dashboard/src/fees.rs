@@ -0,0 +1,333 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+use crate::{
+    exposure::Exposure,
+    fills::{self, FeeLine, Fill},
+    logline::{self, LineParser, LogLine, TailRead, TailReader},
+    state::BotStatus,
+};
+
+const HOUR_MS: i64 = 3_600_000;
+/// `btsFeesOwed` changes kept per bot for the trend line.
+const MAX_OWED_HISTORY: usize = 12;
+/// Window the fee share of proceeds is alerted over.
+const SHARE_WINDOW_MS: i64 = 24 * HOUR_MS;
+
+/// Fees and proceeds summed over a time window, per asset.
+#[derive(Clone, Debug, Default)]
+pub struct FeeTotals {
+    pub maker_fills: u32,
+    pub fees: BTreeMap<String, f64>,
+    pub proceeds: BTreeMap<String, f64>,
+}
+
+impl FeeTotals {
+    /// Fees as a percentage of proceeds, both valued in the first proceeds asset
+    /// with the market-file or grid-centre rates. `Ok(None)` when there are no
+    /// proceeds; `Err` names the missing rate, which leaves the alert inactive.
+    pub fn share(&self, exposure: &Exposure) -> Result<Option<f64>, String> {
+        let Some(reference) = self.proceeds.keys().next() else {
+            return Ok(None);
+        };
+        let value = |amounts: &BTreeMap<String, f64>| -> Result<f64, String> {
+            amounts
+                .iter()
+                .map(|(asset, amount)| {
+                    exposure
+                        .convert(asset, reference)
+                        .map(|(price, _)| amount * price)
+                        .ok_or_else(|| format!("no rate from {asset} to {reference}"))
+                })
+                .sum()
+        };
+        let proceeds = value(&self.proceeds)?;
+        if proceeds <= 0.0 {
+            return Ok(None);
+        }
+        Ok(Some(value(&self.fees)? / proceeds * 100.0))
+    }
+}
+
+/// Totals over one of the windows the Fees tab lists.
+#[derive(Clone, Debug)]
+pub struct FeeWindow {
+    pub label: &'static str,
+    pub totals: FeeTotals,
+    /// The window starts before the oldest line read, so the totals undercount.
+    pub partial: bool,
+}
+
+/// `[FILL]` and `[FEES]` lines of one bot's log, shared by the Fees, Chart and
+/// Cycles tabs. Lines are added as the log grows, so the ledger covers everything
+/// written since the dashboard first read the log.
+#[derive(Clone, Debug, Default)]
+pub struct FeeLedger {
+    pub fills: Vec<Fill>,
+    /// Oldest first.
+    pub fees: Vec<FeeLine>,
+    /// Stamp of the oldest line read when the first read started mid-file; lines
+    /// before it were never seen.
+    pub scanned_from_ms: Option<i64>,
+}
+
+impl FeeLedger {
+    pub fn ingest(&mut self, lines: &[LogLine]) {
+        fills::collect(lines, &mut self.fills, &mut self.fees);
+    }
+
+    /// Totals since `since_ms`; `None` sums everything read.
+    pub fn totals(&self, since_ms: Option<i64>) -> FeeTotals {
+        let since = since_ms.unwrap_or(i64::MIN);
+        let mut totals = FeeTotals::default();
+        for fee in self.fees.iter().filter(|f| f.ts_ms >= since) {
+            totals.maker_fills += fee.fills;
+            *totals.fees.entry(fee.asset.clone()).or_default() += fee.total;
+        }
+        for fill in self.fills.iter().filter(|f| f.ts_ms >= since) {
+            if let Some(p) = &fill.proceeds {
+                *totals.proceeds.entry(p.asset.clone()).or_default() += p.amount;
+            }
+        }
+        totals
+    }
+
+    /// The last 24h, the last 7d and everything read.
+    pub fn windows(&self, now_ms: i64) -> Vec<FeeWindow> {
+        [
+            ("24h", Some(now_ms - 24 * HOUR_MS)),
+            ("7d", Some(now_ms - 7 * 24 * HOUR_MS)),
+            ("all", None),
+        ]
+        .into_iter()
+        .map(|(label, since)| FeeWindow {
+            label,
+            totals: self.totals(since),
+            partial: self
+                .scanned_from_ms
+                .is_some_and(|from| since.is_none_or(|since| since < from)),
+        })
+        .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct FeeLimits {
+    /// Fees above this percentage of proceeds (last 24h) alert.
+    pub proceeds_share_percent: f64,
+    /// How long `btsFeesOwed` may keep rising without a settlement.
+    pub owed_growth_ms: i64,
+}
+
+/// Cross-poll fee state: ledgers fed with the lines appended to each log since the
+/// last poll, and the `btsFeesOwed` values seen in the orders files.
+#[derive(Debug, Default)]
+pub struct FeeTracker {
+    bots: HashMap<String, TrackedFees>,
+}
+
+#[derive(Debug, Default)]
+struct TrackedFees {
+    reader: Option<TailReader>,
+    parser: LineParser,
+    ledger: FeeLedger,
+    /// `(epoch ms, btsFeesOwed)` changes, oldest first.
+    owed: VecDeque<(i64, f64)>,
+    /// First rise of `btsFeesOwed` since it last went down, and the value before it.
+    growing_since: Option<(i64, f64)>,
+}
+
+impl FeeTracker {
+    /// Records one poll and returns the alerts that apply.
+    pub fn observe(
+        &mut self,
+        bots: &[BotStatus],
+        exposure: &Exposure,
+        limits: FeeLimits,
+        now_ms: i64,
+    ) -> Vec<String> {
+        self.bots.retain(|name, _| bots.iter().any(|b| b.name == *name));
+        let mut alerts = Vec::new();
+        for bot in bots {
+            let tracked = self.bots.entry(bot.name.clone()).or_default();
+            tracked.reload(bot);
+            if let Some(owed) = bot.bts_fees_owed {
+                tracked.record_owed(owed, now_ms);
+            }
+
+            if let Some((since, first)) = tracked
+                .growing_since
+                .filter(|(since, _)| now_ms - since >= limits.owed_growth_ms)
+            {
+                alerts.push(format!(
+                    "{}: btsFeesOwed rising for {}m without settlement ({first:.8} -> {:.8} BTS).",
+                    bot.name,
+                    (now_ms - since) / 60_000,
+                    bot.bts_fees_owed.unwrap_or_default()
+                ));
+            }
+            let recent = tracked.ledger.totals(Some(now_ms - SHARE_WINDOW_MS));
+            if let Ok(Some(share)) = recent.share(exposure) {
+                if share > limits.proceeds_share_percent {
+                    alerts.push(format!(
+                        "{}: fees are {share:.1}% of proceeds over 24h (threshold {:.1}%).",
+                        bot.name, limits.proceeds_share_percent
+                    ));
+                }
+            }
+        }
+        alerts
+    }
+
+    pub fn ledger(&self, name: &str) -> Option<&FeeLedger> {
+        self.bots.get(name).map(|t| &t.ledger)
+    }
+
+    /// Recorded `btsFeesOwed` changes for a bot, oldest first.
+    pub fn owed_history(&self, name: &str) -> Vec<(i64, f64)> {
+        self.bots
+            .get(name)
+            .map(|t| t.owed.iter().copied().collect())
+            .unwrap_or_default()
+    }
+}
+
+impl TrackedFees {
+    fn reload(&mut self, bot: &BotStatus) {
+        let Some(path) = bot.identity.out_log.as_ref() else {
+            self.reader = None;
+            self.ledger = FeeLedger::default();
+            return;
+        };
+        let reader = match &mut self.reader {
+            Some(reader) if reader.path() == path => reader,
+            slot => slot.insert(TailReader::new(path.clone(), fills::MAX_FILL_LOG_BYTES)),
+        };
+        let (raw, reset) = match reader.read() {
+            TailRead::Reset(raw) => (raw, true),
+            TailRead::Appended(raw) if raw.is_empty() => return,
+            TailRead::Appended(raw) => (raw, false),
+        };
+        if reset {
+            self.parser = LineParser::default();
+            self.ledger = FeeLedger::default();
+        }
+        let lines = self.parser.parse(raw.iter().map(String::as_str));
+        if reset && reader.truncated() {
+            // Without a stamp to go by, every window is partial.
+            self.ledger.scanned_from_ms = Some(
+                lines
+                    .iter()
+                    .find_map(|l| logline::epoch_ms(l.timestamp.as_deref()?))
+                    .unwrap_or(i64::MAX),
+            );
+        }
+        self.ledger.ingest(&lines);
+    }
+
+    fn record_owed(&mut self, owed: f64, now_ms: i64) {
+        let last = self.owed.back().map(|(_, v)| *v);
+        if last.is_some_and(|l| (l - owed).abs() <= 1e-12) {
+            return;
+        }
+        match last {
+            Some(l) if owed > l => {
+                self.growing_since.get_or_insert((now_ms, l));
+            }
+            _ => self.growing_since = None,
+        }
+        self.owed.push_back((now_ms, owed));
+        if self.owed.len() > MAX_OWED_HISTORY {
+            self.owed.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs, io::Write};
+
+    use crate::{exposure::Rate, testutil::ScratchDir};
+
+    #[test]
+    fn owed_growth_resets_on_settlement() {
+        let mut tracked = TrackedFees::default();
+        tracked.record_owed(0.1, 0);
+        assert_eq!(tracked.growing_since, None);
+        tracked.record_owed(0.2, 10);
+        tracked.record_owed(0.2, 20);
+        tracked.record_owed(0.3, 30);
+        assert_eq!(tracked.growing_since, Some((10, 0.1)));
+        assert_eq!(tracked.owed.len(), 3);
+        tracked.record_owed(0.0, 40);
+        assert_eq!(tracked.growing_since, None);
+    }
+
+    #[test]
+    fn share_values_fees_in_the_proceeds_asset() {
+        let totals = FeeTotals {
+            maker_fills: 3,
+            fees: BTreeMap::from([(String::from("BTS"), 1.0), (String::from("XRP"), 0.5)]),
+            proceeds: BTreeMap::from([(String::from("XRP"), 20.0)]),
+        };
+        let mut exposure = Exposure::default();
+        assert_eq!(totals.share(&exposure), Err(String::from("no rate from BTS to XRP")));
+
+        exposure.rates.push(Rate {
+            base: String::from("XRP"),
+            quote: String::from("BTS"),
+            price: 2.0,
+            source: String::from("XRP/BTS market"),
+        });
+        // 1 BTS is 0.5 XRP, so 1 XRP of fees against 20 XRP of proceeds.
+        assert_eq!(totals.share(&exposure), Ok(Some(5.0)));
+        assert_eq!(FeeTotals::default().share(&exposure), Ok(None));
+    }
+
+    #[test]
+    fn windows_older_than_the_first_line_read_are_partial() {
+        let now = 30 * 24 * HOUR_MS;
+        let mut ledger = FeeLedger::default();
+        let partial = |ledger: &FeeLedger| {
+            ledger
+                .windows(now)
+                .iter()
+                .map(|w| (w.label, w.partial))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(partial(&ledger), [("24h", false), ("7d", false), ("all", false)]);
+        ledger.scanned_from_ms = Some(now - 2 * 24 * HOUR_MS);
+        assert_eq!(partial(&ledger), [("24h", false), ("7d", true), ("all", true)]);
+    }
+
+    #[test]
+    fn reload_adds_only_the_appended_lines() {
+        let dir = ScratchDir::new("fees");
+        let path = dir.join("bot-out.log");
+        let mut bot = BotStatus::stub("bot", "bot-0");
+        bot.identity.out_log = Some(path.clone());
+        fs::write(
+            &path,
+            "[2026-01-15T15:00:00.000Z] [DEBUG] [FILL] sell fill: size=1, price=2, proceeds=2 BTS\n",
+        )
+        .unwrap();
+
+        let mut tracked = TrackedFees::default();
+        tracked.reload(&bot);
+        assert_eq!(tracked.ledger.fills.len(), 1);
+
+        let mut file = fs::OpenOptions::new().append(true).open(&path).unwrap();
+        writeln!(
+            file,
+            "[2026-01-15T15:00:01.000Z] [INFO] [FEES] BTS fees calculated: 1 maker fills @ 0.1 BTS = 0.1 BTS"
+        )
+        .unwrap();
+        tracked.reload(&bot);
+        tracked.reload(&bot);
+        assert_eq!(tracked.ledger.fills.len(), 1);
+        assert_eq!(tracked.ledger.fees.len(), 1);
+        // The fee line priced the fill read on the earlier poll.
+        assert!(tracked.ledger.fills[0].fee.is_some());
+        assert_eq!(tracked.ledger.scanned_from_ms, None);
+    }
+}
@@ -0,0 +1,198 @@
+use crate::{
+    grid::Side,
+    logline::{self, LogLine},
+};
+
+/// How much of a bot log is scanned for `[FILL]` and `[FEES]` lines.
+pub const MAX_FILL_LOG_BYTES: u64 = 2 * 1024 * 1024;
+/// Longest gap between a fill and the `[FEES]` line that prices it.
+const FEE_LINK_MS: i64 = 5_000;
+
+/// One `[FILL]` line in the format `modules/order/export.js` parses:
+/// `[ts] [DEBUG] [FILL] sell fill: size=0.0316, price=1791.30, proceeds=56.60 BTS`.
+#[derive(Clone, Debug)]
+pub struct Fill {
+    pub ts_ms: i64,
+    pub side: Side,
+    pub size: f64,
+    pub price: f64,
+    pub proceeds: Option<AssetAmount>,
+    /// Fee charged for this fill, linked from the next `[FEES]` line.
+    pub fee: Option<AssetAmount>,
+}
+
+#[derive(Clone, Debug)]
+pub struct AssetAmount {
+    pub amount: f64,
+    pub asset: String,
+}
+
+/// One `[FEES]` line: `[FEES] BTS fees calculated: 1 maker fills @ 0.04826000 BTS = 0.04826000 BTS`.
+#[derive(Clone, Debug)]
+pub struct FeeLine {
+    pub ts_ms: i64,
+    pub fills: u32,
+    pub per_fill: f64,
+    pub asset: String,
+    pub total: f64,
+}
+
+pub fn parse_fill(line: &LogLine) -> Option<Fill> {
+    if !line.categories.iter().any(|c| c == "FILL") {
+        return None;
+    }
+    let ts_ms = logline::epoch_ms(line.timestamp.as_deref()?)?;
+    let (_, rest) = line.text.split_once("[FILL]")?;
+    let (side, rest) = rest.trim_start().split_once(" fill:")?;
+    let side = Side::parse(side.trim()).filter(|s| *s != Side::Spread)?;
+    Some(Fill {
+        ts_ms,
+        side,
+        size: field(rest, "size=")?,
+        price: field(rest, "price=")?,
+        proceeds: field(rest, "proceeds=").map(|amount| AssetAmount {
+            amount,
+            asset: rest
+                .split_once("proceeds=")
+                .and_then(|(_, p)| p.split_whitespace().nth(1))
+                .unwrap_or_default()
+                .to_string(),
+        }),
+        fee: None,
+    })
+}
+
+pub fn parse_fee(line: &LogLine) -> Option<FeeLine> {
+    if !line.categories.iter().any(|c| c == "FEES") {
+        return None;
+    }
+    let ts_ms = logline::epoch_ms(line.timestamp.as_deref()?)?;
+    let (_, rest) = line.text.split_once("[FEES]")?;
+    let (head, total) = rest.split_once(" maker fills @ ")?;
+    let fills = head.split_whitespace().last()?.parse::<u32>().ok()?;
+    let (per_fill, total) = total.split_once('=')?;
+    let mut per_fill = per_fill.split_whitespace();
+    Some(FeeLine {
+        ts_ms,
+        fills,
+        per_fill: per_fill.next()?.parse().ok()?,
+        asset: per_fill.next()?.to_string(),
+        total: total.split_whitespace().next()?.parse().ok()?,
+    })
+}
+
+/// Fills and `[FEES]` lines, oldest first. A fee line covers the `N` latest fills
+/// logged within 5s before it, as `linkFillWithFee` in `export.js` assumes.
+/// Adds the `[FILL]` and `[FEES]` lines among `lines` to `fills` and `fees`. A fee
+/// line prices the latest unpriced fills before it, including fills collected from
+/// an earlier chunk of the same log.
+pub fn collect(lines: &[LogLine], fills: &mut Vec<Fill>, fees: &mut Vec<FeeLine>) {
+    for line in lines {
+        if let Some(fill) = parse_fill(line) {
+            fills.push(fill);
+        } else if let Some(fee) = parse_fee(line) {
+            fills
+                .iter_mut()
+                .rev()
+                .take_while(|f| fee.ts_ms - f.ts_ms <= FEE_LINK_MS)
+                .filter(|f| f.fee.is_none())
+                .take(fee.fills as usize)
+                .for_each(|f| {
+                    f.fee = Some(AssetAmount {
+                        amount: fee.per_fill,
+                        asset: fee.asset.clone(),
+                    })
+                });
+            fees.push(fee);
+        }
+    }
+}
+
+fn field(text: &str, key: &str) -> Option<f64> {
+    let (_, rest) = text.split_once(key)?;
+    let end = rest
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-' || c == 'e' || c == 'E'))
+        .unwrap_or(rest.len());
+    rest[..end].parse::<f64>().ok().filter(|n| n.is_finite())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Sample lines from the header of `modules/order/export.js`.
+    const FILL: &str = "[2026-01-15T15:29:06.185Z] [DEBUG] [FILL] sell fill: size=0.0316, price=1791.30065898866, proceeds=56.60510082 BTS";
+    const FEE: &str = "[2026-01-15T15:29:06.185Z] [INFO] [FEES] BTS fees calculated: 1 maker fills @ 0.04826000 BTS = 0.04826000 BTS";
+
+    fn parsed(raw: &str) -> LogLine {
+        logline::parse_lines([raw]).remove(0)
+    }
+
+    #[test]
+    fn parses_the_export_js_samples() {
+        let fill = parse_fill(&parsed(FILL)).unwrap();
+        assert_eq!(fill.ts_ms, logline::epoch_ms("2026-01-15 15:29:06.185").unwrap());
+        assert_eq!(fill.side, Side::Sell);
+        assert_eq!((fill.size, fill.price), (0.0316, 1791.30065898866));
+        let proceeds = fill.proceeds.unwrap();
+        assert_eq!((proceeds.amount, proceeds.asset.as_str()), (56.60510082, "BTS"));
+        assert!(parse_fee(&parsed(FILL)).is_none());
+
+        let fee = parse_fee(&parsed(FEE)).unwrap();
+        assert_eq!((fee.fills, fee.per_fill, fee.total), (1, 0.04826, 0.04826));
+        assert_eq!(fee.asset, "BTS");
+        assert!(parse_fill(&parsed(FEE)).is_none());
+    }
+
+    #[test]
+    fn field_stops_at_the_number_and_rejects_garbage() {
+        assert_eq!(field("size=1.5e-3, price=2", "size="), Some(0.0015));
+        assert_eq!(field("price=-0.25 BTS", "price="), Some(-0.25));
+        assert_eq!(field("size=, price=2", "size="), None);
+        assert_eq!(field("price=2", "size="), None);
+        // A buy without proceeds still parses; a spread "fill" does not.
+        let buy = parsed("[2026-01-15T15:29:07.000Z] [DEBUG] [FILL] buy fill: size=3, price=0.5");
+        assert!(parse_fill(&buy).unwrap().proceeds.is_none());
+        let spread = parsed("[2026-01-15T15:29:07.000Z] [DEBUG] [FILL] spread fill: size=3, price=0.5");
+        assert!(parse_fill(&spread).is_none());
+    }
+
+    #[test]
+    fn fee_lines_price_the_latest_unpriced_fills_within_the_window() {
+        let log = [
+            fill_line("00:00.000", 1),
+            // Too old for the fee line below.
+            fill_line("00:10.000", 2),
+            fill_line("00:16.000", 3),
+            fill_line("00:17.000", 4),
+            fee_line("00:18.000", 1),
+            // Prices fill 3 only: fill 4 already has its fee.
+            fee_line("00:19.000", 5),
+        ];
+
+        let (mut fills, mut fees) = (Vec::new(), Vec::new());
+        collect(&logline::parse_lines(log.iter().map(String::as_str)), &mut fills, &mut fees);
+        let priced = fills.iter().map(|f| f.fee.is_some()).collect::<Vec<_>>();
+        assert_eq!(priced, [false, false, true, true]);
+        assert_eq!(fills[3].fee.as_ref().unwrap().amount, 0.1);
+        assert_eq!(fees.len(), 2);
+        assert_eq!(fills[0].proceeds.as_ref().unwrap().asset, "XRP");
+    }
+
+    #[test]
+    fn fee_lines_price_fills_from_an_earlier_chunk() {
+        let (mut fills, mut fees) = (Vec::new(), Vec::new());
+        collect(&[parsed(&fill_line("00:00.000", 1))], &mut fills, &mut fees);
+        collect(&[parsed(&fee_line("00:01.000", 1))], &mut fills, &mut fees);
+        assert!(fills[0].fee.is_some());
+        assert_eq!(fees.len(), 1);
+    }
+
+    fn fill_line(ts: &str, size: u32) -> String {
+        format!("[2026-01-15T15:{ts}Z] [DEBUG] [FILL] buy fill: size={size}, price=1, proceeds={size} XRP")
+    }
+
+    fn fee_line(ts: &str, n: u32) -> String {
+        format!("[2026-01-15T15:{ts}Z] [INFO] [FEES] BTS fees calculated: {n} maker fills @ 0.1 BTS = 0.{n} BTS")
+    }
+}
@@ -0,0 +1,443 @@
+use serde::Deserialize;
+use serde_json::Value;
+
+// Defaults from `DEFAULT_CONFIG` / `GRID_LIMITS` in `modules/constants.js`.
+const DEFAULT_INCREMENT_PERCENT: f64 = 0.5;
+const DEFAULT_TARGET_SPREAD_PERCENT: f64 = 2.0;
+const DEFAULT_PRICE_BOUND: &str = "3x";
+const DEFAULT_WEIGHT: f64 = 0.5;
+const DEFAULT_ACTIVE_ORDERS: usize = 20;
+pub const MIN_SPREAD_FACTOR: f64 = 2.1;
+const MIN_SPREAD_ORDERS: usize = 2;
+const MIN_INCREMENT_PERCENT: f64 = 0.01;
+const MAX_INCREMENT_PERCENT: f64 = 10.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    Buy,
+    Sell,
+    Spread,
+}
+
+impl Side {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "buy" => Some(Side::Buy),
+            "sell" => Some(Side::Sell),
+            "spread" => Some(Side::Spread),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Side::Buy => "buy",
+            Side::Sell => "sell",
+            Side::Spread => "spread",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct SidePair {
+    #[serde(default)]
+    pub buy: Value,
+    #[serde(default)]
+    pub sell: Value,
+}
+
+impl SidePair {
+    fn get(&self, side: Side) -> &Value {
+        match side {
+            Side::Sell => &self.sell,
+            _ => &self.buy,
+        }
+    }
+}
+
+/// Grid-shaping fields of a `profiles/bots.json` entry, kept as raw JSON so that
+/// numeric strings, `"pool"` start prices and `"3x"` bounds survive parsing.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct GridConfig {
+    #[serde(default, rename = "startPrice")]
+    pub start_price: Value,
+    #[serde(default, rename = "minPrice")]
+    pub min_price: Value,
+    #[serde(default, rename = "maxPrice")]
+    pub max_price: Value,
+    #[serde(default, rename = "incrementPercent")]
+    pub increment_percent: Value,
+    #[serde(default, rename = "targetSpreadPercent")]
+    pub target_spread_percent: Value,
+    #[serde(default, rename = "weightDistribution")]
+    pub weight_distribution: Option<SidePair>,
+    #[serde(default, rename = "botFunds")]
+    pub bot_funds: Option<SidePair>,
+    #[serde(default, rename = "activeOrders")]
+    pub active_orders: Option<SidePair>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FundSpec {
+    Absolute(f64),
+    Percent(f64),
+}
+
+impl GridConfig {
+    pub fn increment_percent(&self) -> f64 {
+        number(&self.increment_percent).unwrap_or(DEFAULT_INCREMENT_PERCENT)
+    }
+
+    pub fn target_spread_percent(&self) -> f64 {
+        number(&self.target_spread_percent).unwrap_or(DEFAULT_TARGET_SPREAD_PERCENT)
+    }
+
+    /// Numeric start price, or `None` for market-derived sources such as `"pool"`.
+    pub fn start_price(&self) -> Option<f64> {
+        number(&self.start_price).filter(|p| *p > 0.0)
+    }
+
+    pub fn weight(&self, side: Side) -> f64 {
+        self.weight_distribution
+            .as_ref()
+            .and_then(|pair| number(pair.get(side)))
+            .unwrap_or(DEFAULT_WEIGHT)
+    }
+
+    pub fn active_orders(&self, side: Side) -> usize {
+        self.active_orders
+            .as_ref()
+            .and_then(|pair| number(pair.get(side)))
+            .map(|n| n.max(0.0) as usize)
+            .unwrap_or(DEFAULT_ACTIVE_ORDERS)
+    }
+
+    pub fn bot_funds(&self, side: Side) -> FundSpec {
+        self.bot_funds
+            .as_ref()
+            .and_then(|pair| fund_spec(pair.get(side)))
+            .unwrap_or(FundSpec::Percent(100.0))
+    }
+
+    pub fn min_price(&self, reference: f64) -> Option<f64> {
+        price_bound(&self.min_price, reference, false)
+    }
+
+    pub fn max_price(&self, reference: f64) -> Option<f64> {
+        price_bound(&self.max_price, reference, true)
+    }
+}
+
+/// Lenient numeric read: accepts JSON numbers and numeric strings.
+pub fn number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse::<f64>().ok(),
+        _ => None,
+    }
+    .filter(|n| n.is_finite())
+}
+
+fn fund_spec(value: &Value) -> Option<FundSpec> {
+    if let Value::String(s) = value {
+        if let Some(pct) = s.trim().strip_suffix('%') {
+            return pct.trim().parse::<f64>().ok().map(FundSpec::Percent);
+        }
+    }
+    number(value).map(FundSpec::Absolute)
+}
+
+/// Mirrors `resolveConfiguredPriceBound`: numbers are absolute, `"Nx"` is relative
+/// to the reference price (divided for the lower bound, multiplied for the upper).
+fn price_bound(value: &Value, reference: f64, upper: bool) -> Option<f64> {
+    let raw = match value {
+        Value::Null => Value::String(DEFAULT_PRICE_BOUND.to_string()),
+        Value::String(s) if s.trim().is_empty() => Value::String(DEFAULT_PRICE_BOUND.to_string()),
+        other => other.clone(),
+    };
+    if let Value::String(s) = &raw {
+        let lowered = s.trim().to_ascii_lowercase();
+        if let Some(mult) = lowered.strip_suffix('x') {
+            let mult = mult.parse::<f64>().ok().filter(|m| *m > 0.0)?;
+            return Some(if upper { reference * mult } else { reference / mult });
+        }
+    }
+    number(&raw)
+}
+
+/// Mirrors `calculateGapSlots` in `modules/order/utils/math.js`.
+pub fn gap_slots(increment_percent: f64, target_spread_percent: f64) -> usize {
+    let increment = if increment_percent > 0.0 {
+        increment_percent
+    } else {
+        DEFAULT_INCREMENT_PERCENT
+    };
+    let step = 1.0 + increment / 100.0;
+    let effective = target_spread_percent.max(increment * MIN_SPREAD_FACTOR);
+    let required = ((1.0 + effective / 100.0).ln() / step.ln()).ceil() as i64;
+    MIN_SPREAD_ORDERS.max((required - 1).max(0) as usize)
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SideFunds {
+    pub buy: f64,
+    pub sell: f64,
+}
+
+#[derive(Clone, Debug)]
+pub struct IdealSlot {
+    pub index: usize,
+    pub price: f64,
+    pub side: Side,
+    pub active: bool,
+    pub size: f64,
+}
+
+#[derive(Clone, Debug)]
+pub struct IdealGrid {
+    pub slots: Vec<IdealSlot>,
+    pub reference_price: f64,
+    pub min_price: f64,
+    pub max_price: f64,
+    pub gap_slots: usize,
+}
+
+impl IdealGrid {
+    pub fn count(&self, side: Side, active: bool) -> usize {
+        self.slots
+            .iter()
+            .filter(|s| s.side == side && s.active == active)
+            .count()
+    }
+}
+
+/// Rebuilds the geometric master rail the bot would create around `reference_price`
+/// (`Grid.createOrderGrid`), assigns BUY/SPREAD/SELL roles, marks the `activeOrders`
+/// slots nearest the spread as active and sizes each side with `weightDistribution`.
+pub fn build_ideal_grid(
+    config: &GridConfig,
+    reference_price: f64,
+    funds: SideFunds,
+) -> Result<IdealGrid, String> {
+    if !(reference_price.is_finite() && reference_price > 0.0) {
+        return Err(format!("invalid reference price {reference_price}"));
+    }
+    let increment = config.increment_percent();
+    if !(MIN_INCREMENT_PERCENT..=MAX_INCREMENT_PERCENT).contains(&increment) {
+        return Err(format!(
+            "incrementPercent {increment} outside [{MIN_INCREMENT_PERCENT}, {MAX_INCREMENT_PERCENT}]"
+        ));
+    }
+    let min_price = config
+        .min_price(reference_price)
+        .ok_or_else(|| String::from("unreadable minPrice"))?;
+    let max_price = config
+        .max_price(reference_price)
+        .ok_or_else(|| String::from("unreadable maxPrice"))?;
+    if !(min_price > 0.0 && min_price < max_price) {
+        return Err(format!("invalid bounds [{min_price}, {max_price}]"));
+    }
+    if !(min_price..=max_price).contains(&reference_price) {
+        return Err(format!(
+            "reference price {reference_price} outside bounds [{min_price}, {max_price}]"
+        ));
+    }
+
+    let step_up = 1.0 + increment / 100.0;
+    let step_down = 1.0 - increment / 100.0;
+    let mut prices = Vec::new();
+    let mut up = reference_price * step_up.sqrt();
+    while up <= max_price {
+        prices.push(up);
+        up *= step_up;
+    }
+    let mut down = reference_price * step_down.sqrt();
+    while down >= min_price {
+        prices.push(down);
+        down *= step_down;
+    }
+    prices.sort_by(f64::total_cmp);
+    if prices.is_empty() {
+        return Err(String::from("grid produced no price levels"));
+    }
+
+    let gap = gap_slots(increment, config.target_spread_percent());
+    let split = prices
+        .iter()
+        .position(|p| *p >= reference_price)
+        .unwrap_or(prices.len()) as i64;
+    let boundary = (split - (gap / 2) as i64 - 1).clamp(0, prices.len() as i64 - 1) as usize;
+    let sell_start = boundary + gap + 1;
+
+    let mut slots = prices
+        .iter()
+        .enumerate()
+        .map(|(index, price)| IdealSlot {
+            index,
+            price: *price,
+            side: if index <= boundary {
+                Side::Buy
+            } else if index >= sell_start {
+                Side::Sell
+            } else {
+                Side::Spread
+            },
+            active: false,
+            size: 0.0,
+        })
+        .collect::<Vec<_>>();
+
+    let buy_idx = slots
+        .iter()
+        .filter(|s| s.side == Side::Buy)
+        .map(|s| s.index)
+        .collect::<Vec<_>>();
+    let sell_idx = slots
+        .iter()
+        .filter(|s| s.side == Side::Sell)
+        .map(|s| s.index)
+        .collect::<Vec<_>>();
+    if buy_idx.is_empty() || sell_idx.is_empty() {
+        return Err(format!(
+            "imbalanced rail (buy={}, sell={})",
+            buy_idx.len(),
+            sell_idx.len()
+        ));
+    }
+
+    let factor = increment / 100.0;
+    let buy_sizes = allocate_by_weights(
+        funds.buy,
+        buy_idx.len(),
+        config.weight(Side::Buy),
+        factor,
+        true,
+    );
+    let sell_sizes = allocate_by_weights(
+        funds.sell,
+        sell_idx.len(),
+        config.weight(Side::Sell),
+        factor,
+        false,
+    );
+    for (i, idx) in buy_idx.iter().enumerate() {
+        slots[*idx].size = buy_sizes[i];
+    }
+    for (i, idx) in sell_idx.iter().enumerate() {
+        slots[*idx].size = sell_sizes[i];
+    }
+
+    // Active windows hug the spread: highest buys and lowest sells.
+    for idx in buy_idx.iter().rev().take(config.active_orders(Side::Buy)) {
+        slots[*idx].active = true;
+    }
+    for idx in sell_idx.iter().take(config.active_orders(Side::Sell)) {
+        slots[*idx].active = true;
+    }
+
+    Ok(IdealGrid {
+        slots,
+        reference_price,
+        min_price,
+        max_price,
+        gap_slots: gap,
+    })
+}
+
+/// Mirrors `allocateFundsByWeights` without blockchain precision quantization.
+pub fn allocate_by_weights(
+    total: f64,
+    n: usize,
+    weight: f64,
+    increment_factor: f64,
+    reverse: bool,
+) -> Vec<f64> {
+    if n == 0 {
+        return vec![];
+    }
+    if !(total.is_finite() && total > 0.0) {
+        return vec![0.0; n];
+    }
+    let base = 1.0 - increment_factor;
+    let raw = (0..n)
+        .map(|i| {
+            let idx = if reverse { n - 1 - i } else { i };
+            base.powf(idx as f64 * weight)
+        })
+        .collect::<Vec<_>>();
+    let sum = raw.iter().sum::<f64>();
+    let sum = if sum > 0.0 { sum } else { 1.0 };
+    raw.into_iter().map(|w| w / sum * total).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Values printed by `Grid.createOrderGrid` and `calculateOrderSizes` in
+    /// `modules/order` for `reference_config()` with buy funds 100 and sell funds 50.
+    const JS_LADDER: [(Side, f64, f64); 8] = [
+        (Side::Buy, 0.9654353152371162, 33.16596873049548),
+        (Side::Buy, 0.9751871871081982, 33.33305275385252),
+        (Side::Buy, 0.9850375627355538, 33.500978515651994),
+        (Side::Spread, 0.99498743710662, 0.0),
+        (Side::Spread, 1.004987562112089, 0.0),
+        (Side::Sell, 1.0150374377332099, 16.834450018517895),
+        (Side::Sell, 1.025187812110542, 16.666105518332717),
+        (Side::Sell, 1.0354396902316474, 16.499444463149388),
+    ];
+
+    fn reference_config() -> GridConfig {
+        serde_json::from_value(serde_json::json!({
+            "startPrice": 1,
+            "minPrice": 0.96,
+            "maxPrice": "1.04",
+            "incrementPercent": 1,
+            "targetSpreadPercent": 2,
+            "weightDistribution": {"buy": 0.5, "sell": 1},
+            "activeOrders": {"buy": 2, "sell": 1},
+        }))
+        .unwrap()
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() <= 1e-12 * b.abs().max(1.0)
+    }
+
+    #[test]
+    fn ideal_grid_matches_the_bot_ladder() {
+        let grid = build_ideal_grid(&reference_config(), 1.0, SideFunds { buy: 100.0, sell: 50.0 }).unwrap();
+        assert_eq!(grid.gap_slots, 2);
+        assert_eq!(grid.slots.len(), JS_LADDER.len());
+        for (slot, (side, price, size)) in grid.slots.iter().zip(JS_LADDER) {
+            assert_eq!(slot.side, side, "slot {}", slot.index);
+            assert!(close(slot.price, price), "slot {}: {} vs {price}", slot.index, slot.price);
+            assert!(close(slot.size, size), "slot {}: {} vs {size}", slot.index, slot.size);
+        }
+        // Active windows hug the spread.
+        let active = grid.slots.iter().filter(|s| s.active).map(|s| s.index).collect::<Vec<_>>();
+        assert_eq!(active, [1, 2, 5]);
+    }
+
+    #[test]
+    fn gap_slots_and_weights_match_math_js() {
+        for (increment, spread, gap) in [(1.0, 2.0, 2), (0.5, 2.0, 3), (1.0, 0.5, 2), (2.0, 10.0, 4), (0.1, 1.0, 9)] {
+            assert_eq!(gap_slots(increment, spread), gap, "{increment}/{spread}");
+        }
+        let sizes = allocate_by_weights(10.0, 4, 2.0, 0.05, false);
+        let js = [2.8967890229764754, 2.614352093236269, 2.3594527641457326, 2.1294061196415233];
+        for (size, expected) in sizes.iter().zip(js) {
+            assert!(close(*size, expected), "{size} vs {expected}");
+        }
+        assert_eq!(allocate_by_weights(0.0, 2, 1.0, 0.01, true), [0.0, 0.0]);
+    }
+
+    #[test]
+    fn bounds_accept_numbers_strings_and_multipliers() {
+        let config: GridConfig = serde_json::from_value(serde_json::json!({"minPrice": "4x", "maxPrice": 3})).unwrap();
+        assert_eq!(config.min_price(2.0), Some(0.5));
+        assert_eq!(config.max_price(2.0), Some(3.0));
+        assert_eq!(GridConfig::default().max_price(2.0), Some(6.0));
+        assert!(build_ideal_grid(&config, 4.0, SideFunds::default()).is_err());
+    }
+}
@@ -0,0 +1,257 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use crate::runtime::RuntimeStatus;
+
+/// pm2 apps registered by `pm2.js` next to the bots.
+pub const SYSTEM_PROCESSES: [&str; 2] = ["dexbot-cred", "dexbot-update"];
+
+/// The single mapping from a `profiles/bots.json` entry to everything the dashboard
+/// reads for it: the orders file key, the runtime process and its log files.
+#[derive(Debug, Clone)]
+pub struct BotIdentity {
+    pub bot_key: String,
+    /// App name `pm2.js` registers for the entry; `None` when it cannot be resolved
+    /// unambiguously (inactive unnamed entries, duplicate names).
+    pub process_name: Option<String>,
+    pub out_log: Option<PathBuf>,
+    pub error_log: Option<PathBuf>,
+    pub log_source: LogSource,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogSource {
+    /// Paths reported by the runtime (`pm_out_log_path` / `pm_err_log_path`).
+    Runtime,
+    /// `profiles/logs/{name}.log` as written by the `pm2.js` ecosystem config.
+    Convention,
+    Missing,
+}
+
+impl LogSource {
+    pub fn label(self) -> &'static str {
+        match self {
+            LogSource::Runtime => "runtime",
+            LogSource::Convention => "profiles/logs",
+            LogSource::Missing => "missing",
+        }
+    }
+}
+
+pub struct EntryRef<'a> {
+    pub name: Option<&'a str>,
+    pub asset_a: &'a str,
+    pub asset_b: &'a str,
+    pub active: bool,
+}
+
+/// Resolves identities for all entries at once, since process names are only
+/// unique across the whole file. Returns one identity per entry plus alerts for
+/// mappings that could not be made exactly.
+pub fn resolve(entries: &[EntryRef], runtime: &RuntimeStatus) -> (Vec<BotIdentity>, Vec<String>) {
+    let mut alerts = Vec::new();
+
+    // pm2.js names unnamed bots `bot-{i}` by position among *active* entries.
+    let mut active_index = 0;
+    let expected = entries
+        .iter()
+        .map(|entry| {
+            let name = match entry.name.filter(|n| !n.is_empty()) {
+                Some(n) => Some(n.to_string()),
+                None if entry.active => Some(format!("bot-{active_index}")),
+                None => None,
+            };
+            if entry.active {
+                active_index += 1;
+            }
+            name
+        })
+        .collect::<Vec<_>>();
+
+    let mut claims: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (index, name) in expected.iter().enumerate() {
+        if let Some(name) = name {
+            claims.entry(name.as_str()).or_default().push(index);
+        }
+    }
+    let mut duplicated = claims
+        .iter()
+        .filter(|(_, indices)| indices.len() > 1)
+        .collect::<Vec<_>>();
+    duplicated.sort();
+    for (name, indices) in &duplicated {
+        let positions = indices
+            .iter()
+            .map(|i| format!("#{}", i + 1))
+            .collect::<Vec<_>>()
+            .join(", ");
+        alerts.push(format!(
+            "bots.json entries {positions} share process name '{name}'; runtime and logs left unassigned."
+        ));
+    }
+
+    let mut identities = Vec::with_capacity(entries.len());
+    for (index, entry) in entries.iter().enumerate() {
+        let bot_key = bot_key(entry.name, entry.asset_a, entry.asset_b, index);
+        let process_name = expected[index]
+            .clone()
+            .filter(|name| claims.get(name.as_str()).is_some_and(|c| c.len() == 1));
+
+        let process = process_name
+            .as_ref()
+            .and_then(|name| runtime.processes.get(name));
+        let runtime_out = process.and_then(|p| p.out_file.clone()).map(PathBuf::from);
+        let runtime_err = process.and_then(|p| p.error_file.clone()).map(PathBuf::from);
+
+        let (out_log, error_log, log_source) = match (&process_name, runtime_out) {
+            (_, Some(out)) => (Some(out), runtime_err, LogSource::Runtime),
+            (Some(name), None) => {
+                let out = PathBuf::from(format!("profiles/logs/{name}.log"));
+                let err = PathBuf::from(format!("profiles/logs/{name}-error.log"));
+                if out.exists() {
+                    (Some(out), Some(err).filter(|p| p.exists()), LogSource::Convention)
+                } else {
+                    (None, None, LogSource::Missing)
+                }
+            }
+            (None, None) => (None, None, LogSource::Missing),
+        };
+        if let (Some(name), Some(_), LogSource::Missing) = (&process_name, process, log_source) {
+            alerts.push(format!(
+                "{name}: running but no log file (runtime reports none, profiles/logs/{name}.log absent)."
+            ));
+        }
+
+        identities.push(BotIdentity {
+            bot_key,
+            process_name,
+            out_log,
+            error_log,
+            log_source,
+        });
+    }
+
+    let mut orphans = runtime
+        .processes
+        .keys()
+        .filter(|name| !SYSTEM_PROCESSES.contains(&name.as_str()))
+        .filter(|name| !claims.contains_key(name.as_str()))
+        .collect::<Vec<_>>();
+    orphans.sort();
+    for name in orphans {
+        alerts.push(format!(
+            "{} process '{name}' matches no bots.json entry.",
+            runtime.backend
+        ));
+    }
+
+    (identities, alerts)
+}
+
+/// Mirrors `createBotKey` in `modules/account_orders.js`.
+pub fn bot_key(name: Option<&str>, asset_a: &str, asset_b: &str, index: usize) -> String {
+    let identifier = match name.filter(|n| !n.is_empty()) {
+        Some(n) => n.to_string(),
+        None if !asset_a.is_empty() && !asset_b.is_empty() => format!("{asset_a}/{asset_b}"),
+        None => format!("bot-{index}"),
+    };
+    format!("{}-{index}", sanitize_key(&identifier))
+}
+
+pub fn sanitize_key(source: &str) -> String {
+    let mut key = String::new();
+    for c in source.trim().to_lowercase().chars() {
+        if c.is_ascii_lowercase() || c.is_ascii_digit() {
+            key.push(c);
+        } else if !key.ends_with('-') {
+            key.push('-');
+        }
+    }
+    let key = key.trim_matches('-');
+    if key.is_empty() {
+        String::from("bot")
+    } else {
+        key.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::ProcessRecord;
+
+    fn entry(name: Option<&str>, active: bool) -> EntryRef<'_> {
+        EntryRef {
+            name,
+            asset_a: "BTS",
+            asset_b: "USD",
+            active,
+        }
+    }
+
+    fn process(name: &str, out_file: Option<&str>) -> (String, ProcessRecord) {
+        let record = ProcessRecord {
+            name: name.to_string(),
+            status: String::from("online"),
+            out_file: out_file.map(str::to_string),
+            ..ProcessRecord::default()
+        };
+        (name.to_string(), record)
+    }
+
+    #[test]
+    fn resolve_numbers_unnamed_bots_among_active_entries_and_flags_conflicts() {
+        let entries = [
+            entry(None, true),
+            entry(None, false),
+            entry(Some("alpha"), true),
+            entry(Some(""), true),
+            entry(Some("dup"), true),
+            entry(Some("dup"), false),
+        ];
+        let runtime = RuntimeStatus {
+            backend: "pm2",
+            online: true,
+            processes: HashMap::from([
+                process("bot-0", Some("/var/log/bot-0-out.log")),
+                process("bot-2", None),
+                process("dup", Some("/var/log/dup-out.log")),
+                process("dexbot-cred", None),
+                process("bot-1", None),
+                process("stray", None),
+            ]),
+        };
+
+        let (identities, alerts) = resolve(&entries, &runtime);
+        assert_eq!(
+            identities
+                .iter()
+                .map(|i| i.process_name.as_deref())
+                .collect::<Vec<_>>(),
+            // The inactive unnamed entry does not use up a number.
+            [Some("bot-0"), None, Some("alpha"), Some("bot-2"), None, None]
+        );
+        assert_eq!(identities[0].bot_key, "bts-usd-0");
+        assert_eq!(identities[2].bot_key, "alpha-2");
+        assert_eq!(identities[0].out_log, Some(PathBuf::from("/var/log/bot-0-out.log")));
+        assert_eq!(identities[0].log_source, LogSource::Runtime);
+        assert_eq!(identities[4].log_source, LogSource::Missing);
+
+        assert_eq!(
+            alerts,
+            [
+                "bots.json entries #5, #6 share process name 'dup'; runtime and logs left unassigned.",
+                "bot-2: running but no log file (runtime reports none, profiles/logs/bot-2.log absent).",
+                "pm2 process 'bot-1' matches no bots.json entry.",
+                "pm2 process 'stray' matches no bots.json entry.",
+            ]
+        );
+    }
+
+    #[test]
+    fn bot_key_mirrors_create_bot_key() {
+        assert_eq!(bot_key(Some("My Bot!"), "BTS", "USD", 3), "my-bot-3");
+        assert_eq!(bot_key(None, "IOB.XRP", "BTS", 1), "iob-xrp-bts-1");
+        assert_eq!(bot_key(None, "", "BTS", 2), "bot-2-2");
+        assert_eq!(sanitize_key("  --  "), "bot");
+    }
+}
@@ -0,0 +1,219 @@
+use std::path::{Path, PathBuf};
+
+use crate::runtime::{ProcessRecord, RuntimeStatus};
+
+pub const CRED_DAEMON: &str = "dexbot-cred";
+pub const UPDATER: &str = "dexbot-update";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Health {
+    Ok,
+    Warn,
+    Down,
+    /// The backend does not manage this process, so its state cannot be judged.
+    Unknown,
+}
+
+impl Health {
+    pub fn label(self) -> &'static str {
+        match self {
+            Health::Ok => "ok",
+            Health::Warn => "warn",
+            Health::Down => "down",
+            Health::Unknown => "unknown",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct InfraComponent {
+    pub name: &'static str,
+    pub role: &'static str,
+    pub status: String,
+    pub health: Health,
+    pub detail: String,
+    pub process: Option<ProcessRecord>,
+    pub log_path: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpdateResult {
+    Updated,
+    UpToDate,
+    Running,
+    Failed,
+}
+
+impl UpdateResult {
+    pub fn label(&self) -> &'static str {
+        match self {
+            UpdateResult::Updated => "updated",
+            UpdateResult::UpToDate => "up to date",
+            UpdateResult::Running => "running",
+            UpdateResult::Failed => "failed",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UpdaterRun {
+    /// ISO timestamp from the `[...] [UPDATE] Starting ...` line.
+    pub started_at: String,
+    pub result: UpdateResult,
+    pub last_message: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Infrastructure {
+    pub components: Vec<InfraComponent>,
+    pub updater_run: Option<UpdaterRun>,
+}
+
+impl Infrastructure {
+    pub fn component(&self, name: &str) -> Option<&InfraComponent> {
+        self.components.iter().find(|c| c.name == name)
+    }
+
+    /// System processes a bot needs before it can start: every bot unlocks its key
+    /// through the credential daemon (`bot.js` falls back to an interactive prompt,
+    /// which a pm2-managed bot cannot answer).
+    pub fn blockers(&self) -> Vec<&'static str> {
+        self.component(CRED_DAEMON)
+            .filter(|c| c.health == Health::Down)
+            .map(|c| vec![c.name])
+            .unwrap_or_default()
+    }
+}
+
+/// Health of the processes `pm2.js` registers next to the bots. Only the pm2
+/// backend runs them, so on other backends a missing entry is `Unknown`, not down.
+pub fn load_infrastructure(runtime: &RuntimeStatus) -> (Infrastructure, Vec<String>) {
+    let managed = runtime.online && runtime.backend == "pm2";
+    let mut alerts = Vec::new();
+
+    let cred_process = runtime.processes.get(CRED_DAEMON).cloned();
+    let (cred_health, cred_detail) = match &cred_process {
+        Some(p) if p.status == "online" => (Health::Ok, String::from("serving keys")),
+        Some(p) => (Health::Down, format!("pm2 status {}", p.status)),
+        None if managed => (Health::Down, String::from("not registered in pm2")),
+        None => (Health::Unknown, format!("not managed by {}", runtime.backend)),
+    };
+    if cred_health == Health::Down {
+        alerts.push(format!(
+            "{CRED_DAEMON} is down ({cred_detail}); bots cannot unlock keys on start."
+        ));
+    }
+
+    let updater_process = runtime.processes.get(UPDATER).cloned();
+    let updater_log = log_path(UPDATER, updater_process.as_ref());
+    let updater_run = updater_log.as_deref().and_then(|path| {
+        last_updater_run(path, updater_process.as_ref().map(|p| p.status.as_str()))
+    });
+    let (updater_health, updater_detail) = match (&updater_process, &updater_run) {
+        (Some(p), _) if p.status == "errored" => (Health::Down, String::from("pm2 status errored")),
+        (_, Some(run)) if run.result == UpdateResult::Failed => (
+            Health::Warn,
+            format!("last run {} failed: {}", run.started_at, run.last_message),
+        ),
+        (_, Some(run)) => (
+            Health::Ok,
+            format!("last run {} {}", run.started_at, run.result.label()),
+        ),
+        // The updater is a cron app (`autorestart: false`), so `stopped` is its idle state.
+        (Some(_), None) => (Health::Ok, String::from("no run recorded yet")),
+        (None, _) if managed => (
+            Health::Warn,
+            String::from("not registered (UPDATER.ACTIVE disabled?)"),
+        ),
+        (None, _) => (Health::Unknown, format!("not managed by {}", runtime.backend)),
+    };
+    if updater_health != Health::Ok && updater_health != Health::Unknown {
+        alerts.push(format!("{UPDATER}: {updater_detail}."));
+    }
+
+    let components = vec![
+        InfraComponent {
+            name: CRED_DAEMON,
+            role: "credential daemon",
+            status: status_text(cred_process.as_ref()),
+            health: cred_health,
+            detail: cred_detail,
+            log_path: log_path(CRED_DAEMON, cred_process.as_ref()),
+            process: cred_process,
+        },
+        InfraComponent {
+            name: UPDATER,
+            role: "scheduled updater",
+            status: status_text(updater_process.as_ref()),
+            health: updater_health,
+            detail: updater_detail,
+            log_path: updater_log,
+            process: updater_process,
+        },
+    ];
+
+    (
+        Infrastructure {
+            components,
+            updater_run,
+        },
+        alerts,
+    )
+}
+
+fn status_text(process: Option<&ProcessRecord>) -> String {
+    process
+        .map(|p| p.status.clone())
+        .unwrap_or_else(|| String::from("absent"))
+}
+
+fn log_path(name: &str, process: Option<&ProcessRecord>) -> Option<PathBuf> {
+    process
+        .and_then(|p| p.out_file.clone())
+        .map(PathBuf::from)
+        .or_else(|| Some(PathBuf::from(format!("profiles/logs/{name}.log"))))
+        .filter(|p| p.exists())
+}
+
+/// Reads the most recent run from `scripts/update.js` output. A run without a
+/// completion line is still running while pm2 reports it online, otherwise it died
+/// (`update.js` prints `UPDATE FAILED` to the error log and exits 1).
+fn last_updater_run(path: &Path, status: Option<&str>) -> Option<UpdaterRun> {
+    let raw = std::fs::read_to_string(path).ok()?;
+    let lines = raw
+        .lines()
+        .filter_map(|line| {
+            let (_, rest) = line.split_once("[UPDATE] ")?;
+            let ts = line
+                .split_once('[')
+                .and_then(|(_, tail)| tail.split_once(']'))
+                .map(|(ts, _)| ts.to_string())
+                .unwrap_or_default();
+            Some((ts, rest.trim().to_string()))
+        })
+        .collect::<Vec<_>>();
+    let start = lines
+        .iter()
+        .rposition(|(_, msg)| msg.starts_with("Starting DEXBot2 update process"))?;
+    let run = &lines[start..];
+    let last_message = run.last().map(|(_, msg)| msg.clone()).unwrap_or_default();
+    let result = if run
+        .iter()
+        .any(|(_, msg)| msg.contains("update completed successfully"))
+    {
+        UpdateResult::Updated
+    } else if run.iter().any(|(_, msg)| {
+        msg.contains("already up to date") || msg.contains("now tracking the correct branch")
+    }) {
+        UpdateResult::UpToDate
+    } else if status == Some("online") {
+        UpdateResult::Running
+    } else {
+        UpdateResult::Failed
+    };
+    Some(UpdaterRun {
+        started_at: run[0].0.clone(),
+        result,
+        last_message,
+    })
+}
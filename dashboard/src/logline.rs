@@ -0,0 +1,549 @@
+use std::{
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+    iter::Peekable,
+    os::unix::fs::MetadataExt,
+    path::{Path, PathBuf},
+    str::Chars,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl Level {
+    pub const ALL: [Level; 4] = [Level::Debug, Level::Info, Level::Warn, Level::Error];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Level::Debug => "debug",
+            Level::Info => "info",
+            Level::Warn => "warn",
+            Level::Error => "error",
+        }
+    }
+
+    fn parse(tag: &str) -> Option<Self> {
+        match tag {
+            "DEBUG" => Some(Level::Debug),
+            "INFO" => Some(Level::Info),
+            "WARN" | "WARNING" => Some(Level::Warn),
+            "ERROR" | "FATAL" => Some(Level::Error),
+            _ => None,
+        }
+    }
+}
+
+/// One log line with the fields the viewers filter and sort on.
+#[derive(Clone, Debug)]
+pub struct LogLine {
+    pub raw: String,
+    /// `raw` with escape sequences removed, used for matching.
+    pub text: String,
+    /// Normalized `YYYY-MM-DD HH:MM:SS.mmm`, sortable as a string.
+    pub timestamp: Option<String>,
+    pub level: Option<Level>,
+    /// Bracketed tags such as `FILL`, `FEES`, `UPDATE`.
+    pub categories: Vec<String>,
+}
+
+/// Parses lines in file order. Continuation lines (stack traces, multi-line
+/// payloads) carry no prefix of their own and inherit level and timestamp from the
+/// line above, so filters and merges keep them with their event.
+///
+/// The bots stamp lines in UTC (`toISOString`) while pm2's prefix is local time, so
+/// pm2-only stamps are shifted by the offset seen on lines that carry both.
+pub fn parse_lines<'a>(lines: impl IntoIterator<Item = &'a str>) -> Vec<LogLine> {
+    LineParser::default().parse(lines)
+}
+
+/// `parse_lines` for a file read in chunks: the pm2 offset and the context
+/// continuation lines inherit carry over from one chunk to the next.
+#[derive(Debug, Default)]
+pub struct LineParser {
+    pm2_offset_ms: Option<i64>,
+    prev: Option<(Option<Level>, Option<String>)>,
+}
+
+impl LineParser {
+    pub fn parse<'a>(&mut self, lines: impl IntoIterator<Item = &'a str>) -> Vec<LogLine> {
+        let texts = lines
+            .into_iter()
+            .map(|raw| (raw, strip_escapes(raw)))
+            .collect::<Vec<_>>();
+        if self.pm2_offset_ms.is_none() {
+            self.pm2_offset_ms = texts.iter().find_map(|(_, text)| {
+                let local = epoch_ms(&pm2_timestamp(text)?)?;
+                let utc = epoch_ms(&bracket_tags(text).find_map(iso_timestamp)?)?;
+                Some(local - utc)
+            });
+        }
+        let pm2_offset_ms = self.pm2_offset_ms.unwrap_or(0);
+
+        let mut parsed: Vec<LogLine> = Vec::with_capacity(texts.len());
+        for (raw, text) in texts {
+            let timestamp = bracket_tags(&text).find_map(iso_timestamp).or_else(|| {
+                pm2_timestamp(&text)
+                    .and_then(|ts| epoch_ms(&ts))
+                    .map(|ms| format_epoch_ms(ms - pm2_offset_ms))
+            });
+            let mut line = LogLine {
+                raw: raw.to_string(),
+                timestamp,
+                level: None,
+                categories: Vec::new(),
+                text,
+            };
+            for tag in bracket_tags(&line.text) {
+                match Level::parse(tag) {
+                    Some(level) if line.level.is_none() => line.level = Some(level),
+                    Some(_) => {}
+                    None if is_category(tag) => line.categories.push(tag.to_string()),
+                    None => {}
+                }
+            }
+            if let Some((level, timestamp)) = &self.prev {
+                if line.timestamp.is_none() && line.level.is_none() {
+                    line.level = *level;
+                    line.timestamp = timestamp.clone();
+                }
+            }
+            self.prev = Some((line.level, line.timestamp.clone()));
+            parsed.push(line);
+        }
+        parsed
+    }
+}
+
+/// Reads at most the last `max_bytes` of a file, dropping the first partial line.
+pub fn read_tail(path: &Path, max_bytes: u64) -> Vec<String> {
+    let Ok(mut file) = File::open(path) else {
+        return vec![];
+    };
+    let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+    let start = len.saturating_sub(max_bytes);
+    let Some(buf) = read_from(&mut file, start) else {
+        return vec![];
+    };
+    let text = String::from_utf8_lossy(&buf);
+    let mut lines = text.lines().map(str::to_string).collect::<Vec<_>>();
+    if start > 0 && !lines.is_empty() {
+        lines.remove(0);
+    }
+    lines
+}
+
+fn read_from(file: &mut File, start: u64) -> Option<Vec<u8>> {
+    file.seek(SeekFrom::Start(start)).ok()?;
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).ok()?;
+    Some(buf)
+}
+
+/// What `TailReader::read` found since the previous call.
+#[derive(Debug, PartialEq, Eq)]
+pub enum TailRead {
+    /// First read, or the file was truncated, replaced or grew by more than the
+    /// tail size: these lines replace everything read before.
+    Reset(Vec<String>),
+    /// Lines completed since the last read, possibly none.
+    Appended(Vec<String>),
+}
+
+/// Follows a growing log. The first read takes the last `max_bytes`; later reads
+/// only take the bytes appended since. A line still being written is left for the
+/// next read.
+#[derive(Debug)]
+pub struct TailReader {
+    path: PathBuf,
+    max_bytes: u64,
+    /// Byte offset just past the last complete line read.
+    pos: u64,
+    /// Inode of the file `pos` belongs to, so a rotated log is read afresh.
+    inode: Option<u64>,
+    /// The last reset began mid-file, so earlier lines were never read.
+    truncated: bool,
+}
+
+impl TailReader {
+    pub fn new(path: PathBuf, max_bytes: u64) -> Self {
+        Self {
+            path,
+            max_bytes,
+            pos: 0,
+            inode: None,
+            truncated: false,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Whether lines before the last `Reset` were skipped, either because the file
+    /// was longer than `max_bytes` or because a burst outgrew it.
+    pub fn truncated(&self) -> bool {
+        self.truncated
+    }
+
+    pub fn read(&mut self) -> TailRead {
+        let opened = File::open(&self.path).and_then(|f| f.metadata().map(|m| (f, m)));
+        let Ok((mut file, meta)) = opened else {
+            self.inode = None;
+            self.pos = 0;
+            self.truncated = false;
+            return TailRead::Reset(vec![]);
+        };
+        let len = meta.len();
+        let continues = self.inode == Some(meta.ino())
+            && len >= self.pos
+            && len - self.pos <= self.max_bytes;
+        let start = if continues {
+            self.pos
+        } else {
+            len.saturating_sub(self.max_bytes)
+        };
+        let buf = if start == len {
+            Vec::new()
+        } else {
+            read_from(&mut file, start).unwrap_or_default()
+        };
+
+        // Only whole lines; after a mid-file start the first one is partial too.
+        let first = if continues || start == 0 {
+            0
+        } else {
+            buf.iter().position(|b| *b == b'\n').map_or(buf.len(), |i| i + 1)
+        };
+        let end = buf.iter().rposition(|b| *b == b'\n').map_or(first, |i| (i + 1).max(first));
+        let lines = String::from_utf8_lossy(&buf[first..end])
+            .lines()
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        self.inode = Some(meta.ino());
+        self.pos = start + end as u64;
+        if continues {
+            TailRead::Appended(lines)
+        } else {
+            self.truncated = start > 0;
+            TailRead::Reset(lines)
+        }
+    }
+}
+
+/// A run of printable text, or the parameters of an SGR (`ESC [ ... m`) sequence.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Segment {
+    Text(String),
+    Sgr(String),
+}
+
+/// Splits a raw line into text and SGR segments. Every other ESC-introduced
+/// sequence (CSI, OSC, two-byte escapes) and C0 control except tab is dropped.
+pub fn segments(raw: &str) -> Segments<'_> {
+    Segments {
+        chars: raw.chars().peekable(),
+        pending: None,
+    }
+}
+
+pub struct Segments<'a> {
+    chars: Peekable<Chars<'a>>,
+    /// An SGR found right after a text run, returned on the next call.
+    pending: Option<Segment>,
+}
+
+impl Iterator for Segments<'_> {
+    type Item = Segment;
+
+    fn next(&mut self) -> Option<Segment> {
+        if let Some(segment) = self.pending.take() {
+            return Some(segment);
+        }
+        let mut text = String::new();
+        while let Some(c) = self.chars.next() {
+            if c != '\u{1b}' {
+                if !c.is_control() || c == '\t' {
+                    text.push(c);
+                }
+                continue;
+            }
+            match self.chars.next() {
+                Some('[') => {
+                    let mut params = String::new();
+                    let mut final_byte = None;
+                    for c in self.chars.by_ref() {
+                        if ('\u{40}'..='\u{7e}').contains(&c) {
+                            final_byte = Some(c);
+                            break;
+                        }
+                        params.push(c);
+                    }
+                    if final_byte == Some('m') {
+                        let sgr = Segment::Sgr(params);
+                        if text.is_empty() {
+                            return Some(sgr);
+                        }
+                        self.pending = Some(sgr);
+                        return Some(Segment::Text(text));
+                    }
+                }
+                Some(']') => {
+                    while let Some(c) = self.chars.next() {
+                        if c == '\u{7}' {
+                            break;
+                        }
+                        if c == '\u{1b}' && self.chars.peek() == Some(&'\\') {
+                            self.chars.next();
+                            break;
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        (!text.is_empty()).then_some(Segment::Text(text))
+    }
+}
+
+/// `raw` without escape sequences or controls other than tab.
+pub fn strip_escapes(raw: &str) -> String {
+    segments(raw)
+        .filter_map(|segment| match segment {
+            Segment::Text(text) => Some(text),
+            Segment::Sgr(_) => None,
+        })
+        .collect()
+}
+
+fn bracket_tags(text: &str) -> impl Iterator<Item = &str> {
+    text.split('[')
+        .skip(1)
+        .filter_map(|part| part.split_once(']').map(|(tag, _)| tag.trim()))
+}
+
+fn is_category(tag: &str) -> bool {
+    !tag.is_empty()
+        && tag.len() <= 24
+        && tag.starts_with(|c: char| c.is_ascii_uppercase())
+        && tag
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_' || c == '-')
+}
+
+/// The `26-01-15 15:29:06.185: ` prefix pm2 adds (`log_date_format` in `pm2.js`).
+fn pm2_timestamp(text: &str) -> Option<String> {
+    let prefix = text.get(..21)?;
+    let bytes = prefix.as_bytes();
+    let shape = bytes[2] == b'-' && bytes[5] == b'-' && bytes[8] == b' ' && bytes[11] == b':';
+    let digits = [0, 1, 3, 4, 6, 7, 9, 10, 12, 13, 15, 16, 18, 19]
+        .iter()
+        .all(|i| bytes[*i].is_ascii_digit());
+    (shape && digits).then(|| format!("20{}", &prefix[..21]))
+}
+
+/// The `[2026-01-15T15:29:06.185Z]` stamp written by the bots.
+fn iso_timestamp(tag: &str) -> Option<String> {
+    let bytes = tag.as_bytes();
+    if bytes.len() < 19 || bytes[4] != b'-' || bytes[10] != b'T' {
+        return None;
+    }
+    let date = tag.get(..10)?;
+    let time = tag.get(11..)?.trim_end_matches('Z');
+    let time = match time.len() {
+        8 => format!("{time}.000"),
+        _ => time.get(..12).unwrap_or(time).to_string(),
+    };
+    Some(format!("{date} {time}"))
+}
+
+/// Milliseconds since the Unix epoch for a normalized `YYYY-MM-DD HH:MM:SS.mmm`.
+pub fn epoch_ms(ts: &str) -> Option<i64> {
+    let num = |range: std::ops::Range<usize>| ts.get(range)?.parse::<i64>().ok();
+    let (y, m, d) = (num(0..4)?, num(5..7)?, num(8..10)?);
+    let (hh, mm, ss) = (num(11..13)?, num(14..16)?, num(17..19)?);
+    let millis = num(20..23).unwrap_or(0);
+    // Days from civil date (Howard Hinnant's algorithm).
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * ((m + 9) % 12) + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+    Some(((days * 24 + hh) * 60 + mm) * 60_000 + ss * 1000 + millis)
+}
+
+pub fn format_epoch_ms(ms: i64) -> String {
+    let days = ms.div_euclid(86_400_000);
+    let rem = ms.rem_euclid(86_400_000);
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + i64::from(m <= 2);
+    format!(
+        "{y:04}-{m:02}-{d:02} {:02}:{:02}:{:02}.{:03}",
+        rem / 3_600_000,
+        rem / 60_000 % 60,
+        rem / 1000 % 60,
+        rem % 1000
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    use crate::testutil::ScratchDir;
+
+    fn scratch_file(tag: &str, content: &str) -> (ScratchDir, PathBuf) {
+        let dir = ScratchDir::new(tag);
+        let path = dir.join("bot.log");
+        fs::write(&path, content).unwrap();
+        (dir, path)
+    }
+
+    fn append(path: &Path, content: &str) {
+        use std::io::Write;
+        let mut file = fs::OpenOptions::new().append(true).open(path).unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+    }
+
+    #[test]
+    fn parse_lines_reads_stamp_level_and_categories() {
+        let lines = parse_lines([
+            "[2026-01-15T15:29:06.185Z] [WARN] [FILL] [FEES] sold 10 [note]",
+            "    at Object.<anonymous> (bot.js:1:1)",
+            "[2026-01-15T15:29:07Z] [INFO] [UPDATE] [ERROR] grid saved",
+            "\u{1b}[31mplain\u{1b}[0m",
+        ]);
+        assert_eq!(lines[0].timestamp.as_deref(), Some("2026-01-15 15:29:06.185"));
+        assert_eq!(lines[0].level, Some(Level::Warn));
+        assert_eq!(lines[0].categories, ["FILL", "FEES"]);
+        // The continuation line inherits the event above it.
+        assert_eq!(lines[1].level, Some(Level::Warn));
+        assert_eq!(lines[1].timestamp, lines[0].timestamp);
+        assert!(lines[1].categories.is_empty());
+        // Only the first level tag counts; a seconds-only stamp gains millis.
+        assert_eq!(lines[2].level, Some(Level::Info));
+        assert_eq!(lines[2].timestamp.as_deref(), Some("2026-01-15 15:29:07.000"));
+        assert_eq!(lines[3].text, "plain");
+    }
+
+    #[test]
+    fn parse_lines_shifts_pm2_stamps_to_utc() {
+        let lines = parse_lines([
+            "26-01-15 16:29:06.185: [2026-01-15T15:29:06.185Z] [INFO] started",
+            "26-01-15 16:30:00.000: raw console output",
+        ]);
+        assert_eq!(lines[1].timestamp.as_deref(), Some("2026-01-15 15:30:00.000"));
+    }
+
+    #[test]
+    fn epoch_ms_round_trips_through_format() {
+        assert_eq!(epoch_ms("1970-01-01 00:00:00.000"), Some(0));
+        assert_eq!(epoch_ms("2026-01-15 15:29:06.185"), Some(1_768_490_946_185));
+        // Leap day and the day after, millis optional.
+        assert_eq!(epoch_ms("2024-02-29 23:59:59"), Some(1_709_251_199_000));
+        assert_eq!(format_epoch_ms(1_709_251_200_000), "2024-03-01 00:00:00.000");
+        assert_eq!(format_epoch_ms(-1), "1969-12-31 23:59:59.999");
+        for ts in ["2000-02-29 12:34:56.789", "2026-12-31 23:59:59.999", "2100-03-01 00:00:00.001"] {
+            assert_eq!(format_epoch_ms(epoch_ms(ts).unwrap()), ts);
+        }
+        assert_eq!(epoch_ms("2026-01-15"), None);
+        assert_eq!(epoch_ms("2026-xx-15 15:29:06"), None);
+    }
+
+    #[test]
+    fn pm2_offset_handles_zones_behind_utc_and_midnight() {
+        // pm2 runs at UTC-5: 22:00 local on the 14th is 03:00 UTC on the 15th.
+        let lines = parse_lines([
+            "26-01-14 22:00:00.000: [2026-01-15T03:00:00.000Z] [INFO] tick",
+            "26-01-14 23:30:00.250: restarting",
+        ]);
+        assert_eq!(lines[1].timestamp.as_deref(), Some("2026-01-15 04:30:00.250"));
+        // Without a line carrying both stamps pm2 time is taken as it is.
+        let lines = parse_lines(["26-01-14 23:30:00.250: restarting"]);
+        assert_eq!(lines[0].timestamp.as_deref(), Some("2026-01-14 23:30:00.250"));
+    }
+
+    #[test]
+    fn line_parser_carries_context_across_chunks() {
+        let mut parser = LineParser::default();
+        parser.parse(["26-01-15 16:29:06.185: [2026-01-15T15:29:06.185Z] [ERROR] boom"]);
+        let next = parser.parse(["    at stack", "26-01-15 16:31:00.000: plain"]);
+        assert_eq!(next[0].level, Some(Level::Error));
+        assert_eq!(next[1].timestamp.as_deref(), Some("2026-01-15 15:31:00.000"));
+    }
+
+    #[test]
+    fn bracket_tags_take_each_closed_bracket() {
+        let tags = bracket_tags("a [ ONE ] b [two][THREE] [open").collect::<Vec<_>>();
+        assert_eq!(tags, ["ONE", "two", "THREE"]);
+    }
+
+    #[test]
+    fn is_category_wants_short_uppercase_tags() {
+        for tag in ["FILL", "FEES", "GRID_RESYNC", "PHASE-2", "A1"] {
+            assert!(is_category(tag), "{tag}");
+        }
+        for tag in ["", "fill", "Fill", "1FILL", "TWO WORDS", "ABCDEFGHIJKLMNOPQRSTUVWXY"] {
+            assert!(!is_category(tag), "{tag}");
+        }
+    }
+
+    #[test]
+    fn read_tail_drops_the_partial_first_line_only_mid_file() {
+        let (_dir, path) = scratch_file("tail", "first\nsecond\nthird");
+        assert_eq!(read_tail(&path, 100), ["first", "second", "third"]);
+        // Starts inside "second": that fragment goes.
+        assert_eq!(read_tail(&path, 9), ["third"]);
+        // Starts exactly on "second": the whole line goes too, as it cannot be told apart.
+        assert_eq!(read_tail(&path, 12), ["third"]);
+        assert!(read_tail(&path.with_extension("missing"), 10).is_empty());
+    }
+
+    #[test]
+    fn tail_reader_follows_appends_and_rereads_after_truncation() {
+        let (_dir, path) = scratch_file("reader", "one\ntwo\nthr");
+        let mut reader = TailReader::new(path.clone(), 1024);
+        assert_eq!(reader.read(), TailRead::Reset(vec!["one".into(), "two".into()]));
+        assert_eq!(reader.read(), TailRead::Appended(vec![]));
+        append(&path, "ee\nfour\n");
+        assert_eq!(reader.read(), TailRead::Appended(vec!["three".into(), "four".into()]));
+
+        fs::write(&path, "new\n").unwrap();
+        assert_eq!(reader.read(), TailRead::Reset(vec!["new".into()]));
+    }
+
+    #[test]
+    fn tail_reader_rereads_a_rotated_file() {
+        let (_dir, path) = scratch_file("rotate", "old line\n");
+        let mut reader = TailReader::new(path.clone(), 1024);
+        reader.read();
+        let rotated = path.with_extension("log.1");
+        fs::rename(&path, &rotated).unwrap();
+        fs::write(&path, "fresh line that is longer\n").unwrap();
+        assert_eq!(reader.read(), TailRead::Reset(vec!["fresh line that is longer".into()]));
+    }
+
+    #[test]
+    fn tail_reader_skips_a_burst_larger_than_the_tail() {
+        let (_dir, path) = scratch_file("burst", "a\n");
+        let mut reader = TailReader::new(path.clone(), 8);
+        reader.read();
+        assert!(!reader.truncated());
+        append(&path, "bbbbbbbbbb\ncc\n");
+        assert_eq!(reader.read(), TailRead::Reset(vec!["cc".into()]));
+        assert!(reader.truncated());
+    }
+}
@@ -0,0 +1,249 @@
+use std::path::PathBuf;
+
+use crate::{
+    logline::{Level, LineParser, LogLine, TailRead, TailReader},
+    scroll::LogScroll,
+};
+
+/// How much of a log file the viewer keeps in memory.
+const MAX_TAIL_BYTES: u64 = 4 * 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogInput {
+    Search(String),
+    JumpTo(String),
+}
+
+/// Full-screen scrollback for one bot log. `view.visible` indexes `lines`.
+#[derive(Debug)]
+pub struct LogViewer {
+    pub title: String,
+    pub path: PathBuf,
+    pub lines: Vec<LogLine>,
+    pub view: LogScroll,
+    pub search: Option<String>,
+    /// Index into `visible` of the match `n`/`N` step from.
+    pub current_match: Option<usize>,
+    pub input: Option<LogInput>,
+    pub status: String,
+    reader: TailReader,
+    parser: LineParser,
+    /// Bytes held in `lines`, kept near `MAX_TAIL_BYTES` as the file grows.
+    bytes: u64,
+}
+
+impl LogViewer {
+    pub fn open(title: String, path: PathBuf) -> Self {
+        let mut viewer = Self {
+            title,
+            reader: TailReader::new(path.clone(), MAX_TAIL_BYTES),
+            parser: LineParser::default(),
+            bytes: 0,
+            path,
+            lines: Vec::new(),
+            view: LogScroll::default(),
+            search: None,
+            current_match: None,
+            input: None,
+            status: String::new(),
+        };
+        viewer.reload();
+        viewer
+    }
+
+    /// Picks up what was appended since the last call; a truncated or rotated
+    /// file is read afresh.
+    pub fn reload(&mut self) {
+        let appended = match self.reader.read() {
+            TailRead::Reset(raw) => {
+                self.parser = LineParser::default();
+                self.lines.clear();
+                self.view.visible.clear();
+                self.view.categories.clear();
+                self.current_match = None;
+                self.bytes = 0;
+                raw
+            }
+            TailRead::Appended(raw) if raw.is_empty() => return,
+            TailRead::Appended(raw) => raw,
+        };
+        let first_new = self.lines.len();
+        self.bytes += appended.iter().map(|l| l.len() as u64 + 1).sum::<u64>();
+        self.lines
+            .extend(self.parser.parse(appended.iter().map(String::as_str)));
+        self.view
+            .add_categories(self.lines[first_new..].iter().flat_map(|l| &l.categories));
+
+        // Drop the oldest lines once the tail has doubled, not on every append.
+        if self.bytes > 2 * MAX_TAIL_BYTES {
+            let mut dropped = 0;
+            while self.bytes > MAX_TAIL_BYTES && dropped < self.lines.len() {
+                self.bytes -= self.lines[dropped].raw.len() as u64 + 1;
+                dropped += 1;
+            }
+            let anchor = self.view.anchor().map(|a| a.saturating_sub(dropped));
+            self.lines.drain(..dropped);
+            self.refilter(anchor);
+            return;
+        }
+
+        let new = (first_new..self.lines.len())
+            .filter(|i| self.view.passes(&self.lines[*i]))
+            .collect::<Vec<_>>();
+        self.view.extend_visible(new);
+    }
+
+    fn apply_filters(&mut self) {
+        self.refilter(self.view.anchor());
+    }
+
+    /// Rebuilds the visible lines, keeping `anchor` (an index into `lines`) on top
+    /// of a paused view.
+    fn refilter(&mut self, anchor: Option<usize>) {
+        self.current_match = None;
+        let visible = (0..self.lines.len())
+            .filter(|i| self.view.passes(&self.lines[*i]))
+            .collect();
+        self.view.set_visible(visible, anchor);
+    }
+
+    pub fn toggle_level(&mut self, level: Level) {
+        self.view.toggle_level(level);
+        self.apply_filters();
+    }
+
+    /// Steps through the categories seen in the file, then back to "all".
+    pub fn cycle_category(&mut self) {
+        self.view.cycle_category();
+        self.apply_filters();
+    }
+
+    pub fn is_match(&self, line: &LogLine) -> bool {
+        self.search
+            .as_ref()
+            .is_some_and(|q| !q.is_empty() && line.text.to_lowercase().contains(&q.to_lowercase()))
+    }
+
+    /// Moves the first match at or after (forward) / before (backward) `from` to the
+    /// top of the page.
+    fn find_from(&mut self, from: usize, forward: bool) -> bool {
+        let visible = &self.view.visible;
+        let hit = if forward {
+            (from..visible.len()).find(|i| self.is_match(&self.lines[visible[*i]]))
+        } else {
+            (0..from.min(visible.len()))
+                .rev()
+                .find(|i| self.is_match(&self.lines[visible[*i]]))
+        };
+        match hit {
+            Some(i) => {
+                self.view.pause_at(i);
+                self.current_match = Some(i);
+                let total = self
+                    .view
+                    .visible
+                    .iter()
+                    .filter(|v| self.is_match(&self.lines[**v]))
+                    .count();
+                self.status = format!("match at line {} ({total} total)", i + 1);
+                true
+            }
+            None => {
+                self.status = String::from("no match");
+                false
+            }
+        }
+    }
+
+    pub fn next_match(&mut self) {
+        let from = self.current_match.map(|m| m + 1).unwrap_or(self.view.offset);
+        self.find_from(from, true);
+    }
+
+    pub fn prev_match(&mut self) {
+        let from = self.current_match.unwrap_or(self.view.offset);
+        self.find_from(from, false);
+    }
+
+    pub fn begin_input(&mut self, input: LogInput) {
+        if let LogInput::Search(_) = input {
+            self.current_match = None;
+        }
+        self.input = Some(input);
+    }
+
+    /// Applies typed search text immediately so matches track each keystroke.
+    pub fn input_push(&mut self, c: char) {
+        match &mut self.input {
+            Some(LogInput::Search(q)) => {
+                q.push(c);
+                self.search = Some(q.clone());
+                let from = self.current_match.unwrap_or(self.view.offset);
+                self.find_from(from, true);
+            }
+            Some(LogInput::JumpTo(t)) => t.push(c),
+            None => {}
+        }
+    }
+
+    pub fn input_pop(&mut self) {
+        match &mut self.input {
+            Some(LogInput::Search(q)) => {
+                q.pop();
+                self.search = Some(q.clone()).filter(|q| !q.is_empty());
+            }
+            Some(LogInput::JumpTo(t)) => {
+                t.pop();
+            }
+            None => {}
+        }
+    }
+
+    pub fn commit_input(&mut self) {
+        match self.input.take() {
+            Some(LogInput::Search(q)) => {
+                self.search = Some(q).filter(|q| !q.is_empty());
+            }
+            Some(LogInput::JumpTo(t)) => self.jump_to(&t),
+            None => {}
+        }
+    }
+
+    pub fn cancel_input(&mut self) {
+        if let Some(LogInput::Search(_)) = self.input.take() {
+            self.search = None;
+        }
+    }
+
+    /// Jumps to the first visible line at or after `target`. Accepts a full
+    /// `YYYY-MM-DD HH:MM[:SS]` (a `T` separator works too) or just `HH:MM[:SS]`,
+    /// which is taken on the date of the newest line.
+    pub fn jump_to(&mut self, target: &str) {
+        let target = target.trim().replace('T', " ");
+        let target = if target.contains('-') {
+            target
+        } else {
+            let date = self
+                .lines
+                .iter()
+                .rev()
+                .find_map(|l| l.timestamp.as_ref())
+                .map(|ts| ts[..10].to_string())
+                .unwrap_or_default();
+            format!("{date} {target}")
+        };
+        let hit = self.view.visible.iter().position(|i| {
+            self.lines[*i]
+                .timestamp
+                .as_ref()
+                .is_some_and(|ts| ts.as_str() >= target.as_str())
+        });
+        match hit {
+            Some(i) => {
+                self.view.pause_at(i);
+                self.status = format!("jumped to {target}");
+            }
+            None => self.status = format!("no line at or after {target}"),
+        }
+    }
+}
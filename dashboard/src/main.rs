@@ -1,6 +1,37 @@
+mod accounts;
 mod actions;
+mod analysis;
+mod ansi;
 mod app;
+mod backups;
+mod chart;
+mod consistency;
+mod cred_probe;
+mod cycles;
+mod divergence;
+mod dryrun;
+mod exposure;
+mod fees;
+mod fills;
+mod grid;
+mod identity;
+mod infra;
+mod logline;
+mod logview;
+mod market;
+mod partials;
+mod preview;
+mod reset;
+mod restart;
+mod runtime;
+mod scroll;
+mod settings;
+mod spread;
 mod state;
+#[cfg(test)]
+mod testutil;
+mod timeline;
+mod trend;
 mod ui;
 
 use std::{io, time::Duration};
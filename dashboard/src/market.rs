@@ -0,0 +1,326 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::SystemTime,
+};
+
+use serde::Deserialize;
+
+use crate::grid::GridConfig;
+
+const DATA_DIR: &str = "market_adapter/data";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AssetRef {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub symbol: String,
+}
+
+impl AssetRef {
+    /// The adapter writes ids into `symbol` when it has no symbol lookup, so match
+    /// either field against either field.
+    pub fn matches(&self, other: &AssetRef) -> bool {
+        let keys = [self.id.as_str(), self.symbol.as_str()];
+        [other.id.as_str(), other.symbol.as_str()]
+            .iter()
+            .any(|k| !k.is_empty() && keys.iter().any(|own| own.eq_ignore_ascii_case(k)))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MarketMeta {
+    asset_a: AssetRef,
+    asset_b: AssetRef,
+    #[serde(default)]
+    interval_seconds: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct MarketFileRaw {
+    meta: MarketMeta,
+    #[serde(default)]
+    candles: Vec<Vec<f64>>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Candle {
+    pub ts_ms: i64,
+    pub close: f64,
+}
+
+/// One `market_adapter/data/*.json` price history (`[ts, open, high, low, close, vol]`),
+/// priced in asset B per asset A.
+#[derive(Debug, Clone)]
+pub struct MarketFile {
+    pub path: PathBuf,
+    pub asset_a: AssetRef,
+    pub asset_b: AssetRef,
+    pub interval_seconds: u64,
+    pub candles: Vec<Candle>,
+}
+
+/// A market file oriented to a bot's pair; `inverted` when the file quotes A per B.
+#[derive(Debug, Clone)]
+pub struct MarketSeries<'a> {
+    pub file: &'a Arc<MarketFile>,
+    pub inverted: bool,
+}
+
+impl MarketSeries<'_> {
+    fn orient(&self, price: f64) -> f64 {
+        if self.inverted {
+            1.0 / price
+        } else {
+            price
+        }
+    }
+
+    /// `(ts_ms, close)` in the bot's price orientation, oldest first.
+    pub fn closes(&self) -> Vec<(i64, f64)> {
+        self.file
+            .candles
+            .iter()
+            .map(|c| (c.ts_ms, self.orient(c.close)))
+            .collect()
+    }
+
+    pub fn latest(&self) -> Option<(i64, f64)> {
+        self.file
+            .candles
+            .last()
+            .map(|c| (c.ts_ms, self.orient(c.close)))
+    }
+
+    pub fn label(&self) -> String {
+        let name = self
+            .file
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if self.inverted {
+            format!("{name} (inverted)")
+        } else {
+            name
+        }
+    }
+}
+
+/// Parsed `market_adapter/data` files kept across polls. The adapter rewrites a
+/// file when it fetches candles, so one is only re-read when its modification time
+/// or length changes; files that fail to parse are remembered as such until then.
+#[derive(Debug, Default)]
+pub struct MarketCache {
+    files: HashMap<PathBuf, (FileStamp, Option<Arc<MarketFile>>)>,
+}
+
+/// Modification time and length of a file, to tell whether it was rewritten.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FileStamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl FileStamp {
+    pub fn of(path: &Path) -> Option<Self> {
+        let meta = fs::metadata(path).ok()?;
+        Some(Self {
+            modified: meta.modified().ok(),
+            len: meta.len(),
+        })
+    }
+}
+
+impl MarketCache {
+    pub fn load(&mut self) -> Vec<Arc<MarketFile>> {
+        self.load_from(Path::new(DATA_DIR))
+    }
+
+    fn load_from(&mut self, dir: &Path) -> Vec<Arc<MarketFile>> {
+        let paths = fs::read_dir(dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|e| e.ok())
+                    .map(|e| e.path())
+                    .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        self.files.retain(|path, _| paths.contains(path));
+
+        let mut files = Vec::new();
+        for path in paths {
+            let Some(stamp) = FileStamp::of(&path) else {
+                continue;
+            };
+            let file = match self.files.get(&path) {
+                Some((cached, file)) if *cached == stamp => file.clone(),
+                _ => {
+                    let file = parse_market_file(&path).map(Arc::new);
+                    self.files.insert(path, (stamp, file.clone()));
+                    file
+                }
+            };
+            files.extend(file);
+        }
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        files
+    }
+}
+
+fn parse_market_file(path: &Path) -> Option<MarketFile> {
+    let raw = fs::read_to_string(path).ok()?;
+    let parsed = serde_json::from_str::<MarketFileRaw>(&raw).ok()?;
+    let candles = parsed
+        .candles
+        .iter()
+        .filter(|c| c.len() >= 5 && c[4] > 0.0)
+        .map(|c| Candle {
+            ts_ms: c[0] as i64,
+            close: c[4],
+        })
+        .collect::<Vec<_>>();
+    Some(MarketFile {
+        path: path.to_path_buf(),
+        asset_a: parsed.meta.asset_a,
+        asset_b: parsed.meta.asset_b,
+        interval_seconds: parsed.meta.interval_seconds,
+        candles,
+    })
+}
+
+/// Picks the freshest file for the pair, preferring the finer interval on ties.
+pub fn find_series<'a>(
+    files: &'a [Arc<MarketFile>],
+    asset_a: &AssetRef,
+    asset_b: &AssetRef,
+) -> Option<MarketSeries<'a>> {
+    files
+        .iter()
+        .filter_map(|file| {
+            if file.asset_a.matches(asset_a) && file.asset_b.matches(asset_b) {
+                Some(MarketSeries {
+                    file,
+                    inverted: false,
+                })
+            } else if file.asset_a.matches(asset_b) && file.asset_b.matches(asset_a) {
+                Some(MarketSeries {
+                    file,
+                    inverted: true,
+                })
+            } else {
+                None
+            }
+        })
+        .filter(|s| !s.file.candles.is_empty())
+        .max_by(|a, b| {
+            let last = |s: &MarketSeries| s.file.candles.last().map(|c| c.ts_ms).unwrap_or(0);
+            last(a)
+                .cmp(&last(b))
+                .then(b.file.interval_seconds.cmp(&a.file.interval_seconds))
+        })
+}
+
+#[derive(Debug, Clone)]
+pub struct MarketGauge {
+    pub price: f64,
+    pub as_of_ms: i64,
+    pub source: String,
+    pub min_price: f64,
+    pub max_price: f64,
+    pub grid_center: f64,
+    /// Log-scale position of `price` between the bounds (0 = min, 1 = max).
+    pub position: f64,
+    /// Percent distance of `price` from the grid centre.
+    pub drift_percent: f64,
+}
+
+impl MarketGauge {
+    pub fn in_range(&self) -> bool {
+        (self.min_price..=self.max_price).contains(&self.price)
+    }
+
+    /// Fixed-width text gauge: `|` marks the grid centre, `*` the market price.
+    pub fn bar(&self, width: usize) -> String {
+        let width = width.max(3);
+        let slot = |pos: f64| ((pos.clamp(0.0, 1.0) * (width - 1) as f64).round()) as usize;
+        let mut cells = vec!['-'; width];
+        let span = (self.max_price / self.min_price).ln();
+        let center = (self.grid_center / self.min_price).ln() / span;
+        cells[slot(center)] = '|';
+        cells[slot(self.position)] = '*';
+        let left = if self.price < self.min_price { "<" } else { "[" };
+        let right = if self.price > self.max_price { ">" } else { "]" };
+        format!("{left}{}{right}", cells.into_iter().collect::<String>())
+    }
+}
+
+/// Places the latest market price inside the bot's configured range. Relative
+/// (`"3x"`) bounds resolve against the grid centre, as the bot resolves them
+/// against its start price.
+pub fn gauge(
+    series: &MarketSeries,
+    config: &GridConfig,
+    grid_center: f64,
+) -> Result<MarketGauge, String> {
+    let (as_of_ms, price) = series
+        .latest()
+        .ok_or_else(|| String::from("no candles"))?;
+    let min_price = config
+        .min_price(grid_center)
+        .ok_or_else(|| String::from("unreadable minPrice"))?;
+    let max_price = config
+        .max_price(grid_center)
+        .ok_or_else(|| String::from("unreadable maxPrice"))?;
+    if !(min_price > 0.0 && min_price < max_price) {
+        return Err(format!("invalid bounds [{min_price}, {max_price}]"));
+    }
+    Ok(MarketGauge {
+        price,
+        as_of_ms,
+        source: series.label(),
+        min_price,
+        max_price,
+        grid_center,
+        position: (price / min_price).ln() / (max_price / min_price).ln(),
+        drift_percent: (price / grid_center - 1.0) * 100.0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::ScratchDir;
+
+    #[test]
+    fn cache_rereads_only_changed_files() {
+        let dir = ScratchDir::new("market");
+        let file = |close: f64| {
+            format!(
+                r#"{{"meta":{{"assetA":{{"symbol":"XRP"}},"assetB":{{"symbol":"BTS"}},"intervalSeconds":3600}},"candles":[[1700000000000,1,1,1,{close},0]]}}"#
+            )
+        };
+        fs::write(dir.join("a.json"), file(1.5)).unwrap();
+        fs::write(dir.join("b.json"), file(2.5)).unwrap();
+        fs::write(dir.join("broken.json"), "{").unwrap();
+
+        let mut cache = MarketCache::default();
+        let first = cache.load_from(&dir);
+        assert_eq!(first.len(), 2);
+        let again = cache.load_from(&dir);
+        assert!(Arc::ptr_eq(&first[0], &again[0]) && Arc::ptr_eq(&first[1], &again[1]));
+
+        // Same mtime granularity is not guaranteed, so change the length too.
+        fs::write(dir.join("b.json"), file(12.5)).unwrap();
+        fs::remove_file(dir.join("a.json")).unwrap();
+        let changed = cache.load_from(&dir);
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].candles[0].close, 12.5);
+        assert_eq!(cache.files.len(), 2);
+    }
+}
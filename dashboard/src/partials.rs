@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+
+use crate::{grid::Side, state::BotStatus};
+
+/// A PARTIAL slot of a persisted grid.
+#[derive(Clone, Debug)]
+pub struct PartialSlot {
+    pub id: String,
+    pub side: Option<Side>,
+    pub price: f64,
+    pub remaining: f64,
+    /// Size the slot had while ACTIVE, when the dashboard saw it; otherwise the
+    /// ideal size the bot would place there.
+    pub original: Option<f64>,
+    pub original_seen: bool,
+    /// Ideal size of the matching slot in the rebuilt grid (see `divergence`).
+    pub ideal: Option<f64>,
+    /// First poll that found the slot PARTIAL (epoch ms).
+    pub partial_since_ms: i64,
+    /// False when the slot was already PARTIAL at the first poll, so the real
+    /// duration is longer than shown.
+    pub transition_seen: bool,
+    pub dust: bool,
+}
+
+impl PartialSlot {
+    pub fn fill_percent(&self) -> Option<f64> {
+        let original = self.original.filter(|o| *o > 0.0)?;
+        Some(((1.0 - self.remaining / original) * 100.0).clamp(0.0, 100.0))
+    }
+}
+
+/// Cross-poll slot states, so a slot's partial age and its size before the first
+/// fill survive the orders file being rewritten.
+#[derive(Debug, Default)]
+pub struct PartialTracker {
+    bots: HashMap<String, TrackedBot>,
+}
+
+#[derive(Debug, Default)]
+struct TrackedBot {
+    polled: bool,
+    slots: HashMap<String, TrackedSlot>,
+    partials: Vec<PartialSlot>,
+}
+
+#[derive(Debug, Default)]
+struct TrackedSlot {
+    active_size: Option<f64>,
+    partial_since: Option<(i64, bool)>,
+}
+
+impl PartialTracker {
+    /// Records one poll. Dust mirrors `Grid._hasAnyDust`: remaining size below
+    /// `dust_threshold_percent` of the slot's ideal size.
+    pub fn observe(&mut self, bots: &[BotStatus], dust_threshold_percent: f64, now_ms: i64) {
+        self.bots.retain(|name, _| bots.iter().any(|b| b.name == *name));
+        for bot in bots {
+            let tracked = self.bots.entry(bot.name.clone()).or_default();
+            let ideal_sizes = bot
+                .divergence
+                .as_ref()
+                .map(|d| {
+                    d.slots
+                        .iter()
+                        .filter(|s| s.ideal_side.is_some() && s.ideal_size > 0.0)
+                        .map(|s| (s.id.clone(), s.ideal_size))
+                        .collect::<HashMap<_, _>>()
+                })
+                .unwrap_or_default();
+
+            let mut partials = Vec::new();
+            tracked
+                .slots
+                .retain(|id, _| bot.grid.iter().any(|s| s.label() == *id));
+            for slot in &bot.grid {
+                let id = slot.label();
+                let first_poll = !tracked.polled;
+                let state = tracked.slots.entry(id.clone()).or_default();
+                match slot.state.as_deref() {
+                    Some("active") => {
+                        state.active_size = Some(slot.size);
+                        state.partial_since = None;
+                    }
+                    Some("partial") => {
+                        let (since_ms, transition_seen) =
+                            *state.partial_since.get_or_insert((now_ms, !first_poll));
+                        let ideal = ideal_sizes.get(&id).copied();
+                        partials.push(PartialSlot {
+                            id,
+                            side: slot.side(),
+                            price: slot.price,
+                            remaining: slot.size,
+                            original: state.active_size.or(ideal),
+                            original_seen: state.active_size.is_some(),
+                            ideal,
+                            partial_since_ms: since_ms,
+                            transition_seen,
+                            dust: ideal
+                                .is_some_and(|i| slot.size < i * dust_threshold_percent / 100.0),
+                        });
+                    }
+                    _ => *state = TrackedSlot::default(),
+                }
+            }
+            tracked.polled = true;
+            partials.sort_by_key(|p| p.partial_since_ms);
+            tracked.partials = partials;
+        }
+    }
+
+    /// PARTIAL slots of a bot, longest partial first.
+    pub fn partials(&self, name: &str) -> &[PartialSlot] {
+        self.bots
+            .get(name)
+            .map(|t| t.partials.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// `(partial, dust)` slot counts.
+    pub fn counts(&self, name: &str) -> (usize, usize) {
+        let partials = self.partials(name);
+        (partials.len(), partials.iter().filter(|p| p.dust).count())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    use crate::{
+        divergence::{GridDivergence, SlotDivergence},
+        grid::IdealGrid,
+    };
+
+    /// A bot whose slots `s1`..`s3` are all ideally 10 on the buy side.
+    fn bot(slots: [(&str, f64); 3]) -> BotStatus {
+        let mut bot = BotStatus::stub("bot", "bot-0");
+        bot.grid = serde_json::from_value(json!(slots
+            .iter()
+            .enumerate()
+            .map(|(i, (state, size))| json!({
+                "id": format!("s{}", i + 1),
+                "type": "buy",
+                "state": state,
+                "price": 1.0 - i as f64 / 100.0,
+                "size": size,
+            }))
+            .collect::<Vec<_>>()))
+        .unwrap();
+        let slots = bot
+            .grid
+            .iter()
+            .map(|s| SlotDivergence {
+                id: s.label(),
+                side: s.side(),
+                state: s.state.clone().unwrap_or_default(),
+                price: s.price,
+                size: s.size,
+                ideal_side: Some(Side::Buy),
+                ideal_active: true,
+                ideal_size: 10.0,
+                price_error_percent: 0.0,
+                size_error: 0.0,
+                counted: true,
+            })
+            .collect();
+        bot.divergence = Ok(GridDivergence {
+            slots,
+            ideal: IdealGrid {
+                slots: Vec::new(),
+                reference_price: 1.0,
+                min_price: 0.5,
+                max_price: 2.0,
+                gap_slots: 0,
+            },
+            rms_percent: 0.0,
+            promille: 0.0,
+            worst_slot: None,
+            unmatched_persisted: 0,
+            unmatched_ideal: 0,
+            role_mismatches: 0,
+            state_mismatches: 0,
+            partial_excluded: 0,
+        });
+        bot
+    }
+
+    /// `(id, partial since, transition seen, original, original seen)`, oldest first.
+    fn summary(tracker: &PartialTracker) -> Vec<(&str, i64, bool, Option<f64>, bool)> {
+        tracker
+            .partials("bot")
+            .iter()
+            .map(|p| (p.id.as_str(), p.partial_since_ms, p.transition_seen, p.original, p.original_seen))
+            .collect()
+    }
+
+    fn dust(tracker: &PartialTracker) -> Vec<&str> {
+        tracker
+            .partials("bot")
+            .iter()
+            .filter(|p| p.dust)
+            .map(|p| p.id.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn observe_tracks_partial_age_original_size_and_dust() {
+        let mut tracker = PartialTracker::default();
+        // First poll: s2 and s3 were already PARTIAL, so their transition is unseen
+        // and their original size falls back to the ideal one. 0.3 is dust at 5%.
+        tracker.observe(&[bot([("active", 8.0), ("partial", 4.0), ("partial", 0.3)])], 5.0, 0);
+        assert_eq!(
+            summary(&tracker),
+            [
+                ("s2", 0, false, Some(10.0), false),
+                ("s3", 0, false, Some(10.0), false),
+            ]
+        );
+        assert_eq!(dust(&tracker), ["s3"]);
+
+        // s1 fills partly: its ACTIVE size becomes the original.
+        tracker.observe(&[bot([("partial", 6.0), ("partial", 4.0), ("virtual", 10.0)])], 5.0, 1000);
+        assert_eq!(
+            summary(&tracker),
+            [
+                ("s2", 0, false, Some(10.0), false),
+                ("s1", 1000, true, Some(8.0), true),
+            ]
+        );
+        assert_eq!(tracker.partials("bot")[1].fill_percent(), Some(25.0));
+        assert_eq!(tracker.counts("bot"), (2, 0));
+
+        // Going VIRTUAL forgets both the ACTIVE size and the partial age.
+        tracker.observe(&[bot([("virtual", 10.0), ("partial", 4.0), ("virtual", 10.0)])], 5.0, 2000);
+        tracker.observe(&[bot([("partial", 5.0), ("partial", 0.4), ("virtual", 10.0)])], 5.0, 3000);
+        assert_eq!(
+            summary(&tracker),
+            [
+                ("s2", 0, false, Some(10.0), false),
+                ("s1", 3000, true, Some(10.0), false),
+            ]
+        );
+        assert_eq!(dust(&tracker), ["s2"]);
+        assert_eq!(tracker.counts("bot"), (2, 1));
+
+        tracker.observe(&[], 5.0, 4000);
+        assert!(tracker.partials("bot").is_empty());
+    }
+}
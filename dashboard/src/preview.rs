@@ -0,0 +1,254 @@
+use serde_json::Value;
+
+use crate::grid::{self, FundSpec, GridConfig, IdealGrid, IdealSlot, Side, SideFunds, SidePair};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PreviewField {
+    StartPrice,
+    MinPrice,
+    MaxPrice,
+    IncrementPercent,
+    TargetSpreadPercent,
+    ActiveBuy,
+    ActiveSell,
+    WeightBuy,
+    WeightSell,
+    FundsBuy,
+    FundsSell,
+}
+
+impl PreviewField {
+    pub const ALL: [PreviewField; 11] = [
+        PreviewField::StartPrice,
+        PreviewField::MinPrice,
+        PreviewField::MaxPrice,
+        PreviewField::IncrementPercent,
+        PreviewField::TargetSpreadPercent,
+        PreviewField::ActiveBuy,
+        PreviewField::ActiveSell,
+        PreviewField::WeightBuy,
+        PreviewField::WeightSell,
+        PreviewField::FundsBuy,
+        PreviewField::FundsSell,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PreviewField::StartPrice => "startPrice",
+            PreviewField::MinPrice => "minPrice",
+            PreviewField::MaxPrice => "maxPrice",
+            PreviewField::IncrementPercent => "incrementPercent",
+            PreviewField::TargetSpreadPercent => "targetSpreadPercent",
+            PreviewField::ActiveBuy => "activeOrders.buy",
+            PreviewField::ActiveSell => "activeOrders.sell",
+            PreviewField::WeightBuy => "weightDistribution.buy",
+            PreviewField::WeightSell => "weightDistribution.sell",
+            PreviewField::FundsBuy => "botFunds.buy",
+            PreviewField::FundsSell => "botFunds.sell",
+        }
+    }
+}
+
+/// Editable grid parameters for the Preview tab. Values are kept as the text the
+/// operator typed so that `"3x"` bounds and `"50%"` funds behave as in `bots.json`.
+#[derive(Debug)]
+pub struct PreviewState {
+    pub values: Vec<String>,
+    pub selected: usize,
+    pub editing: Option<String>,
+    /// Ladder rows from the centred window; kept inside the ladder by `clamp_scroll`.
+    pub scroll: i32,
+    /// Ladder rows on screen; set by the renderer.
+    pub page_height: usize,
+    pub source: String,
+}
+
+impl Default for PreviewState {
+    fn default() -> Self {
+        let defaults = GridConfig::default();
+        let mut state = Self {
+            values: vec![String::new(); PreviewField::ALL.len()],
+            selected: 0,
+            editing: None,
+            scroll: 0,
+            page_height: 20,
+            source: String::from("defaults"),
+        };
+        state.fill_from(&defaults, None);
+        state
+    }
+}
+
+impl PreviewState {
+    pub fn load_from_bot(&mut self, name: &str, config: &GridConfig, fallback_start: Option<f64>) {
+        self.fill_from(config, fallback_start);
+        self.source = format!("bots.json entry '{name}'");
+        self.scroll = 0;
+    }
+
+    fn fill_from(&mut self, config: &GridConfig, fallback_start: Option<f64>) {
+        let start = config
+            .start_price()
+            .or(fallback_start)
+            .map(|p| p.to_string())
+            .unwrap_or_default();
+        for (i, field) in PreviewField::ALL.iter().enumerate() {
+            self.values[i] = match field {
+                PreviewField::StartPrice => start.clone(),
+                PreviewField::MinPrice => text(&config.min_price, "3x"),
+                PreviewField::MaxPrice => text(&config.max_price, "3x"),
+                PreviewField::IncrementPercent => config.increment_percent().to_string(),
+                PreviewField::TargetSpreadPercent => config.target_spread_percent().to_string(),
+                PreviewField::ActiveBuy => config.active_orders(Side::Buy).to_string(),
+                PreviewField::ActiveSell => config.active_orders(Side::Sell).to_string(),
+                PreviewField::WeightBuy => config.weight(Side::Buy).to_string(),
+                PreviewField::WeightSell => config.weight(Side::Sell).to_string(),
+                PreviewField::FundsBuy => fund_text(config.bot_funds(Side::Buy)),
+                PreviewField::FundsSell => fund_text(config.bot_funds(Side::Sell)),
+            };
+        }
+    }
+
+    pub fn value(&self, field: PreviewField) -> &str {
+        let index = PreviewField::ALL
+            .iter()
+            .position(|f| *f == field)
+            .unwrap_or(0);
+        &self.values[index]
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1) % PreviewField::ALL.len();
+    }
+
+    pub fn select_prev(&mut self) {
+        let len = PreviewField::ALL.len();
+        self.selected = (self.selected + len - 1) % len;
+    }
+
+    pub fn begin_edit(&mut self) {
+        self.editing = Some(self.values[self.selected].clone());
+    }
+
+    pub fn commit_edit(&mut self) {
+        if let Some(typed) = self.editing.take() {
+            self.values[self.selected] = typed.trim().to_string();
+            self.source = String::from("edited");
+            self.clamp_scroll();
+        }
+    }
+
+    pub fn scroll_by(&mut self, rows: i32) {
+        self.scroll += rows;
+        self.clamp_scroll();
+    }
+
+    pub fn set_page_height(&mut self, rows: usize) {
+        if rows != self.page_height {
+            self.page_height = rows;
+            self.clamp_scroll();
+        }
+    }
+
+    /// First ladder row shown for `slots` (highest price first), keeping the
+    /// spread centred until `scroll` moves the window.
+    pub fn first_row(&self, slots: &[&IdealSlot]) -> usize {
+        let (base, max_start) = self.window(slots);
+        (base + self.scroll).clamp(0, max_start) as usize
+    }
+
+    /// `(centred start, last start)` of the ladder window.
+    fn window(&self, slots: &[&IdealSlot]) -> (i32, i32) {
+        let visible = self.page_height as i32;
+        let center = slots
+            .iter()
+            .position(|s| s.side == Side::Spread)
+            .unwrap_or(slots.len() / 2) as i32;
+        (center - visible / 2, (slots.len() as i32 - visible).max(0))
+    }
+
+    /// Keeps the window inside the ladder, so paging back from either end moves
+    /// the view on the first key press.
+    fn clamp_scroll(&mut self) {
+        let Ok(grid) = self.compute() else {
+            self.scroll = 0;
+            return;
+        };
+        let slots = ladder(&grid);
+        let (base, max_start) = self.window(&slots);
+        self.scroll = self.scroll.clamp(-base, max_start - base);
+    }
+
+    pub fn to_config(&self) -> GridConfig {
+        let raw = |field| Value::String(self.value(field).to_string());
+        GridConfig {
+            start_price: raw(PreviewField::StartPrice),
+            min_price: raw(PreviewField::MinPrice),
+            max_price: raw(PreviewField::MaxPrice),
+            increment_percent: raw(PreviewField::IncrementPercent),
+            target_spread_percent: raw(PreviewField::TargetSpreadPercent),
+            weight_distribution: Some(SidePair {
+                buy: raw(PreviewField::WeightBuy),
+                sell: raw(PreviewField::WeightSell),
+            }),
+            bot_funds: Some(SidePair {
+                buy: raw(PreviewField::FundsBuy),
+                sell: raw(PreviewField::FundsSell),
+            }),
+            active_orders: Some(SidePair {
+                buy: raw(PreviewField::ActiveBuy),
+                sell: raw(PreviewField::ActiveSell),
+            }),
+        }
+    }
+
+    /// Projects the ladder for the current form values. Percentage `botFunds` have no
+    /// wallet balance to resolve against, so sizes on that side are shown in percent
+    /// of the wallet instead of asset units.
+    pub fn compute(&self) -> Result<IdealGrid, String> {
+        let config = self.to_config();
+        let start = config
+            .start_price()
+            .ok_or_else(|| String::from("startPrice must be a positive number for preview"))?;
+        let budget = |side| match config.bot_funds(side) {
+            FundSpec::Absolute(amount) | FundSpec::Percent(amount) => amount,
+        };
+        grid::build_ideal_grid(
+            &config,
+            start,
+            SideFunds {
+                buy: budget(Side::Buy),
+                sell: budget(Side::Sell),
+            },
+        )
+    }
+
+    pub fn size_unit(&self, side: Side) -> &'static str {
+        match self.to_config().bot_funds(side) {
+            FundSpec::Percent(_) => "% of wallet",
+            FundSpec::Absolute(_) => "units",
+        }
+    }
+}
+
+/// Ladder rows as the Preview tab lists them, highest price first.
+pub fn ladder(grid: &IdealGrid) -> Vec<&IdealSlot> {
+    let mut slots = grid.slots.iter().collect::<Vec<_>>();
+    slots.sort_by(|a, b| b.price.total_cmp(&a.price));
+    slots
+}
+
+fn text(value: &Value, fallback: &str) -> String {
+    match value {
+        Value::String(s) if !s.trim().is_empty() => s.clone(),
+        Value::Number(n) => n.to_string(),
+        _ => fallback.to_string(),
+    }
+}
+
+fn fund_text(spec: FundSpec) -> String {
+    match spec {
+        FundSpec::Absolute(amount) => amount.to_string(),
+        FundSpec::Percent(pct) => format!("{pct}%"),
+    }
+}
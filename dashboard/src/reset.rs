@@ -0,0 +1,231 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+
+use crate::{runtime::RuntimeStatus, state::BotStatus};
+
+/// Runtime states in which the bot no longer writes its orders file.
+const STOPPED_STATES: [&str; 2] = ["stopped", "errored"];
+
+/// Per-bot alternative to `Clear Orders`: drops one bot's persisted grid so it
+/// regenerates on the next start.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BotReset {
+    pub name: String,
+    pub bot_key: String,
+    pub orders_file: PathBuf,
+}
+
+impl BotReset {
+    /// Refuses while the bot's process may still be running, since it would write
+    /// the grid straight back.
+    pub fn for_bot(bot: &BotStatus, runtime: &RuntimeStatus) -> Result<Self, String> {
+        check_stopped(bot, runtime)?;
+        let orders_file = PathBuf::from(format!("profiles/orders/{}.json", bot.identity.bot_key));
+        if !orders_file.exists() {
+            return Err(format!("{}: no orders file {}", bot.name, orders_file.display()));
+        }
+        Ok(Self {
+            name: bot.name.clone(),
+            bot_key: bot.identity.bot_key.clone(),
+            orders_file,
+        })
+    }
+
+    pub fn describe(&self) -> String {
+        format!("Reset orders of {} ({})", self.name, self.orders_file.display())
+    }
+
+    /// Bots other than this one in the orders file; they are kept.
+    pub fn other_entries(&self) -> usize {
+        fs::read_to_string(&self.orders_file)
+            .ok()
+            .and_then(|raw| serde_json::from_str::<Value>(&raw).ok())
+            .as_ref()
+            .map_or(0, |parsed| other_entries(parsed, &self.bot_key))
+    }
+
+    /// Removes the file when it only holds this bot, else just `bots.{botKey}`.
+    pub fn execute(&self) -> Result<String> {
+        let name = self.describe();
+        let raw = fs::read_to_string(&self.orders_file)?;
+        let mut parsed = serde_json::from_str::<Value>(&raw)
+            .map_err(|err| anyhow!("[danger] {name} failed\n{err}"))?;
+        let others = other_entries(&parsed, &self.bot_key);
+        if others == 0 {
+            fs::remove_file(&self.orders_file)?;
+            return Ok(format!("[danger] {name}\nremoved {}", self.orders_file.display()));
+        }
+        // Written the way `account_orders.js` writes it, so the other bots' entries
+        // keep their key order and number formatting.
+        if let Some(bots) = parsed.get_mut("bots").and_then(Value::as_object_mut) {
+            bots.shift_remove(&self.bot_key);
+        }
+        fs::write(&self.orders_file, serde_json::to_string_pretty(&parsed)? + "\n")?;
+        Ok(format!(
+            "[danger] {name}\nremoved bots.{} ({others} other entries kept)",
+            self.bot_key
+        ))
+    }
+}
+
+fn other_entries(parsed: &Value, bot_key: &str) -> usize {
+    parsed
+        .get("bots")
+        .and_then(Value::as_object)
+        .map_or(0, |bots| bots.keys().filter(|k| *k != bot_key).count())
+}
+
+pub fn check_stopped(bot: &BotStatus, runtime: &RuntimeStatus) -> Result<(), String> {
+    if !runtime.online {
+        return Err(format!(
+            "{}: runtime backend {} offline; cannot confirm the bot is stopped",
+            bot.name, runtime.backend
+        ));
+    }
+    if bot.active && bot.identity.process_name.is_none() {
+        return Err(format!(
+            "{}: process name unresolved; cannot confirm the bot is stopped",
+            bot.name
+        ));
+    }
+    match &bot.process {
+        Some(p) if !STOPPED_STATES.contains(&p.status.as_str()) => Err(format!(
+            "{}: {} process is {}; stop it first",
+            bot.name, runtime.backend, p.status
+        )),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    use crate::{runtime::ProcessRecord, testutil::ScratchDir};
+
+    fn scratch_file(tag: &str, contents: &str) -> (ScratchDir, PathBuf) {
+        let dir = ScratchDir::new(tag);
+        let path = dir.join("orders.json");
+        fs::write(&path, contents).unwrap();
+        (dir, path)
+    }
+
+    fn reset(orders_file: PathBuf) -> BotReset {
+        BotReset {
+            name: String::from("alpha"),
+            bot_key: String::from("alpha-0"),
+            orders_file,
+        }
+    }
+
+    fn pm2(online: bool) -> RuntimeStatus {
+        RuntimeStatus {
+            backend: "pm2",
+            online,
+            processes: HashMap::new(),
+        }
+    }
+
+    fn with_process(status: &str) -> BotStatus {
+        let mut bot = BotStatus::stub("alpha", "alpha-0");
+        bot.process = Some(ProcessRecord {
+            name: String::from("alpha"),
+            status: status.to_string(),
+            ..ProcessRecord::default()
+        });
+        bot
+    }
+
+    #[test]
+    fn removes_the_file_when_only_this_bot_is_in_it() {
+        let (_dir, path) = scratch_file("reset-sole", r#"{"bots":{"alpha-0":{"grid":[]}},"lastUpdated":"x"}"#);
+        let reset = reset(path.clone());
+        assert_eq!(reset.other_entries(), 0);
+        let message = reset.execute().unwrap();
+        assert!(message.ends_with(&format!("removed {}", path.display())));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn removes_only_this_entry_and_keeps_the_others_as_written() {
+        // Unsorted keys and a float that only round-trips exactly, as
+        // `JSON.stringify(data, null, 2)` writes them.
+        let before = r#"{
+  "bots": {
+    "zeta-1": {
+      "meta": {
+        "name": "zeta",
+        "active": true
+      },
+      "grid": [
+        {
+          "price": 0.30000000000000004,
+          "id": "1.7.9"
+        }
+      ]
+    },
+    "alpha-0": {
+      "grid": []
+    },
+    "beta-2": {
+      "btsFeesOwed": 1e-7,
+      "grid": []
+    }
+  },
+  "lastUpdated": "2026-01-15T10:00:00.000Z"
+}
+"#;
+        let after = r#"{
+  "bots": {
+    "zeta-1": {
+      "meta": {
+        "name": "zeta",
+        "active": true
+      },
+      "grid": [
+        {
+          "price": 0.30000000000000004,
+          "id": "1.7.9"
+        }
+      ]
+    },
+    "beta-2": {
+      "btsFeesOwed": 1e-7,
+      "grid": []
+    }
+  },
+  "lastUpdated": "2026-01-15T10:00:00.000Z"
+}
+"#;
+        let (_dir, path) = scratch_file("reset-shared", before);
+        let reset = reset(path.clone());
+        assert_eq!(reset.other_entries(), 2);
+        let message = reset.execute().unwrap();
+        assert!(message.ends_with("removed bots.alpha-0 (2 other entries kept)"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), after);
+    }
+
+    #[test]
+    fn refuses_unless_the_process_is_known_to_be_stopped() {
+        let stopped = with_process("stopped");
+        assert_eq!(check_stopped(&stopped, &pm2(true)), Ok(()));
+        assert_eq!(check_stopped(&with_process("errored"), &pm2(true)), Ok(()));
+
+        let online = check_stopped(&with_process("online"), &pm2(true)).unwrap_err();
+        assert_eq!(online, "alpha: pm2 process is online; stop it first");
+
+        let offline = check_stopped(&stopped, &pm2(false)).unwrap_err();
+        assert!(offline.contains("runtime backend pm2 offline"));
+
+        let mut unresolved = BotStatus::stub("alpha", "alpha-0");
+        unresolved.identity.process_name = None;
+        let err = check_stopped(&unresolved, &pm2(true)).unwrap_err();
+        assert!(err.contains("process name unresolved"));
+        // An inactive entry without a process name has nothing running.
+        unresolved.active = false;
+        assert_eq!(check_stopped(&unresolved, &pm2(true)), Ok(()));
+    }
+}
@@ -0,0 +1,231 @@
+use std::collections::{BTreeSet, HashMap};
+
+use serde_json::Value;
+
+use crate::state::BotStatus;
+
+/// One `bots.json` field that differs from what the running process started with.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldChange {
+    /// Dotted path, e.g. `botFunds.buy`.
+    pub field: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConfigState {
+    /// `bots.json` not modified since the process started, or the entry is unchanged.
+    Current,
+    /// The bot's own entry changed after the process started.
+    RestartRequired(Vec<FieldChange>),
+    /// `bots.json` was modified after the start, but the dashboard never saw the
+    /// entry the process started with.
+    Unknown,
+}
+
+/// Field-level differences between two `bots.json` entries; nested objects are
+/// compared key by key.
+pub fn diff(before: &Value, after: &Value) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    diff_into("", before, after, &mut changes);
+    changes
+}
+
+fn diff_into(path: &str, before: &Value, after: &Value, changes: &mut Vec<FieldChange>) {
+    match (before, after) {
+        (Value::Object(a), Value::Object(b)) => {
+            let keys = a.keys().chain(b.keys()).collect::<BTreeSet<_>>();
+            for key in keys {
+                let field = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                match (a.get(key), b.get(key)) {
+                    (Some(x), Some(y)) => diff_into(&field, x, y, changes),
+                    (x, y) => changes.push(FieldChange {
+                        field,
+                        before: x.cloned(),
+                        after: y.cloned(),
+                    }),
+                }
+            }
+        }
+        (a, b) if a != b => changes.push(FieldChange {
+            field: path.to_string(),
+            before: Some(a.clone()),
+            after: Some(b.clone()),
+        }),
+        _ => {}
+    }
+}
+
+/// Cross-poll record of the entry each running process started with. The entry
+/// is captured the first time a start is seen, provided `bots.json` has not been
+/// modified since that start; otherwise the baseline is unknown.
+#[derive(Debug, Default)]
+pub struct RestartTracker {
+    bots: HashMap<String, Baseline>,
+}
+
+#[derive(Debug)]
+struct Baseline {
+    started_at_ms: i64,
+    entry: Option<Value>,
+    state: ConfigState,
+}
+
+impl RestartTracker {
+    pub fn observe(&mut self, bots: &[BotStatus], bots_json_modified_ms: Option<i64>) -> Vec<String> {
+        let mut alerts = Vec::new();
+        let mut running = Vec::new();
+        for bot in bots {
+            let Some(started_at_ms) = bot
+                .process
+                .as_ref()
+                .filter(|p| p.status == "online")
+                .and_then(|p| p.started_at_ms)
+                .filter(|s| *s > 0)
+            else {
+                continue;
+            };
+            running.push(bot.name.clone());
+            let modified_after_start = bots_json_modified_ms.is_some_and(|m| m > started_at_ms);
+
+            let baseline = self
+                .bots
+                .entry(bot.name.clone())
+                .or_insert_with(|| Baseline {
+                    started_at_ms: -1,
+                    entry: None,
+                    state: ConfigState::Current,
+                });
+            if baseline.started_at_ms != started_at_ms {
+                baseline.started_at_ms = started_at_ms;
+                baseline.entry = (!modified_after_start).then(|| bot.entry.clone());
+            }
+            // Compared field by field, so a re-saved or reordered file is no change.
+            baseline.state = match &baseline.entry {
+                _ if !modified_after_start => ConfigState::Current,
+                Some(started_entry) => {
+                    let changes = diff(started_entry, &bot.entry);
+                    if changes.is_empty() {
+                        ConfigState::Current
+                    } else {
+                        ConfigState::RestartRequired(changes)
+                    }
+                }
+                None => ConfigState::Unknown,
+            };
+            if let ConfigState::RestartRequired(changes) = &baseline.state {
+                let fields = changes.iter().map(|c| c.field.as_str()).collect::<Vec<_>>();
+                alerts.push(format!(
+                    "{}: bots.json entry changed since the process started ({}); restart to apply.",
+                    bot.name,
+                    fields.join(", ")
+                ));
+            }
+        }
+        self.bots.retain(|name, _| running.contains(name));
+        alerts
+    }
+
+    /// `None` when the bot has no running process.
+    pub fn state(&self, name: &str) -> Option<&ConfigState> {
+        self.bots.get(name).map(|b| &b.state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::ProcessRecord;
+
+    fn running(entry: Value, started_at_ms: i64) -> BotStatus {
+        let mut bot = BotStatus::stub("alpha", "alpha-0");
+        bot.entry = entry;
+        bot.process = Some(ProcessRecord {
+            name: String::from("alpha"),
+            status: String::from("online"),
+            started_at_ms: Some(started_at_ms),
+            ..ProcessRecord::default()
+        });
+        bot
+    }
+
+    #[test]
+    fn observe_flags_only_real_changes_after_the_start() {
+        let started = serde_json::json!({"name": "alpha", "incrementPercent": 0.5, "active": true});
+        let mut tracker = RestartTracker::default();
+
+        // Last written before the start: the entry is the one the process runs.
+        assert!(tracker.observe(&[running(started.clone(), 2_000)], Some(1_000)).is_empty());
+        assert_eq!(tracker.state("alpha"), Some(&ConfigState::Current));
+
+        // Re-saved with the keys reordered: nothing to restart for.
+        let reordered = serde_json::json!({"active": true, "incrementPercent": 0.5, "name": "alpha"});
+        assert!(tracker.observe(&[running(reordered, 2_000)], Some(3_000)).is_empty());
+        assert_eq!(tracker.state("alpha"), Some(&ConfigState::Current));
+
+        let edited = serde_json::json!({"name": "alpha", "incrementPercent": 0.4, "active": true});
+        let alerts = tracker.observe(&[running(edited.clone(), 2_000)], Some(4_000));
+        assert_eq!(
+            alerts,
+            ["alpha: bots.json entry changed since the process started (incrementPercent); restart to apply."]
+        );
+        let Some(ConfigState::RestartRequired(changes)) = tracker.state("alpha") else {
+            panic!("expected RestartRequired");
+        };
+        assert_eq!(changes[0].after, Some(serde_json::json!(0.4)));
+
+        // A restart after the edit takes the edited entry as the new baseline.
+        assert!(tracker.observe(&[running(edited, 5_000)], Some(4_000)).is_empty());
+        assert_eq!(tracker.state("alpha"), Some(&ConfigState::Current));
+
+        // Stopped processes are forgotten.
+        tracker.observe(&[BotStatus::stub("alpha", "alpha-0")], Some(4_000));
+        assert_eq!(tracker.state("alpha"), None);
+    }
+
+    #[test]
+    fn observe_is_unknown_when_first_seen_after_an_edit() {
+        let entry = serde_json::json!({"name": "alpha"});
+        let mut tracker = RestartTracker::default();
+        assert!(tracker.observe(&[running(entry.clone(), 2_000)], Some(3_000)).is_empty());
+        assert_eq!(tracker.state("alpha"), Some(&ConfigState::Unknown));
+
+        // Only a new start gives it a baseline.
+        tracker.observe(&[running(entry, 4_000)], Some(3_000));
+        assert_eq!(tracker.state("alpha"), Some(&ConfigState::Current));
+    }
+
+    #[test]
+    fn diff_reports_nested_added_and_removed_fields() {
+        let before = serde_json::json!({
+            "name": "xrp-bts",
+            "incrementPercent": 0.5,
+            "botFunds": {"buy": "50%", "sell": "100%"},
+            "startPrice": "pool",
+        });
+        let after = serde_json::json!({
+            "name": "xrp-bts",
+            "incrementPercent": 0.4,
+            "botFunds": {"buy": "60%", "sell": "100%"},
+            "activeOrders": {"buy": 5},
+        });
+        let fields = diff(&before, &after)
+            .into_iter()
+            .map(|c| (c.field, c.before.is_some(), c.after.is_some()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fields,
+            vec![
+                (String::from("activeOrders"), false, true),
+                (String::from("botFunds.buy"), true, true),
+                (String::from("incrementPercent"), true, true),
+                (String::from("startPrice"), true, false),
+            ]
+        );
+    }
+}
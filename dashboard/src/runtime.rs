@@ -0,0 +1,804 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+
+use crate::identity::SYSTEM_PROCESSES;
+
+/// Runtime state of one managed process, normalized to pm2's status vocabulary
+/// (`online`, `stopped`, `errored`, `launching`, ...) whatever the backend.
+/// Metrics are only filled in where the backend reports them (pm2 fills all of them).
+#[derive(Debug, Clone, Default)]
+pub struct ProcessRecord {
+    pub name: String,
+    pub status: String,
+    pub pid: Option<u32>,
+    pub cpu_percent: Option<f64>,
+    pub memory_bytes: Option<u64>,
+    /// Epoch milliseconds of the last (re)start (`pm2_env.pm_uptime`).
+    pub started_at_ms: Option<i64>,
+    pub restarts: Option<u64>,
+    pub unstable_restarts: Option<u64>,
+    pub node_version: Option<String>,
+    pub exec_path: Option<String>,
+    pub out_file: Option<String>,
+    pub error_file: Option<String>,
+}
+
+impl ProcessRecord {
+    pub fn uptime(&self, now_ms: i64) -> Option<Duration> {
+        self.started_at_ms
+            .filter(|started| *started > 0 && *started <= now_ms)
+            .map(|started| Duration::from_millis((now_ms - started) as u64))
+    }
+}
+
+pub trait RuntimeProvider {
+    fn name(&self) -> &'static str;
+    fn list(&self) -> Result<Vec<ProcessRecord>>;
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuntimeBackend {
+    #[default]
+    Auto,
+    Pm2,
+    Docker,
+    Systemd,
+    Proc,
+}
+
+#[derive(Debug, Clone)]
+pub struct RuntimeStatus {
+    pub backend: &'static str,
+    pub online: bool,
+    pub processes: HashMap<String, ProcessRecord>,
+}
+
+/// How often `Auto` re-walks every backend once one has been picked, so a bot
+/// moved to another runtime is still found without running all of them per poll.
+const AUTO_REPROBE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Queries the configured backend and remembers which one `Auto` settled on.
+#[derive(Debug, Default)]
+pub struct RuntimeSelector {
+    chosen: Option<&'static str>,
+    probed_at: Option<Instant>,
+}
+
+impl RuntimeSelector {
+    /// `Auto` walks pm2, Docker, systemd and `/proc` in that order and keeps the
+    /// first one that reports any bot process, else the first that answered. The
+    /// cred and update daemons are not bots: a host running only those under pm2
+    /// does not pin `Auto` to pm2.
+    pub fn load(&mut self, backend: RuntimeBackend, systemd_pattern: &str) -> RuntimeStatus {
+        let pm2 = Pm2Provider::new("pm2");
+        let docker = DockerProvider::new("docker");
+        let systemd = SystemdProvider::new("systemctl", systemd_pattern);
+        let procfs = ProcProvider::new("/proc");
+
+        let candidates: Vec<&dyn RuntimeProvider> = match backend {
+            RuntimeBackend::Auto => vec![&pm2, &docker, &systemd, &procfs],
+            RuntimeBackend::Pm2 => vec![&pm2],
+            RuntimeBackend::Docker => vec![&docker],
+            RuntimeBackend::Systemd => vec![&systemd],
+            RuntimeBackend::Proc => vec![&procfs],
+        };
+        self.select(&candidates, Instant::now())
+    }
+
+    /// Between full walks only the remembered backend is asked; a failure there
+    /// walks all of them again straight away.
+    fn select(&mut self, candidates: &[&dyn RuntimeProvider], now: Instant) -> RuntimeStatus {
+        let fresh = self
+            .probed_at
+            .is_some_and(|at| now.duration_since(at) < AUTO_REPROBE_INTERVAL);
+        let remembered = self
+            .chosen
+            .filter(|_| fresh && candidates.len() > 1)
+            .and_then(|name| candidates.iter().find(|p| p.name() == name));
+        if let Some(status) = remembered.and_then(|provider| query(*provider)) {
+            return status;
+        }
+
+        self.probed_at = Some(now);
+        let mut first_ok: Option<RuntimeStatus> = None;
+        for provider in candidates {
+            let Some(status) = query(*provider) else {
+                continue;
+            };
+            if status
+                .processes
+                .keys()
+                .any(|name| !SYSTEM_PROCESSES.contains(&name.as_str()))
+            {
+                self.chosen = Some(status.backend);
+                return status;
+            }
+            first_ok.get_or_insert(status);
+        }
+        self.chosen = first_ok.as_ref().map(|s| s.backend);
+
+        first_ok.unwrap_or_else(|| RuntimeStatus {
+            backend: candidates.first().map(|p| p.name()).unwrap_or("none"),
+            online: false,
+            processes: HashMap::new(),
+        })
+    }
+}
+
+fn query(provider: &dyn RuntimeProvider) -> Option<RuntimeStatus> {
+    let records = provider.list().ok()?;
+    Some(RuntimeStatus {
+        backend: provider.name(),
+        online: true,
+        processes: records
+            .into_iter()
+            .map(|r| (r.name.clone(), r))
+            .collect(),
+    })
+}
+
+fn run(program: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new(program).args(args).output()?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "{} exited with {:?}",
+            program.display(),
+            output.status.code()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Bot name from a `bot.js <name>` or `dexbot.js start <name>` command line.
+fn bot_name_from_args<S: AsRef<str>>(args: &[S]) -> Option<String> {
+    let args = args.iter().map(|a| a.as_ref()).collect::<Vec<_>>();
+    for (i, arg) in args.iter().enumerate() {
+        let file = arg.rsplit('/').next().unwrap_or(arg);
+        let name = match file {
+            "bot.js" => args.get(i + 1),
+            "dexbot.js" if args.get(i + 1) == Some(&"start") => args.get(i + 2),
+            _ => None,
+        };
+        if let Some(name) = name.filter(|n| !n.is_empty()) {
+            return Some(name.trim_matches('"').to_string());
+        }
+    }
+    None
+}
+
+pub struct Pm2Provider {
+    program: PathBuf,
+}
+
+impl Pm2Provider {
+    pub fn new(program: impl Into<PathBuf>) -> Self {
+        Self {
+            program: program.into(),
+        }
+    }
+}
+
+impl RuntimeProvider for Pm2Provider {
+    fn name(&self) -> &'static str {
+        "pm2"
+    }
+
+    fn list(&self) -> Result<Vec<ProcessRecord>> {
+        let text = run(&self.program, &["jlist"])?;
+        let json_start = text.find('[').unwrap_or(0);
+        let value = serde_json::from_str::<serde_json::Value>(&text[json_start..])?;
+        let items = value
+            .as_array()
+            .ok_or_else(|| anyhow!("pm2 jlist did not return an array"))?;
+
+        let mut records = Vec::new();
+        for item in items {
+            let name = item
+                .get("name")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string();
+            if name.is_empty() {
+                continue;
+            }
+            let env = item.get("pm2_env");
+            let env_str = |key: &str| {
+                env.and_then(|e| e.get(key))
+                    .and_then(|v| v.as_str())
+                    .filter(|v| !v.is_empty())
+                    .map(str::to_string)
+            };
+            let env_u64 = |key: &str| env.and_then(|e| e.get(key)).and_then(|v| v.as_u64());
+            let monit = item.get("monit");
+            records.push(ProcessRecord {
+                name,
+                status: env_str("status").unwrap_or_else(|| String::from("unknown")),
+                pid: item
+                    .get("pid")
+                    .and_then(|v| v.as_u64())
+                    .filter(|pid| *pid > 0)
+                    .map(|pid| pid as u32),
+                cpu_percent: monit.and_then(|m| m.get("cpu")).and_then(|v| v.as_f64()),
+                memory_bytes: monit.and_then(|m| m.get("memory")).and_then(|v| v.as_u64()),
+                started_at_ms: env
+                    .and_then(|e| e.get("pm_uptime"))
+                    .and_then(|v| v.as_i64()),
+                restarts: env_u64("restart_time"),
+                unstable_restarts: env_u64("unstable_restarts"),
+                node_version: env_str("node_version"),
+                exec_path: env_str("pm_exec_path"),
+                out_file: env_str("pm_out_log_path"),
+                error_file: env_str("pm_err_log_path"),
+            });
+        }
+        Ok(records)
+    }
+}
+
+pub struct DockerProvider {
+    program: PathBuf,
+}
+
+impl DockerProvider {
+    pub fn new(program: impl Into<PathBuf>) -> Self {
+        Self {
+            program: program.into(),
+        }
+    }
+}
+
+impl RuntimeProvider for DockerProvider {
+    fn name(&self) -> &'static str {
+        "docker"
+    }
+
+    fn list(&self) -> Result<Vec<ProcessRecord>> {
+        let ids = run(&self.program, &["ps", "-aq"])?;
+        let ids = ids.split_whitespace().collect::<Vec<_>>();
+        if ids.is_empty() {
+            return Ok(vec![]);
+        }
+        let mut args = vec!["inspect"];
+        args.extend(ids);
+        let text = run(&self.program, &args)?;
+        let value = serde_json::from_str::<serde_json::Value>(&text)?;
+        let items = value
+            .as_array()
+            .ok_or_else(|| anyhow!("docker inspect did not return an array"))?;
+
+        let mut records = Vec::new();
+        for item in items {
+            let config = item.get("Config");
+            let env_name = config
+                .and_then(|c| c.get("Env"))
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|v| v.as_str())
+                .find_map(|kv| kv.strip_prefix("BOT_NAME="))
+                .filter(|n| !n.is_empty())
+                .map(str::to_string);
+            let cmd = config
+                .and_then(|c| c.get("Cmd"))
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|v| v.as_str())
+                .flat_map(|s| s.split_whitespace())
+                .collect::<Vec<_>>();
+            // Only containers that run a bot are reported.
+            let Some(name) = env_name.or_else(|| bot_name_from_args(&cmd)) else {
+                continue;
+            };
+            let state = item.get("State");
+            let status = match state
+                .and_then(|s| s.get("Status"))
+                .and_then(|v| v.as_str())
+                .unwrap_or("unknown")
+            {
+                "running" => "online",
+                "restarting" => "launching",
+                "exited" | "created" => "stopped",
+                "dead" => "errored",
+                other => other,
+            }
+            .to_string();
+            let pid = state
+                .and_then(|s| s.get("Pid"))
+                .and_then(|v| v.as_u64())
+                .filter(|pid| *pid > 0)
+                .map(|pid| pid as u32);
+            records.push(ProcessRecord {
+                name,
+                status,
+                pid,
+                restarts: item.get("RestartCount").and_then(|v| v.as_u64()),
+                ..ProcessRecord::default()
+            });
+        }
+        Ok(records)
+    }
+}
+
+pub struct SystemdProvider {
+    program: PathBuf,
+    pattern: String,
+}
+
+impl SystemdProvider {
+    pub fn new(program: impl Into<PathBuf>, pattern: &str) -> Self {
+        Self {
+            program: program.into(),
+            pattern: pattern.to_string(),
+        }
+    }
+}
+
+impl RuntimeProvider for SystemdProvider {
+    fn name(&self) -> &'static str {
+        "systemd"
+    }
+
+    fn list(&self) -> Result<Vec<ProcessRecord>> {
+        let text = run(
+            &self.program,
+            &[
+                "show",
+                "--property=Id,ActiveState,SubState,MainPID,ExecStart",
+                &self.pattern,
+            ],
+        )?;
+
+        let mut records = Vec::new();
+        for block in text.split("\n\n") {
+            let props = block
+                .lines()
+                .filter_map(|line| line.split_once('='))
+                .collect::<HashMap<_, _>>();
+            let Some(unit) = props.get("Id").filter(|id| !id.is_empty()) else {
+                continue;
+            };
+            // `ExecStart={ path=... ; argv[]=/usr/bin/node /app/bot.js name ; ... }`
+            let argv = props
+                .get("ExecStart")
+                .and_then(|exec| exec.split("argv[]=").nth(1))
+                .map(|rest| rest.split(" ;").next().unwrap_or(rest))
+                .map(|argv| argv.split_whitespace().collect::<Vec<_>>())
+                .unwrap_or_default();
+            let instance = unit
+                .strip_suffix(".service")
+                .and_then(|u| u.split_once('@'))
+                .map(|(_, instance)| instance.to_string())
+                .filter(|i| !i.is_empty());
+            let Some(name) = bot_name_from_args(&argv).or(instance) else {
+                continue;
+            };
+            let status = match props.get("ActiveState").copied().unwrap_or("unknown") {
+                "active" => "online",
+                "activating" | "reloading" => "launching",
+                "failed" => "errored",
+                "inactive" | "deactivating" => "stopped",
+                other => other,
+            }
+            .to_string();
+            let pid = props
+                .get("MainPID")
+                .and_then(|pid| pid.parse::<u32>().ok())
+                .filter(|pid| *pid > 0);
+            records.push(ProcessRecord {
+                name,
+                status,
+                pid,
+                ..ProcessRecord::default()
+            });
+        }
+        Ok(records)
+    }
+}
+
+/// Bare-process fallback: scans `<root>/<pid>/cmdline` for `bot.js <name>`.
+pub struct ProcProvider {
+    root: PathBuf,
+}
+
+impl ProcProvider {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl RuntimeProvider for ProcProvider {
+    fn name(&self) -> &'static str {
+        "proc"
+    }
+
+    fn list(&self) -> Result<Vec<ProcessRecord>> {
+        let mut records = Vec::new();
+        for entry in fs::read_dir(&self.root)?.filter_map(|e| e.ok()) {
+            let Ok(pid) = entry.file_name().to_string_lossy().parse::<u32>() else {
+                continue;
+            };
+            let Ok(raw) = fs::read(entry.path().join("cmdline")) else {
+                continue;
+            };
+            let args = raw
+                .split(|b| *b == 0)
+                .filter(|a| !a.is_empty())
+                .map(|a| String::from_utf8_lossy(a).to_string())
+                .collect::<Vec<_>>();
+            if let Some(name) = bot_name_from_args(&args) {
+                records.push(ProcessRecord {
+                    name,
+                    status: String::from("online"),
+                    pid: Some(pid),
+                    ..ProcessRecord::default()
+                });
+            }
+        }
+        Ok(records)
+    }
+}
+
+/// Cross-poll memory and restart history. pm2 keeps a crash-looping app "online",
+/// so the only signals are a restart counter that keeps rising and memory that
+/// keeps growing; both need state that outlives a single snapshot.
+#[derive(Debug, Default)]
+pub struct ProcessTracker {
+    processes: HashMap<String, TrackedProcess>,
+}
+
+#[derive(Debug, Default)]
+struct TrackedProcess {
+    restarts: Option<u64>,
+    unstable_restarts: Option<u64>,
+    memory: VecDeque<(Instant, u64)>,
+    restart_events: VecDeque<(Instant, u64, u64)>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TrackerLimits {
+    pub memory_growth_percent: f64,
+    pub window: Duration,
+}
+
+impl ProcessTracker {
+    /// Records one poll and returns the alerts that currently apply. Restart alerts
+    /// stay visible for the whole window so a single 1s poll does not hide them.
+    pub fn observe(
+        &mut self,
+        status: &RuntimeStatus,
+        limits: TrackerLimits,
+        now: Instant,
+    ) -> Vec<String> {
+        self.processes
+            .retain(|name, _| status.processes.contains_key(name));
+
+        let mut names = status.processes.keys().collect::<Vec<_>>();
+        names.sort();
+        let mut alerts = Vec::new();
+        for name in names {
+            let record = &status.processes[name];
+            let tracked = self.processes.entry(name.clone()).or_default();
+
+            if let (Some(before), Some(after)) = (tracked.restarts, record.restarts) {
+                if after > before {
+                    let unstable = record
+                        .unstable_restarts
+                        .unwrap_or(0)
+                        .saturating_sub(tracked.unstable_restarts.unwrap_or(0));
+                    tracked.restart_events.push_back((now, after - before, unstable));
+                }
+            }
+            tracked.restarts = record.restarts;
+            tracked.unstable_restarts = record.unstable_restarts;
+            while tracked
+                .restart_events
+                .front()
+                .is_some_and(|(at, _, _)| now.duration_since(*at) > limits.window)
+            {
+                tracked.restart_events.pop_front();
+            }
+            if !tracked.restart_events.is_empty() {
+                let restarts = tracked.restart_events.iter().map(|e| e.1).sum::<u64>();
+                let unstable = tracked.restart_events.iter().map(|e| e.2).sum::<u64>();
+                alerts.push(format!(
+                    "{name}: restarted {restarts}x in the last {}s ({unstable} unstable, total {}).",
+                    limits.window.as_secs(),
+                    record.restarts.unwrap_or(0)
+                ));
+            }
+
+            match record.memory_bytes.filter(|m| *m > 0) {
+                Some(memory) => tracked.memory.push_back((now, memory)),
+                None => tracked.memory.clear(),
+            }
+            while tracked
+                .memory
+                .front()
+                .is_some_and(|(at, _)| now.duration_since(*at) > limits.window)
+            {
+                tracked.memory.pop_front();
+            }
+            if let (Some((_, first)), Some((_, last))) =
+                (tracked.memory.front(), tracked.memory.back())
+            {
+                let growth = (*last as f64 / *first as f64 - 1.0) * 100.0;
+                if growth > limits.memory_growth_percent {
+                    alerts.push(format!(
+                        "{name}: memory grew {growth:.0}% ({} -> {}) within {}s.",
+                        format_bytes(*first),
+                        format_bytes(*last),
+                        limits.window.as_secs()
+                    ));
+                }
+            }
+        }
+        alerts
+    }
+}
+
+pub fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    let mb = bytes as f64 / (1024.0 * 1024.0);
+    if mb >= 1024.0 {
+        format!("{:.1}G", mb / 1024.0)
+    } else {
+        format!("{mb:.0}M")
+    }
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        0..=59 => format!("{secs}s"),
+        60..=3599 => format!("{}m", secs / 60),
+        3600..=86399 => format!("{}h{:02}m", secs / 3600, secs % 3600 / 60),
+        _ => format!("{}d{:02}h", secs / 86400, secs % 86400 / 3600),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    use crate::testutil::ScratchDir;
+
+    /// Writes an executable stand-in that prints `stdout` for any arguments.
+    fn stand_in(dir: &Path, name: &str, stdout: &str) -> PathBuf {
+        let payload = dir.join(format!("{name}.out"));
+        fs::write(&payload, stdout).unwrap();
+        let path = dir.join(name);
+        fs::write(&path, format!("#!/bin/sh\ncat '{}'\n", payload.display())).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[test]
+    fn pm2_parses_jlist_after_banner() {
+        let dir = ScratchDir::new("pm2");
+        let bin = stand_in(
+            &dir,
+            "pm2",
+            r#"some banner
+[{"name":"bts","pid":42,"monit":{"cpu":1.5,"memory":73400320},"pm2_env":{"status":"online","pm_uptime":1700000000000,"restart_time":3,"unstable_restarts":1,"node_version":"20.11.0","pm_out_log_path":"/srv/profiles/logs/bts.log"}},{"name":"","pm2_env":{}}]"#,
+        );
+        let records = Pm2Provider::new(bin).list().unwrap();
+        assert_eq!(records.len(), 1);
+        let bts = &records[0];
+        assert_eq!(bts.name, "bts");
+        assert_eq!(bts.status, "online");
+        assert_eq!(bts.pid, Some(42));
+        assert_eq!(bts.memory_bytes, Some(73_400_320));
+        assert_eq!(bts.restarts, Some(3));
+        assert_eq!(bts.unstable_restarts, Some(1));
+        assert_eq!(bts.node_version.as_deref(), Some("20.11.0"));
+        assert_eq!(bts.out_file.as_deref(), Some("/srv/profiles/logs/bts.log"));
+        assert_eq!(bts.error_file, None);
+        assert_eq!(
+            bts.uptime(1_700_000_090_000),
+            Some(Duration::from_secs(90))
+        );
+    }
+
+    #[test]
+    fn tracker_flags_restart_increase_and_memory_growth() {
+        let limits = TrackerLimits {
+            memory_growth_percent: 50.0,
+            window: Duration::from_secs(600),
+        };
+        let poll = |restarts, memory| RuntimeStatus {
+            backend: "pm2",
+            online: true,
+            processes: HashMap::from([(
+                String::from("bts"),
+                ProcessRecord {
+                    name: String::from("bts"),
+                    status: String::from("online"),
+                    restarts: Some(restarts),
+                    memory_bytes: Some(memory),
+                    ..ProcessRecord::default()
+                },
+            )]),
+        };
+        let mut tracker = ProcessTracker::default();
+        let t0 = Instant::now();
+        assert!(tracker.observe(&poll(2, 100 << 20), limits, t0).is_empty());
+        let alerts = tracker.observe(&poll(4, 120 << 20), limits, t0 + Duration::from_secs(1));
+        assert_eq!(alerts.len(), 1);
+        assert!(alerts[0].contains("restarted 2x"));
+        let alerts = tracker.observe(&poll(4, 160 << 20), limits, t0 + Duration::from_secs(2));
+        assert_eq!(alerts.len(), 2);
+        assert!(alerts[1].contains("memory grew 60%"));
+        // Both signals age out once the window has passed without new evidence.
+        let alerts = tracker.observe(&poll(4, 160 << 20), limits, t0 + Duration::from_secs(700));
+        assert!(alerts.is_empty());
+    }
+
+    #[test]
+    fn docker_maps_bot_name_and_state() {
+        let dir = ScratchDir::new("docker");
+        let inspect = r#"[
+            {"Name":"/dexbot2","Config":{"Env":["BOT_NAME=xrp-bts"],"Cmd":["sh","-c","node dexbot.js start \"xrp-bts\""]},"State":{"Status":"running","Pid":77}},
+            {"Name":"/db","Config":{"Env":[],"Cmd":["postgres"]},"State":{"Status":"running","Pid":5}},
+            {"Name":"/old","Config":{"Env":[],"Cmd":["node","bot.js","legacy"]},"State":{"Status":"exited","Pid":0}}
+        ]"#;
+        let bin = dir.join("docker");
+        fs::write(dir.join("inspect.out"), inspect).unwrap();
+        fs::write(
+            &bin,
+            format!(
+                "#!/bin/sh\nif [ \"$1\" = ps ]; then echo abc; echo def; echo ghi; else cat '{}'; fi\n",
+                dir.join("inspect.out").display()
+            ),
+        )
+        .unwrap();
+        fs::set_permissions(&bin, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let records = DockerProvider::new(bin).list().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].name, "xrp-bts");
+        assert_eq!(records[0].status, "online");
+        assert_eq!(records[1].name, "legacy");
+        assert_eq!(records[1].status, "stopped");
+    }
+
+    #[test]
+    fn systemd_reads_exec_start_and_instance_units() {
+        let dir = ScratchDir::new("systemd");
+        let bin = stand_in(
+            &dir,
+            "systemctl",
+            "Id=dexbot-a.service\nActiveState=active\nSubState=running\nMainPID=100\nExecStart={ path=/usr/bin/node ; argv[]=/usr/bin/node /srv/dexbot/bot.js alpha ; ignore_errors=no }\n\n\
+             Id=dexbot@beta.service\nActiveState=failed\nSubState=failed\nMainPID=0\nExecStart=\n",
+        );
+        let records = SystemdProvider::new(bin, "dexbot*").list().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!((records[0].name.as_str(), records[0].status.as_str()), ("alpha", "online"));
+        assert_eq!(records[0].pid, Some(100));
+        assert_eq!((records[1].name.as_str(), records[1].status.as_str()), ("beta", "errored"));
+        assert_eq!(records[1].pid, None);
+    }
+
+    #[test]
+    fn proc_scan_matches_bot_command_lines() {
+        let root = ScratchDir::new("proc");
+        for (pid, cmdline) in [
+            ("10", "node\0/app/bot.js\0gamma\0"),
+            ("11", "node\0/app/credential-daemon.js\0"),
+            ("self", "node\0bot.js\0ignored\0"),
+        ] {
+            fs::create_dir_all(root.join(pid)).unwrap();
+            fs::write(root.join(pid).join("cmdline"), cmdline).unwrap();
+        }
+        let records = ProcProvider::new(root.to_path_buf()).list().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].name, "gamma");
+        assert_eq!(records[0].pid, Some(10));
+    }
+
+    struct Counting {
+        name: &'static str,
+        records: Vec<&'static str>,
+        calls: std::cell::Cell<usize>,
+    }
+
+    impl RuntimeProvider for Counting {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn list(&self) -> Result<Vec<ProcessRecord>> {
+            self.calls.set(self.calls.get() + 1);
+            Ok(self
+                .records
+                .iter()
+                .map(|name| ProcessRecord {
+                    name: name.to_string(),
+                    status: String::from("online"),
+                    ..ProcessRecord::default()
+                })
+                .collect())
+        }
+    }
+
+    #[test]
+    fn auto_asks_only_the_remembered_backend_between_probes() {
+        let counting = |name, records| Counting {
+            name,
+            records,
+            calls: std::cell::Cell::new(0),
+        };
+        let pm2 = counting("pm2", vec![]);
+        let docker = counting("docker", vec!["alpha"]);
+        let candidates: Vec<&dyn RuntimeProvider> = vec![&pm2, &docker];
+        let mut selector = RuntimeSelector::default();
+        let t0 = Instant::now();
+
+        assert_eq!(selector.select(&candidates, t0).backend, "docker");
+        assert_eq!((pm2.calls.get(), docker.calls.get()), (1, 1));
+        let status = selector.select(&candidates, t0 + Duration::from_secs(1));
+        assert_eq!(status.backend, "docker");
+        assert!(status.processes.contains_key("alpha"));
+        assert_eq!((pm2.calls.get(), docker.calls.get()), (1, 2));
+
+        // The slow re-probe walks pm2 first again.
+        selector.select(&candidates, t0 + AUTO_REPROBE_INTERVAL);
+        assert_eq!((pm2.calls.get(), docker.calls.get()), (2, 3));
+    }
+
+    #[test]
+    fn auto_remembers_an_answering_backend_without_bots() {
+        let pm2 = Counting {
+            name: "pm2",
+            records: vec![],
+            calls: std::cell::Cell::new(0),
+        };
+        let missing = Pm2Provider::new("/nonexistent/docker");
+        let candidates: Vec<&dyn RuntimeProvider> = vec![&missing, &pm2];
+        let mut selector = RuntimeSelector::default();
+        let t0 = Instant::now();
+        let status = selector.select(&candidates, t0);
+        assert!(status.online && status.processes.is_empty());
+        selector.select(&candidates, t0 + Duration::from_secs(1));
+        assert_eq!(pm2.calls.get(), 2);
+    }
+
+    #[test]
+    fn auto_skips_a_backend_running_only_system_processes() {
+        let pm2 = Counting {
+            name: "pm2",
+            records: vec!["dexbot-cred", "dexbot-update"],
+            calls: std::cell::Cell::new(0),
+        };
+        let docker = Counting {
+            name: "docker",
+            records: vec!["alpha"],
+            calls: std::cell::Cell::new(0),
+        };
+        let candidates: Vec<&dyn RuntimeProvider> = vec![&pm2, &docker];
+        let status = RuntimeSelector::default().select(&candidates, Instant::now());
+        assert_eq!(status.backend, "docker");
+    }
+
+    #[test]
+    fn missing_backend_reports_offline() {
+        let err = Pm2Provider::new("/nonexistent/pm2").list();
+        assert!(err.is_err());
+    }
+}
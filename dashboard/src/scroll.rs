@@ -0,0 +1,174 @@
+use std::collections::HashSet;
+
+use crate::logline::{Level, LogLine};
+
+/// Filtered, scrollable window over a list of log lines, shared by the log viewer
+/// and the timeline. `visible` holds indices into the owner's list in ascending
+/// order and `offset` indexes `visible`.
+#[derive(Debug)]
+pub struct LogScroll {
+    pub visible: Vec<usize>,
+    pub offset: usize,
+    pub page_height: usize,
+    /// Pinned to the newest line; any upward scroll pauses it.
+    pub follow: bool,
+    pub levels: HashSet<Level>,
+    pub category: Option<String>,
+    /// Categories seen in the owner's lines, sorted.
+    pub categories: Vec<String>,
+}
+
+impl Default for LogScroll {
+    fn default() -> Self {
+        Self {
+            visible: Vec::new(),
+            offset: 0,
+            page_height: 20,
+            follow: true,
+            levels: Level::ALL.into_iter().collect(),
+            category: None,
+            categories: Vec::new(),
+        }
+    }
+}
+
+impl LogScroll {
+    pub fn passes(&self, line: &LogLine) -> bool {
+        line.level.is_none_or(|l| self.levels.contains(&l))
+            && self
+                .category
+                .as_ref()
+                .is_none_or(|c| line.categories.contains(c))
+    }
+
+    /// The owner's index of the line on top of the page.
+    pub fn anchor(&self) -> Option<usize> {
+        self.visible.get(self.offset).copied()
+    }
+
+    /// Replaces `visible`. A following view jumps to the end; a paused one keeps
+    /// `anchor` (an owner index in the new list) on top, or the next line that
+    /// passes the filters.
+    pub fn set_visible(&mut self, visible: Vec<usize>, anchor: Option<usize>) {
+        self.visible = visible;
+        if self.follow {
+            self.offset = self.max_offset();
+        } else {
+            self.offset = anchor
+                .map(|a| self.visible.partition_point(|i| *i < a))
+                .unwrap_or(0)
+                .min(self.max_offset());
+        }
+    }
+
+    /// Appends lines that arrived after everything in `visible`.
+    pub fn extend_visible(&mut self, more: impl IntoIterator<Item = usize>) {
+        self.visible.extend(more);
+        if self.follow {
+            self.offset = self.max_offset();
+        }
+    }
+
+    pub fn add_categories<'a>(&mut self, found: impl IntoIterator<Item = &'a String>) {
+        for category in found {
+            if let Err(at) = self.categories.binary_search(category) {
+                self.categories.insert(at, category.clone());
+            }
+        }
+    }
+
+    pub fn max_offset(&self) -> usize {
+        self.visible.len().saturating_sub(self.page_height.max(1))
+    }
+
+    pub fn set_page_height(&mut self, height: usize) {
+        self.page_height = height.max(1);
+        if self.follow {
+            self.offset = self.max_offset();
+        } else {
+            self.offset = self.offset.min(self.max_offset());
+        }
+    }
+
+    /// Pages scroll by one line less than the page, so one line stays in view.
+    pub fn page(&self) -> usize {
+        self.page_height.saturating_sub(1).max(1)
+    }
+
+    pub fn scroll_up(&mut self, lines: usize) {
+        self.follow = false;
+        self.offset = self.offset.saturating_sub(lines);
+    }
+
+    pub fn scroll_down(&mut self, lines: usize) {
+        self.offset = (self.offset + lines).min(self.max_offset());
+    }
+
+    /// Pauses at `index` of `visible`, clamped so the last page stays full.
+    pub fn pause_at(&mut self, index: usize) {
+        self.follow = false;
+        self.offset = index.min(self.max_offset());
+    }
+
+    pub fn top(&mut self) {
+        self.follow = false;
+        self.offset = 0;
+    }
+
+    pub fn bottom(&mut self) {
+        self.follow = true;
+        self.offset = self.max_offset();
+    }
+
+    pub fn toggle_follow(&mut self) {
+        if self.follow {
+            self.follow = false;
+        } else {
+            self.bottom();
+        }
+    }
+
+    /// The owner refilters afterwards.
+    pub fn toggle_level(&mut self, level: Level) {
+        if !self.levels.remove(&level) {
+            self.levels.insert(level);
+        }
+    }
+
+    /// Steps through `categories`, then back to "all". The owner refilters
+    /// afterwards.
+    pub fn cycle_category(&mut self) {
+        self.category = match &self.category {
+            None => self.categories.first().cloned(),
+            Some(current) => self
+                .categories
+                .iter()
+                .position(|c| c == current)
+                .and_then(|i| self.categories.get(i + 1))
+                .cloned(),
+        };
+    }
+
+    /// `first-last of total` for a view title.
+    pub fn position_label(&self) -> String {
+        format!(
+            "{}-{} of {}",
+            (self.offset + 1).min(self.visible.len()),
+            (self.offset + self.page_height).min(self.visible.len()),
+            self.visible.len()
+        )
+    }
+
+    /// `1[x]debug 2[x]info ...` for a view title.
+    pub fn levels_label(&self) -> String {
+        Level::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, level)| {
+                let mark = if self.levels.contains(level) { "x" } else { " " };
+                format!("{}[{mark}]{}", i + 1, level.label())
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
@@ -0,0 +1,146 @@
+use std::{fs, path::Path};
+
+use serde::Deserialize;
+
+use crate::runtime::RuntimeBackend;
+
+const SETTINGS_PATH: &str = "profiles/dashboard.settings.json";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogColorMode {
+    /// Render the ANSI colours the bot wrote (`modules/order/logger.js`).
+    #[default]
+    Source,
+    /// Ignore source colours and colour by parsed level.
+    Dashboard,
+}
+
+impl LogColorMode {
+    pub fn label(self) -> &'static str {
+        match self {
+            LogColorMode::Source => "source colours",
+            LogColorMode::Dashboard => "level colours",
+        }
+    }
+
+    pub fn toggled(self) -> Self {
+        match self {
+            LogColorMode::Source => LogColorMode::Dashboard,
+            LogColorMode::Dashboard => LogColorMode::Source,
+        }
+    }
+}
+
+/// Dashboard-only tuning knobs, read from `profiles/dashboard.settings.json`.
+/// Every field is optional in the file; missing keys fall back to the defaults below.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct DashboardSettings {
+    /// RMS size divergence (percent) above which a grid raises an alert.
+    /// Mirrors `GRID_LIMITS.GRID_COMPARISON.RMS_PERCENTAGE` in `modules/constants.js`.
+    pub divergence_alert_rms_percent: f64,
+    /// Where bot processes run: `auto`, `pm2`, `docker`, `systemd` or `proc`.
+    pub runtime_backend: RuntimeBackend,
+    /// Unit pattern passed to `systemctl show` by the systemd backend.
+    pub systemd_unit_pattern: String,
+    /// Memory growth (percent) within the watch window that raises an alert.
+    pub memory_growth_alert_percent: f64,
+    /// How long (seconds) restart events and memory samples are kept for alerting.
+    pub process_watch_window_secs: u64,
+    /// Seconds between credential daemon probes.
+    pub cred_probe_interval_secs: u64,
+    /// Reply timeout for the probe; matches the bots' 5s `getPrivateKeyFromDaemon` timeout.
+    pub cred_probe_timeout_ms: u64,
+    /// Initial log colouring; `a` toggles it in the log viewer.
+    pub log_colors: LogColorMode,
+    /// Market distance (percent) from the grid centre that raises an alert.
+    pub market_drift_alert_percent: f64,
+    /// Multiple of `targetSpreadPercent` above which a bot's spread counts as too wide.
+    pub spread_wide_alert_factor: f64,
+    /// How long (seconds) the spread must stay too wide before it raises an alert.
+    pub spread_wide_alert_secs: u64,
+    /// PARTIAL slots holding less than this percent of their ideal size are dust.
+    /// Mirrors `PARTIAL_DUST_THRESHOLD_PERCENTAGE` in `modules/constants.js`.
+    pub dust_threshold_percent: f64,
+    /// Fees above this percentage of proceeds over 24h raise an alert.
+    pub fee_proceeds_alert_percent: f64,
+    /// How long (seconds) `btsFeesOwed` may keep rising without settlement before alerting.
+    pub fees_owed_growth_alert_secs: u64,
+    /// Asset (symbol or id) the Exposure tab values holdings in; `c` cycles it.
+    pub exposure_reference_asset: String,
+    /// Newest `profiles/backups` archives kept regardless of age.
+    pub backup_retention_count: usize,
+    /// Archives beyond `backup_retention_count` are pruned after this many days.
+    pub backup_retention_days: u64,
+}
+
+impl Default for DashboardSettings {
+    fn default() -> Self {
+        Self {
+            divergence_alert_rms_percent: 14.3,
+            runtime_backend: RuntimeBackend::Auto,
+            systemd_unit_pattern: String::from("dexbot*"),
+            memory_growth_alert_percent: 50.0,
+            process_watch_window_secs: 600,
+            cred_probe_interval_secs: 15,
+            cred_probe_timeout_ms: 5000,
+            log_colors: LogColorMode::Source,
+            market_drift_alert_percent: 10.0,
+            spread_wide_alert_factor: 2.0,
+            spread_wide_alert_secs: 300,
+            dust_threshold_percent: 5.0,
+            fee_proceeds_alert_percent: 5.0,
+            fees_owed_growth_alert_secs: 3600,
+            exposure_reference_asset: String::from("BTS"),
+            backup_retention_count: 20,
+            backup_retention_days: 30,
+        }
+    }
+}
+
+/// Settings plus an alert when the file exists but cannot be used, since falling
+/// back to the defaults silently would change every threshold.
+pub fn load_settings() -> (DashboardSettings, Vec<String>) {
+    load_settings_from(Path::new(SETTINGS_PATH))
+}
+
+fn load_settings_from(path: &Path) -> (DashboardSettings, Vec<String>) {
+    let Ok(raw) = fs::read_to_string(path) else {
+        return (DashboardSettings::default(), Vec::new());
+    };
+    match serde_json::from_str(&raw) {
+        Ok(settings) => (settings, Vec::new()),
+        Err(err) => (
+            DashboardSettings::default(),
+            vec![format!("{} ignored, using defaults: {err}", path.display())],
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutil::ScratchDir;
+
+    #[test]
+    fn unusable_file_falls_back_with_an_alert() {
+        let dir = ScratchDir::new("settings");
+        let path = dir.join("dashboard.settings.json");
+        let (settings, alerts) = load_settings_from(&path);
+        assert_eq!(settings.dust_threshold_percent, 5.0);
+        assert!(alerts.is_empty());
+
+        fs::write(&path, r#"{"dustThresholdPercent": 2.5}"#).unwrap();
+        let (settings, alerts) = load_settings_from(&path);
+        assert_eq!(settings.dust_threshold_percent, 2.5);
+        assert_eq!(settings.backup_retention_count, 20);
+        assert!(alerts.is_empty());
+
+        fs::write(&path, r#"{"dustThresholdPercent": "2.5%"}"#).unwrap();
+        let (settings, alerts) = load_settings_from(&path);
+        assert_eq!(settings.dust_threshold_percent, 5.0);
+        assert_eq!(alerts.len(), 1);
+        assert!(alerts[0].contains("ignored, using defaults: invalid type"), "{}", alerts[0]);
+    }
+}
@@ -0,0 +1,175 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+use crate::{grid::Side, state::{BotStatus, PersistedSlot}};
+
+/// Changes kept per bot for the history line.
+const MAX_HISTORY: usize = 12;
+
+/// Gap between the best on-chain orders of a persisted grid.
+#[derive(Clone, Copy, Debug)]
+pub struct SpreadReading {
+    pub best_buy: f64,
+    pub best_sell: f64,
+    pub percent: f64,
+}
+
+/// `Grid.calculateCurrentSpread`: ACTIVE/PARTIAL orders with a size, spread =
+/// (best sell / best buy - 1) * 100.
+pub fn measure(grid: &[PersistedSlot]) -> Option<SpreadReading> {
+    let on_chain = |side: Side| {
+        grid.iter()
+            .filter(move |s| s.side() == Some(side) && s.size > 0.0 && s.price > 0.0)
+            .filter(|s| matches!(s.state.as_deref(), Some("active" | "partial")))
+            .map(|s| s.price)
+    };
+    let best_buy = on_chain(Side::Buy).reduce(f64::max)?;
+    let best_sell = on_chain(Side::Sell).reduce(f64::min)?;
+    Some(SpreadReading {
+        best_buy,
+        best_sell,
+        percent: (best_sell / best_buy - 1.0) * 100.0,
+    })
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SpreadLimits {
+    /// Multiple of `targetSpreadPercent` that counts as too wide.
+    pub wide_factor: f64,
+    /// How long the spread must stay too wide before alerting.
+    pub wide_after: Duration,
+}
+
+/// Cross-poll spread history. The persisted grid only changes on fills and
+/// rebalances, so only changes are recorded, with the wall-clock time they were seen.
+#[derive(Debug, Default)]
+pub struct SpreadTracker {
+    bots: HashMap<String, TrackedSpread>,
+}
+
+#[derive(Debug, Default)]
+struct TrackedSpread {
+    /// `(epoch ms, spread %)`, oldest first.
+    history: VecDeque<(i64, f64)>,
+    wide_since: Option<Instant>,
+}
+
+impl SpreadTracker {
+    /// Records one poll of every active bot and returns the alerts that apply.
+    pub fn observe(
+        &mut self,
+        bots: &[BotStatus],
+        limits: SpreadLimits,
+        now: Instant,
+        now_ms: i64,
+    ) -> Vec<String> {
+        self.bots
+            .retain(|name, _| bots.iter().any(|b| b.active && b.name == *name));
+        bots.iter()
+            .filter(|b| b.active)
+            .filter_map(|bot| {
+                self.observe_bot(
+                    &bot.name,
+                    measure(&bot.grid).map(|r| r.percent),
+                    bot.config.target_spread_percent(),
+                    bot.config.increment_percent(),
+                    limits,
+                    now,
+                    now_ms,
+                )
+            })
+            .collect()
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn observe_bot(
+        &mut self,
+        name: &str,
+        percent: Option<f64>,
+        target: f64,
+        increment: f64,
+        limits: SpreadLimits,
+        now: Instant,
+        now_ms: i64,
+    ) -> Option<String> {
+        let tracked = self.bots.entry(name.to_string()).or_default();
+        let Some(percent) = percent else {
+            tracked.wide_since = None;
+            return None;
+        };
+        if tracked
+            .history
+            .back()
+            .is_none_or(|(_, last)| (last - percent).abs() > 1e-9)
+        {
+            tracked.history.push_back((now_ms, percent));
+            if tracked.history.len() > MAX_HISTORY {
+                tracked.history.pop_front();
+            }
+        }
+
+        let wide_limit = target * limits.wide_factor;
+        if target > 0.0 && percent > wide_limit {
+            let since = *tracked.wide_since.get_or_insert(now);
+            let elapsed = now.duration_since(since);
+            if elapsed >= limits.wide_after {
+                return Some(format!(
+                    "{name}: spread {percent:.2}% above {:.1}x target {target:.2}% for {}s; no fills or a stuck rebalance?",
+                    limits.wide_factor,
+                    elapsed.as_secs()
+                ));
+            }
+        } else {
+            tracked.wide_since = None;
+        }
+        (percent < increment).then(|| {
+            format!(
+                "{name}: spread {percent:.2}% below incrementPercent {increment:.2}%; buy and sell orders may cross."
+            )
+        })
+    }
+
+    /// Recorded changes for a bot, oldest first.
+    pub fn history(&self, name: &str) -> Vec<(i64, f64)> {
+        self.bots
+            .get(name)
+            .map(|t| t.history.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn wide_since(&self, name: &str) -> Option<Instant> {
+        self.bots.get(name).and_then(|t| t.wide_since)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracker_alerts_on_sustained_wide_and_narrow_spreads() {
+        let limits = SpreadLimits {
+            wide_factor: 2.0,
+            wide_after: Duration::from_secs(300),
+        };
+        let mut tracker = SpreadTracker::default();
+        let t0 = Instant::now();
+        let mut poll = |percent, secs| {
+            tracker.observe_bot("bts", Some(percent), 1.0, 0.4, limits, t0 + Duration::from_secs(secs), secs as i64)
+        };
+        assert!(poll(1.1, 0).is_none());
+        // Too wide, but not for long enough yet.
+        assert!(poll(2.5, 10).is_none());
+        assert!(poll(2.5, 200).is_none());
+        assert!(poll(2.6, 310).unwrap().contains("above 2.0x target"));
+        // Back in range resets the timer.
+        assert!(poll(1.0, 320).is_none());
+        assert!(poll(2.5, 330).is_none());
+        assert!(poll(0.3, 340).unwrap().contains("may cross"));
+        let history = tracker.history("bts");
+        assert_eq!(history.len(), 6);
+        assert_eq!(history.last(), Some(&(340, 0.3)));
+    }
+}
@@ -2,29 +2,121 @@ use std::{
     collections::HashMap,
     fs,
     path::{Path, PathBuf},
-    process::Command,
+    sync::Arc,
 };
 
 use anyhow::Result;
 use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{
+    accounts::{self, AccountSummary, ChainTotals},
+    consistency::{self, ConsistencyReport},
+    divergence::{self, GridDivergence},
+    exposure::{self, Exposure},
+    grid::{GridConfig, Side},
+    identity::{self, BotIdentity},
+    infra::{self, Infrastructure},
+    market::{self, AssetRef, MarketCache, MarketFile, MarketGauge},
+    runtime::{ProcessRecord, RuntimeSelector, RuntimeStatus},
+    settings::{self, DashboardSettings},
+    trend::{TrendCache, TrendState},
+};
 
 #[derive(Debug, Clone)]
 pub struct Snapshot {
     pub bots: Vec<BotStatus>,
     pub warnings: usize,
-    pub pm2_online: bool,
-    pub pm2_processes: usize,
+    pub runtime: RuntimeStatus,
+    pub infra: Infrastructure,
+    pub accounts: Vec<AccountSummary>,
+    pub exposure: Exposure,
+    pub consistency: ConsistencyReport,
+    /// `market_adapter/data` price histories, shared with `MarketCache`.
+    pub market_files: Vec<Arc<MarketFile>>,
+    /// Epoch ms of the last `profiles/bots.json` write.
+    pub bots_json_modified_ms: Option<i64>,
     pub alerts: Vec<String>,
+    pub settings: DashboardSettings,
 }
 
 #[derive(Debug, Clone)]
 pub struct BotStatus {
     pub name: String,
+    pub identity: BotIdentity,
     pub pair: String,
     pub active: bool,
+    /// `preferredAccount` of the bots.json entry.
+    pub account: Option<String>,
     pub runtime_status: String,
-    pub log_path: Option<String>,
+    pub process: Option<ProcessRecord>,
+    /// System processes that are down and keep this bot from starting.
+    pub blocked_by: Vec<&'static str>,
     pub log_tail: Vec<String>,
+    pub chain_totals: Option<ChainTotals>,
+    pub config: GridConfig,
+    /// The raw `bots.json` entry, for change detection.
+    pub entry: Value,
+    /// Slots of the persisted orders file; empty when there is none.
+    pub grid: Vec<PersistedSlot>,
+    /// `btsFeesOwed` of the orders file: BTS fees accrued but not yet settled.
+    pub bts_fees_owed: Option<f64>,
+    /// Persisted spread centre, else a numeric `startPrice`.
+    pub grid_center: Option<f64>,
+    pub asset_a: AssetRef,
+    pub asset_b: AssetRef,
+    pub divergence: Result<GridDivergence, String>,
+    pub market: Result<MarketGauge, String>,
+    pub trend: Result<TrendState, String>,
+}
+
+/// One bot entry of `profiles/orders/{botKey}.json` (see `modules/account_orders.js`).
+#[derive(Debug, Clone, Deserialize)]
+pub struct PersistedBot {
+    #[serde(default)]
+    pub grid: Vec<PersistedSlot>,
+    #[serde(default)]
+    pub assets: Option<PersistedAssets>,
+    #[serde(default, rename = "btsFeesOwed")]
+    pub bts_fees_owed: Option<f64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PersistedAssets {
+    #[serde(rename = "assetA")]
+    pub asset_a: AssetRef,
+    #[serde(rename = "assetB")]
+    pub asset_b: AssetRef,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PersistedSlot {
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default, rename = "type")]
+    pub slot_type: Option<String>,
+    #[serde(default)]
+    pub state: Option<String>,
+    #[serde(default)]
+    pub price: f64,
+    #[serde(default)]
+    pub size: f64,
+}
+
+impl PersistedSlot {
+    pub fn side(&self) -> Option<Side> {
+        self.slot_type.as_deref().and_then(Side::parse)
+    }
+
+    pub fn label(&self) -> String {
+        self.id.clone().unwrap_or_else(|| String::from("?"))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct OrdersFile {
+    #[serde(default)]
+    bots: HashMap<String, PersistedBot>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -43,26 +135,66 @@ struct BotEntry {
     #[serde(rename = "assetB")]
     asset_b: String,
     active: Option<bool>,
+    #[serde(default, rename = "preferredAccount")]
+    preferred_account: Option<String>,
+    #[serde(flatten)]
+    grid: GridConfig,
+}
+
+/// State kept between snapshots so each poll only redoes what changed.
+#[derive(Debug, Default)]
+pub struct SnapshotCache {
+    pub runtime: RuntimeSelector,
+    pub market: MarketCache,
+    pub trend: TrendCache,
 }
 
-pub fn load_snapshot() -> Result<Snapshot> {
+pub fn load_snapshot(cache: &mut SnapshotCache) -> Result<Snapshot> {
     let bots_path = PathBuf::from("profiles/bots.json");
+    let (settings, settings_alerts) = settings::load_settings();
     let mut bots = Vec::new();
-    let mut warnings = 0;
-    let mut alerts = Vec::new();
+    let mut warnings = settings_alerts.len();
+    let mut alerts = settings_alerts;
 
-    let (pm2_map, pm2_online) = load_pm2_status();
-    let pm2_processes = pm2_map.len();
-    if !pm2_online {
+    let runtime = cache
+        .runtime
+        .load(settings.runtime_backend, &settings.systemd_unit_pattern);
+    if !runtime.online {
         warnings += 1;
-        alerts.push(String::from("PM2 unavailable (pm2 jlist failed or not installed)."));
+        alerts.push(format!(
+            "Runtime backend {} unavailable (command failed or not installed).",
+            runtime.backend
+        ));
     }
+    let (infra, infra_alerts) = infra::load_infrastructure(&runtime);
+    warnings += infra_alerts.len();
+    alerts.extend(infra_alerts);
+    let blockers = infra.blockers();
+    let market_files = cache.market.load();
 
     if bots_path.exists() {
         let raw = fs::read_to_string(&bots_path)?;
         let parsed: BotsFile = serde_json::from_str(&raw).unwrap_or_else(|_| BotsFile { bots: vec![] });
+        let raw_entries = serde_json::from_str::<Value>(&raw)
+            .ok()
+            .and_then(|v| v.get("bots").and_then(Value::as_array).cloned())
+            .unwrap_or_default();
 
-        for (index, entry) in parsed.bots.iter().enumerate() {
+        let refs = parsed
+            .bots
+            .iter()
+            .map(|entry| identity::EntryRef {
+                name: entry.name.as_deref(),
+                asset_a: &entry.asset_a,
+                asset_b: &entry.asset_b,
+                active: entry.active.unwrap_or(true),
+            })
+            .collect::<Vec<_>>();
+        let (identities, identity_alerts) = identity::resolve(&refs, &runtime);
+        warnings += identity_alerts.len();
+        alerts.extend(identity_alerts);
+
+        for ((index, entry), identity) in parsed.bots.iter().enumerate().zip(identities) {
             let name = entry
                 .name
                 .clone()
@@ -75,32 +207,120 @@ pub fn load_snapshot() -> Result<Snapshot> {
             };
 
             let active = entry.active.unwrap_or(true);
-            let runtime_status = if let Some(status) = pm2_map.get(&name) {
-                status.clone()
+            let process = identity
+                .process_name
+                .as_ref()
+                .and_then(|p| runtime.processes.get(p))
+                .cloned();
+            let runtime_status = if let Some(process) = &process {
+                process.status.clone()
             } else if active {
                 String::from("not-running")
             } else {
                 String::from("disabled")
             };
 
-            let log_path = resolve_bot_log_path(&name);
-            let log_tail = log_path
+            let blocked_by = if active && runtime_status != "online" {
+                blockers.clone()
+            } else {
+                vec![]
+            };
+
+            let log_tail = identity
+                .out_log
                 .as_ref()
-                .map(|p| tail_lines(Path::new(p), 10))
+                .map(|p| tail_lines(p, 10))
                 .unwrap_or_default();
 
+            let chain_totals = identity
+                .out_log
+                .as_ref()
+                .and_then(|p| accounts::read_chain_totals(p));
+
             if log_tail.iter().any(|line| has_error_marker(line)) {
                 warnings += 1;
                 alerts.push(format!("{name}: error/warn marker found in recent log lines."));
             }
 
+            let persisted = load_persisted_bot(&identity.bot_key);
+            let divergence = match &persisted {
+                Some(p) => divergence::compute(&entry.grid, &p.grid),
+                None => Err(String::from("no orders file")),
+            };
+            if let Ok(div) = &divergence {
+                if div.rms_percent > settings.divergence_alert_rms_percent {
+                    warnings += 1;
+                    alerts.push(format!(
+                        "{name}: grid divergence {:.1}% RMS exceeds {:.1}% threshold.",
+                        div.rms_percent, settings.divergence_alert_rms_percent
+                    ));
+                }
+            }
+
+            let (asset_a, asset_b) = match persisted.as_ref().and_then(|p| p.assets.clone()) {
+                Some(assets) => (assets.asset_a, assets.asset_b),
+                None => (
+                    AssetRef {
+                        id: String::new(),
+                        symbol: entry.asset_a.clone(),
+                    },
+                    AssetRef {
+                        id: String::new(),
+                        symbol: entry.asset_b.clone(),
+                    },
+                ),
+            };
+            let grid_center = persisted
+                .as_ref()
+                .and_then(|p| divergence::spread_center(&p.grid))
+                .or_else(|| entry.grid.start_price());
+            let series = market::find_series(&market_files, &asset_a, &asset_b);
+            let market = match (&series, grid_center) {
+                (None, _) => Err(String::from("no market_adapter data for pair")),
+                (Some(_), None) => Err(String::from("no grid centre (no persisted spread, startPrice not numeric)")),
+                (Some(series), Some(center)) => market::gauge(series, &entry.grid, center),
+            };
+            let trend = match &series {
+                Some(series) => cache.trend.analyze(series),
+                None => Err(String::from("no market_adapter data for pair")),
+            };
+            if let Ok(gauge) = &market {
+                if !gauge.in_range() {
+                    warnings += 1;
+                    alerts.push(format!(
+                        "{name}: market {:.6} outside configured range [{:.6}, {:.6}]; grid cannot trade.",
+                        gauge.price, gauge.min_price, gauge.max_price
+                    ));
+                } else if gauge.drift_percent.abs() > settings.market_drift_alert_percent {
+                    warnings += 1;
+                    alerts.push(format!(
+                        "{name}: market {:+.1}% from grid centre (threshold {:.1}%).",
+                        gauge.drift_percent, settings.market_drift_alert_percent
+                    ));
+                }
+            }
+
             bots.push(BotStatus {
                 name,
+                identity,
                 pair,
                 active,
+                account: entry.preferred_account.clone().filter(|a| !a.is_empty()),
                 runtime_status,
-                log_path,
+                process,
+                blocked_by,
                 log_tail,
+                chain_totals,
+                config: entry.grid.clone(),
+                entry: raw_entries.get(index).cloned().unwrap_or(Value::Null),
+                bts_fees_owed: persisted.as_ref().and_then(|p| p.bts_fees_owed),
+                grid: persisted.map(|p| p.grid).unwrap_or_default(),
+                grid_center,
+                asset_a,
+                asset_b,
+                divergence,
+                market,
+                trend,
             });
         }
     } else {
@@ -108,93 +328,52 @@ pub fn load_snapshot() -> Result<Snapshot> {
         alerts.push(String::from("profiles/bots.json not found."));
     }
 
+    let blocked = bots
+        .iter()
+        .filter(|b| !b.blocked_by.is_empty())
+        .map(|b| b.name.as_str())
+        .collect::<Vec<_>>();
+    if !blocked.is_empty() {
+        warnings += 1;
+        alerts.push(format!(
+            "Blocked by {}: {}.",
+            blockers.join(", "),
+            blocked.join(", ")
+        ));
+    }
+
+    let (accounts, account_alerts) = accounts::summarize(&bots);
+    warnings += account_alerts.len();
+    alerts.extend(account_alerts);
+    cache.trend.retain(&market_files);
+    let exposure = exposure::summarize(&bots, &market_files);
+    let consistency = consistency::check(&bots, &runtime);
+    let bots_json_modified_ms = fs::metadata(&bots_path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as i64);
+
     Ok(Snapshot {
         bots,
         warnings,
-        pm2_online,
-        pm2_processes,
+        runtime,
+        infra,
+        accounts,
+        exposure,
+        consistency,
+        market_files,
+        bots_json_modified_ms,
         alerts,
+        settings,
     })
 }
 
-fn load_pm2_status() -> (HashMap<String, String>, bool) {
-    let output = Command::new("pm2").arg("jlist").output();
-    let Ok(output) = output else {
-        return (HashMap::new(), false);
-    };
-    if !output.status.success() {
-        return (HashMap::new(), false);
-    }
-
-    let text = String::from_utf8_lossy(&output.stdout);
-    let json_start = text.find('[').unwrap_or(0);
-    let payload = &text[json_start..];
-    let parsed = serde_json::from_str::<serde_json::Value>(payload);
-    let Ok(value) = parsed else {
-        return (HashMap::new(), false);
-    };
-
-    let Some(items) = value.as_array() else {
-        return (HashMap::new(), false);
-    };
-
-    let mut map = HashMap::new();
-    for item in items {
-        let name = item
-            .get("name")
-            .and_then(|v| v.as_str())
-            .unwrap_or_default()
-            .to_string();
-        if name.is_empty() {
-            continue;
-        }
-        let status = item
-            .get("pm2_env")
-            .and_then(|env| env.get("status"))
-            .and_then(|v| v.as_str())
-            .unwrap_or("unknown")
-            .to_string();
-        map.insert(name, status);
-    }
-    (map, true)
-}
-
-fn resolve_bot_log_path(bot_name: &str) -> Option<String> {
-    let direct = PathBuf::from(format!("profiles/logs/{bot_name}.log"));
-    if direct.exists() {
-        return Some(direct.to_string_lossy().to_string());
-    }
-
-    let logs_dir = PathBuf::from("profiles/logs");
-    if !logs_dir.exists() {
-        return None;
-    }
-
-    let mut candidates = fs::read_dir(logs_dir)
-        .ok()?
-        .filter_map(|entry| entry.ok())
-        .filter(|entry| {
-            entry
-                .path()
-                .extension()
-                .map(|ext| ext == "log")
-                .unwrap_or(false)
-        })
-        .collect::<Vec<_>>();
-
-    candidates.sort_by_key(|entry| entry.metadata().and_then(|m| m.modified()).ok());
-
-    candidates
-        .into_iter()
-        .rev()
-        .find(|entry| {
-            entry
-                .file_name()
-                .to_string_lossy()
-                .to_lowercase()
-                .contains(&bot_name.to_lowercase())
-        })
-        .map(|entry| entry.path().to_string_lossy().to_string())
+fn load_persisted_bot(bot_key: &str) -> Option<PersistedBot> {
+    let path = PathBuf::from(format!("profiles/orders/{bot_key}.json"));
+    let raw = fs::read_to_string(path).ok()?;
+    let mut parsed = serde_json::from_str::<OrdersFile>(&raw).ok()?;
+    parsed.bots.remove(bot_key)
 }
 
 fn tail_lines(path: &Path, max_lines: usize) -> Vec<String> {
@@ -215,3 +394,43 @@ fn has_error_marker(line: &str) -> bool {
     let upper = line.to_ascii_uppercase();
     upper.contains("ERROR") || upper.contains("WARN") || upper.contains("FATAL")
 }
+
+#[cfg(test)]
+impl BotStatus {
+    /// Active entry `name` with key `bot_key`, its process named after it, and no
+    /// runtime record, logs, orders or market data.
+    pub fn stub(name: &str, bot_key: &str) -> Self {
+        let asset = |symbol: &str| AssetRef {
+            id: String::new(),
+            symbol: symbol.to_string(),
+        };
+        Self {
+            name: name.to_string(),
+            identity: BotIdentity {
+                bot_key: bot_key.to_string(),
+                process_name: Some(name.to_string()),
+                out_log: None,
+                error_log: None,
+                log_source: identity::LogSource::Missing,
+            },
+            pair: String::from("BTS/USD"),
+            active: true,
+            account: None,
+            runtime_status: String::from("unknown"),
+            process: None,
+            blocked_by: Vec::new(),
+            log_tail: Vec::new(),
+            chain_totals: None,
+            config: GridConfig::default(),
+            entry: Value::Null,
+            grid: Vec::new(),
+            bts_fees_owed: None,
+            grid_center: None,
+            asset_a: asset("BTS"),
+            asset_b: asset("USD"),
+            divergence: Err(String::new()),
+            market: Err(String::new()),
+            trend: Err(String::new()),
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use std::{collections::HashSet, path::PathBuf};
+
+use crate::{
+    logline::{self, Level, LogLine},
+    scroll::LogScroll,
+};
+
+/// Per-file read budget; the merged window is trimmed to what every file covers.
+const MAX_TAIL_BYTES: u64 = 512 * 1024;
+
+#[derive(Debug, Clone)]
+pub struct TimelineEvent {
+    /// Index into the bot list the timeline was built from.
+    pub bot: usize,
+    pub line: LogLine,
+}
+
+/// Fleet-wide log events merged by timestamp. `view.visible` indexes `events`.
+#[derive(Debug, Default)]
+pub struct TimelineState {
+    pub bots: Vec<String>,
+    pub events: Vec<TimelineEvent>,
+    pub view: LogScroll,
+    /// Oldest timestamp every truncated log still covers; earlier events are dropped
+    /// because a busy bot's tail would otherwise hide a quiet bot's history.
+    pub window_start: Option<String>,
+    pub bot_filter: Option<usize>,
+}
+
+impl TimelineState {
+    /// Rebuilds the merge from `(bot name, log path)` pairs in bot-list order.
+    pub fn rebuild(&mut self, sources: &[(String, Option<PathBuf>)]) {
+        // Merges reorder and the window trims, so a paused view is pinned to the
+        // event itself rather than to its position.
+        let anchor = self.view.anchor().map(|i| {
+            let event = &self.events[i];
+            (self.bots.get(event.bot).cloned(), event.line.clone())
+        });
+        let mut events = Vec::new();
+        let mut window_start: Option<String> = None;
+        for (bot, (_, path)) in sources.iter().enumerate() {
+            let Some(path) = path else {
+                continue;
+            };
+            let truncated = path
+                .metadata()
+                .map(|m| m.len() > MAX_TAIL_BYTES)
+                .unwrap_or(false);
+            let raw = logline::read_tail(path, MAX_TAIL_BYTES);
+            let lines = logline::parse_lines(raw.iter().map(String::as_str));
+            if truncated {
+                if let Some(first) = lines.iter().find_map(|l| l.timestamp.clone()) {
+                    if window_start.as_ref().is_none_or(|w| first > *w) {
+                        window_start = Some(first);
+                    }
+                }
+            }
+            // Lines before the first stamped line cannot be placed in time.
+            events.extend(
+                lines
+                    .into_iter()
+                    .filter(|l| l.timestamp.is_some())
+                    .map(|line| TimelineEvent { bot, line }),
+            );
+        }
+        if let Some(start) = &window_start {
+            events.retain(|e| e.line.timestamp.as_ref().is_some_and(|ts| ts >= start));
+        }
+        // Stable: equal stamps keep bot order, and lines within one file keep file order.
+        events.sort_by(|a, b| a.line.timestamp.cmp(&b.line.timestamp));
+
+        let mut categories = events
+            .iter()
+            .flat_map(|e| e.line.categories.iter().cloned())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        categories.sort();
+
+        let names = sources.iter().map(|(name, _)| name.clone()).collect::<Vec<_>>();
+        if names != self.bots {
+            self.bot_filter = None;
+        }
+        self.bots = names;
+        self.events = events;
+        self.window_start = window_start;
+        self.view.categories = categories;
+        let anchor = anchor.map(|(bot, line)| self.locate(bot.as_deref(), &line));
+        self.refilter(anchor);
+    }
+
+    /// Index of the event matching `line` from `bot`, else of the first event
+    /// stamped at or after it.
+    fn locate(&self, bot: Option<&str>, line: &LogLine) -> usize {
+        let start = self
+            .events
+            .partition_point(|e| e.line.timestamp < line.timestamp);
+        self.events[start..]
+            .iter()
+            .take_while(|e| e.line.timestamp == line.timestamp)
+            .position(|e| self.bots.get(e.bot).map(String::as_str) == bot && e.line.raw == line.raw)
+            .map_or(start, |i| start + i)
+    }
+
+    fn apply_filters(&mut self) {
+        self.refilter(self.view.anchor());
+    }
+
+    fn refilter(&mut self, anchor: Option<usize>) {
+        let visible = self
+            .events
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| self.bot_filter.is_none_or(|b| e.bot == b))
+            .filter(|(_, e)| self.view.passes(&e.line))
+            .map(|(i, _)| i)
+            .collect();
+        self.view.set_visible(visible, anchor);
+    }
+
+    pub fn cycle_bot(&mut self) {
+        self.bot_filter = match self.bot_filter {
+            None if !self.bots.is_empty() => Some(0),
+            Some(b) if b + 1 < self.bots.len() => Some(b + 1),
+            _ => None,
+        };
+        self.apply_filters();
+    }
+
+    pub fn toggle_level(&mut self, level: Level) {
+        self.view.toggle_level(level);
+        self.apply_filters();
+    }
+
+    pub fn cycle_category(&mut self) {
+        self.view.cycle_category();
+        self.apply_filters();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    use crate::testutil::ScratchDir;
+
+    #[test]
+    fn paused_view_stays_on_its_event_when_earlier_lines_merge_in() {
+        let dir = ScratchDir::new("timeline");
+        let (a, b) = (dir.join("a.log"), dir.join("b.log"));
+        let stamped = |minutes: std::ops::Range<u32>| {
+            minutes
+                .map(|m| format!("[2026-01-15T10:{m:02}:00.000Z] [INFO] line {m}\n"))
+                .collect::<String>()
+        };
+        fs::write(&a, stamped(0..40)).unwrap();
+        fs::write(&b, "").unwrap();
+        let sources = vec![
+            (String::from("a"), Some(a.clone())),
+            (String::from("b"), Some(b.clone())),
+        ];
+
+        let mut timeline = TimelineState::default();
+        timeline.view.set_page_height(5);
+        timeline.rebuild(&sources);
+        timeline.view.scroll_up(20);
+        let top = |t: &TimelineState| t.events[t.view.visible[t.view.offset]].line.text.clone();
+        let before = top(&timeline);
+
+        // Bot b logs events stamped before the one on top.
+        fs::write(&b, stamped(0..15)).unwrap();
+        timeline.rebuild(&sources);
+        assert_eq!(top(&timeline), before);
+        assert!(!timeline.view.follow);
+
+        // Filters keep the anchor too, moving to the next line that passes.
+        timeline.cycle_bot();
+        assert_eq!(top(&timeline), before);
+    }
+}
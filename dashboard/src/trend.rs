@@ -0,0 +1,326 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use serde::Deserialize;
+
+use crate::market::{FileStamp, MarketFile, MarketSeries};
+
+const OPTIMIZATION_DIR: &str = "analysis/ama_fitting";
+/// `DualAMA` defaults in `analysis/trend_detection/dual_ama.js`.
+const FAST_AMA: AmaParams = AmaParams {
+    er: 40.0,
+    fast: 5.0,
+    slow: 15.0,
+};
+const SLOW_AMA: AmaParams = AmaParams {
+    er: 20.0,
+    fast: 2.0,
+    slow: 30.0,
+};
+const NEUTRAL_TOLERANCE: f64 = 0.001;
+const MIN_SEPARATION_PERCENT: f64 = 1.0;
+const MIN_BARS_FOR_CONFIRMATION: usize = 3;
+/// `TrendAnalyzer` reports nothing before this many candles.
+const WARMUP_BARS: usize = 50;
+const MAX_CROSSOVERS: usize = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+pub struct AmaParams {
+    pub er: f64,
+    pub fast: f64,
+    pub slow: f64,
+}
+
+/// Kaufman's Adaptive Moving Average, as `analysis/ama_fitting/ama.js`.
+#[derive(Debug)]
+struct Ama {
+    er_period: usize,
+    fast_sc: f64,
+    slow_sc: f64,
+    prev: Option<f64>,
+    history: Vec<f64>,
+}
+
+impl Ama {
+    fn new(params: AmaParams) -> Self {
+        Self {
+            er_period: params.er.max(1.0) as usize,
+            fast_sc: 2.0 / (params.fast + 1.0),
+            slow_sc: 2.0 / (params.slow + 1.0),
+            prev: None,
+            history: Vec::new(),
+        }
+    }
+
+    fn update(&mut self, price: f64) -> f64 {
+        self.history.push(price);
+        if self.history.len() > self.er_period + 1 {
+            self.history.remove(0);
+        }
+        if self.history.len() <= self.er_period {
+            self.prev = Some(price);
+            return price;
+        }
+        let direction = (price - self.history[0]).abs();
+        let volatility = self
+            .history
+            .windows(2)
+            .map(|w| (w[1] - w[0]).abs())
+            .sum::<f64>();
+        let er = if volatility == 0.0 {
+            0.0
+        } else {
+            direction / volatility
+        };
+        let smooth = (er * (self.fast_sc - self.slow_sc) + self.slow_sc).powi(2);
+        let prev = self.prev.unwrap_or(price);
+        let ama = prev + smooth * (price - prev);
+        self.prev = Some(ama);
+        ama
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Trend {
+    Bullish,
+    Bearish,
+    Neutral,
+}
+
+impl Trend {
+    pub fn label(self) -> &'static str {
+        match self {
+            Trend::Bullish => "bullish",
+            Trend::Bearish => "bearish",
+            Trend::Neutral => "neutral",
+        }
+    }
+}
+
+/// Fast AMA crossing the slow one; `to` is the new raw direction.
+#[derive(Clone, Copy, Debug)]
+pub struct Crossover {
+    pub ts_ms: i64,
+    pub to: Trend,
+}
+
+#[derive(Clone, Debug)]
+pub struct TrendState {
+    /// Confirmed trend: raw direction held for 3+ bars with 1%+ separation.
+    pub trend: Trend,
+    pub raw: Trend,
+    pub separation_percent: f64,
+    pub bars_in_trend: usize,
+    /// `dual_ama.js` mapping: 5% separation = 100.
+    pub confidence: u8,
+    pub slow_params: AmaParams,
+    /// Where `slow_params` came from: an optimization results file or the defaults.
+    pub params_source: String,
+    /// Price distance from the slow (centre) AMA.
+    pub price_from_center_percent: f64,
+    /// Newest first.
+    pub crossovers: Vec<Crossover>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OptimizationFile {
+    meta: OptimizationMeta,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OptimizationMeta {
+    best_area_max_dist: Option<AmaParams>,
+}
+
+/// `optimization_results_{data file stem}.json`, as written by
+/// `optimizer_high_resolution.js --data <file>`.
+fn params_path(series: &MarketSeries) -> Option<PathBuf> {
+    let stem = series.file.path.file_stem()?.to_string_lossy().to_string();
+    Some(Path::new(OPTIMIZATION_DIR).join(format!("optimization_results_{stem}.json")))
+}
+
+/// The tuned centre AMA for a market file: `bestAreaMaxDist` of its
+/// optimization results file.
+fn tuned_params(series: &MarketSeries) -> Option<(AmaParams, String)> {
+    let path = params_path(series)?;
+    let raw = fs::read_to_string(&path).ok()?;
+    let parsed = serde_json::from_str::<OptimizationFile>(&raw).ok()?;
+    let params = parsed.meta.best_area_max_dist?;
+    let name = path.file_name()?.to_string_lossy().to_string();
+    Some((params, name))
+}
+
+/// Trend results kept across polls, one per market file and orientation, so bots
+/// on the same pair share one run. An entry is recomputed when `MarketCache`
+/// re-reads its market file or the optimization results file changes.
+#[derive(Debug, Default)]
+pub struct TrendCache {
+    entries: HashMap<(PathBuf, bool), TrendEntry>,
+}
+
+#[derive(Debug)]
+struct TrendEntry {
+    file: Arc<MarketFile>,
+    params: Option<FileStamp>,
+    result: Result<TrendState, String>,
+}
+
+impl TrendCache {
+    pub fn analyze(&mut self, series: &MarketSeries) -> Result<TrendState, String> {
+        let params = params_path(series).and_then(|p| FileStamp::of(&p));
+        let key = (series.file.path.clone(), series.inverted);
+        if let Some(entry) = self.entries.get(&key) {
+            if Arc::ptr_eq(&entry.file, series.file) && entry.params == params {
+                return entry.result.clone();
+            }
+        }
+        let result = analyze(series);
+        self.entries.insert(
+            key,
+            TrendEntry {
+                file: Arc::clone(series.file),
+                params,
+                result: result.clone(),
+            },
+        );
+        result
+    }
+
+    /// Drops entries for market files that are gone or were re-read.
+    pub fn retain(&mut self, files: &[Arc<MarketFile>]) {
+        self.entries
+            .retain(|_, entry| files.iter().any(|f| Arc::ptr_eq(f, &entry.file)));
+    }
+}
+
+/// Runs the dual-AMA system over the series' closes. The fast AMA keeps the
+/// `DualAMA` defaults; the slow (centre) AMA uses the pair's tuned parameters
+/// when an optimization file exists for the data file.
+fn analyze(series: &MarketSeries) -> Result<TrendState, String> {
+    let closes = series.closes();
+    if closes.len() < WARMUP_BARS {
+        return Err(format!("warming up: {}/{WARMUP_BARS} candles", closes.len()));
+    }
+    let (slow_params, params_source) =
+        tuned_params(series).unwrap_or((SLOW_AMA, String::from("dual_ama.js defaults")));
+    let mut fast = Ama::new(FAST_AMA);
+    let mut slow = Ama::new(slow_params);
+
+    let mut raw = Trend::Neutral;
+    let mut prev_raw: Option<Trend> = None;
+    let mut bars_in_trend = 0;
+    let mut last_direction: Option<Trend> = None;
+    let mut crossovers = Vec::new();
+    let mut separation_percent = 0.0;
+    let mut slow_value = 0.0;
+    for (ts_ms, price) in &closes {
+        let fast_value = fast.update(*price);
+        slow_value = slow.update(*price);
+        separation_percent = if slow_value == 0.0 {
+            0.0
+        } else {
+            (fast_value - slow_value).abs() / slow_value.abs() * 100.0
+        };
+        raw = if (fast_value - slow_value).abs() < slow_value.abs() * NEUTRAL_TOLERANCE {
+            Trend::Neutral
+        } else if fast_value > slow_value {
+            Trend::Bullish
+        } else {
+            Trend::Bearish
+        };
+        if prev_raw == Some(raw) {
+            bars_in_trend += 1;
+        } else {
+            prev_raw = Some(raw);
+            bars_in_trend = 1;
+        }
+        if raw != Trend::Neutral {
+            if last_direction.is_some_and(|d| d != raw) {
+                crossovers.push(Crossover {
+                    ts_ms: *ts_ms,
+                    to: raw,
+                });
+            }
+            last_direction = Some(raw);
+        }
+    }
+
+    let confirmed = raw != Trend::Neutral
+        && separation_percent >= MIN_SEPARATION_PERCENT
+        && bars_in_trend >= MIN_BARS_FOR_CONFIRMATION;
+    let confidence = if raw == Trend::Neutral {
+        0.0
+    } else {
+        (separation_percent / 5.0 * 100.0).min(100.0)
+    };
+    let (_, price) = closes[closes.len() - 1];
+    crossovers.reverse();
+    crossovers.truncate(MAX_CROSSOVERS);
+    Ok(TrendState {
+        trend: if confirmed { raw } else { Trend::Neutral },
+        raw,
+        separation_percent,
+        bars_in_trend,
+        confidence: confidence.round() as u8,
+        slow_params,
+        params_source,
+        price_from_center_percent: if slow_value == 0.0 {
+            0.0
+        } else {
+            (price - slow_value) / slow_value * 100.0
+        },
+        crossovers,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::market::{AssetRef, Candle};
+
+    fn file(closes: impl Iterator<Item = f64>) -> Arc<MarketFile> {
+        let asset = |symbol: &str| AssetRef {
+            id: String::new(),
+            symbol: symbol.to_string(),
+        };
+        Arc::new(MarketFile {
+            path: PathBuf::from("market_adapter/data/test_pair_1h.json"),
+            asset_a: asset("XRP"),
+            asset_b: asset("BTS"),
+            interval_seconds: 3600,
+            candles: closes
+                .enumerate()
+                .map(|(i, close)| Candle {
+                    ts_ms: i as i64 * 3_600_000,
+                    close,
+                })
+                .collect(),
+        })
+    }
+
+    #[test]
+    fn cache_shares_a_run_until_the_file_is_reread() {
+        let rising = file((0..80).map(|i| 1.0 + i as f64 * 0.01));
+        let series = |file, inverted| MarketSeries { file, inverted };
+        let mut cache = TrendCache::default();
+
+        let up = cache.analyze(&series(&rising, false)).unwrap().raw;
+        assert_ne!(up, Trend::Neutral);
+        cache.analyze(&series(&rising, false)).unwrap();
+        assert_eq!(cache.entries.len(), 1);
+        // The other orientation is its own run.
+        assert_ne!(cache.analyze(&series(&rising, true)).unwrap().raw, up);
+        assert_eq!(cache.entries.len(), 2);
+
+        // A re-read file replaces the entry under the same path.
+        let falling = file((0..80).map(|i| 2.0 - i as f64 * 0.01));
+        assert_ne!(cache.analyze(&series(&falling, false)).unwrap().raw, up);
+        cache.retain(std::slice::from_ref(&falling));
+        assert_eq!(cache.entries.len(), 1);
+    }
+}
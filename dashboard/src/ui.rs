@@ -1,12 +1,38 @@
+mod accounts;
+mod analysis;
+mod backups;
+mod chart;
+mod consistency;
+mod cycles;
+mod exposure;
+mod fees;
+mod grid;
+mod preview;
+mod system;
+mod timeline;
+
+use std::ops::Range;
+
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
+    text::Line,
     widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Tabs, Wrap},
     Frame,
 };
 
 use crate::{
+    ansi,
     app::{App, PendingAction, Tab},
+    dryrun,
+    identity,
+    logline::{self, Level, LogLine},
+    logview::LogInput,
+    restart::ConfigState,
+    runtime,
+    settings::LogColorMode,
+    spread,
+    trend::Trend,
 };
 
 pub fn render(frame: &mut Frame, app: &mut App) {
@@ -19,6 +45,15 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         ])
         .split(frame.size());
 
+    if app.log_view.is_some() {
+        render_tabs(frame, app, chunks[0]);
+        let area = Rect {
+            height: frame.size().height.saturating_sub(chunks[0].height),
+            ..chunks[1]
+        };
+        return render_log_view(frame, app, area);
+    }
+
     render_tabs(frame, app, chunks[0]);
     render_main(frame, app, chunks[1]);
     render_bottom(frame, app, chunks[2]);
@@ -28,29 +63,80 @@ pub fn render(frame: &mut Frame, app: &mut App) {
     }
 }
 
-fn render_tabs(frame: &mut Frame, app: &App, area: Rect) {
-    let titles = [Tab::Overview, Tab::BotDetail, Tab::Scripts, Tab::Alerts]
-        .iter()
-        .map(|tab| tab.title())
-        .collect::<Vec<_>>();
+/// Cells one tab title takes in `Tabs`: the title, a space either side and the divider.
+fn tab_cells(title: &str) -> usize {
+    title.chars().count() + 3
+}
 
-    let selected = match app.tab {
-        Tab::Overview => 0,
-        Tab::BotDetail => 1,
-        Tab::Scripts => 2,
-        Tab::Alerts => 3,
-    };
+/// The run of tabs around `selected` that fits in `width` cells. When some tabs
+/// are left out, room is kept for the `«`/`»` markers that stand in for them.
+fn tab_window(titles: &[&str], selected: usize, width: usize) -> Range<usize> {
+    let cells = titles.iter().map(|t| tab_cells(t)).collect::<Vec<_>>();
+    if cells.iter().sum::<usize>() <= width + 1 {
+        return 0..titles.len();
+    }
+    let budget = (width + 1).saturating_sub(2 * tab_cells("«"));
+    let (mut start, mut end) = (selected, selected + 1);
+    let mut used = cells[selected];
+    loop {
+        let mut grew = false;
+        if end < titles.len() && used + cells[end] <= budget {
+            used += cells[end];
+            end += 1;
+            grew = true;
+        }
+        if start > 0 && used + cells[start - 1] <= budget {
+            start -= 1;
+            used += cells[start];
+            grew = true;
+        }
+        if !grew {
+            return start..end;
+        }
+    }
+}
 
-    let status = if app.snapshot.pm2_online {
+fn render_tabs(frame: &mut Frame, app: &App, area: Rect) {
+    let all = Tab::ALL.iter().map(|tab| tab.title()).collect::<Vec<_>>();
+    let window = tab_window(&all, app.tab.index(), area.width.saturating_sub(2) as usize);
+    let mut titles = Vec::new();
+    if window.start > 0 {
+        titles.push("«");
+    }
+    let selected = titles.len() + app.tab.index() - window.start;
+    titles.extend_from_slice(&all[window.clone()]);
+    if window.end < all.len() {
+        titles.push("»");
+    }
+
+    let runtime = &app.snapshot.runtime;
+    let status = if runtime.online {
+        let system = runtime
+            .processes
+            .keys()
+            .filter(|name| identity::SYSTEM_PROCESSES.contains(&name.as_str()))
+            .count();
         format!(
-            "PM2 online | processes: {} | alerts: {}",
-            app.snapshot.pm2_processes,
+            "{} online | bots: {} (+{} system) | alerts: {}",
+            runtime.backend,
+            runtime.processes.len() - system,
+            system,
             app.snapshot.alerts.len()
         )
     } else {
-        format!("PM2 offline | alerts: {}", app.snapshot.alerts.len())
+        format!(
+            "{} offline | alerts: {}",
+            runtime.backend,
+            app.snapshot.alerts.len()
+        )
     };
 
+    let cred = match &app.cred_probe.latest {
+        Some(probe) => format!("cred: {}", probe.health.label()),
+        None => String::from("cred: probing"),
+    };
+    let status = format!("{status} | {cred}");
+
     let tabs = Tabs::new(titles)
         .block(
             Block::default()
@@ -65,6 +151,22 @@ fn render_tabs(frame: &mut Frame, app: &App, area: Rect) {
 }
 
 fn render_main(frame: &mut Frame, app: &mut App, area: Rect) {
+    match app.tab {
+        Tab::Grid => return grid::render(frame, app, area),
+        Tab::Chart => return chart::render(frame, app, area),
+        Tab::Cycles => return cycles::render(frame, app, area),
+        Tab::Fees => return fees::render(frame, app, area),
+        Tab::Preview => return preview::render(frame, app, area),
+        Tab::Analysis => return analysis::render(frame, app, area),
+        Tab::System => return system::render(frame, app, area),
+        Tab::Consistency => return consistency::render(frame, app, area),
+        Tab::Backups => return backups::render(frame, app, area),
+        Tab::Accounts => return accounts::render(frame, app, area),
+        Tab::Exposure => return exposure::render(frame, app, area),
+        Tab::Timeline => return timeline::render(frame, app, area),
+        _ => {}
+    }
+
     let columns = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
@@ -74,15 +176,63 @@ fn render_main(frame: &mut Frame, app: &mut App, area: Rect) {
         ])
         .split(area);
 
+    render_bot_list(frame, app, columns[0]);
+    render_bot_detail_and_actions(frame, app, &columns);
+}
+
+fn render_bot_list(frame: &mut Frame, app: &mut App, area: Rect) {
+    let now_ms = runtime::now_ms();
     let bot_items = app
         .snapshot
         .bots
         .iter()
         .map(|bot| {
             let cfg = if bot.active { "active" } else { "inactive" };
+            let div = match &bot.divergence {
+                Ok(d) => format!("{:.1}%", d.rms_percent),
+                Err(_) => String::from("-"),
+            };
+            let proc = match &bot.process {
+                Some(p) => format!(
+                    " cpu:{} mem:{} up:{} r:{}",
+                    p.cpu_percent
+                        .map(|c| format!("{c:.0}%"))
+                        .unwrap_or_else(|| String::from("-")),
+                    p.memory_bytes
+                        .map(runtime::format_bytes)
+                        .unwrap_or_else(|| String::from("-")),
+                    p.uptime(now_ms)
+                        .map(runtime::format_duration)
+                        .unwrap_or_else(|| String::from("-")),
+                    p.restarts
+                        .map(|r| r.to_string())
+                        .unwrap_or_else(|| String::from("-")),
+                ),
+                None => String::new(),
+            };
+            let blocked = if bot.blocked_by.is_empty() {
+                String::new()
+            } else {
+                format!(" BLOCKED({})", bot.blocked_by.join(","))
+            };
+            let mkt = match &bot.market {
+                Ok(g) if !g.in_range() => String::from(" mkt:OUT"),
+                Ok(g) => format!(" mkt:{:+.1}%", g.drift_percent),
+                Err(_) => String::new(),
+            };
+            let trend = match &bot.trend {
+                Ok(t) => format!(" trend:{}", t.trend.label()),
+                Err(_) => String::new(),
+            };
+            let (partial, dust) = app.partial_tracker.counts(&bot.name);
+            let restart = match app.restart_tracker.state(&bot.name) {
+                Some(ConfigState::RestartRequired(_)) => " RESTART-REQUIRED",
+                Some(ConfigState::Unknown) => " RESTART?",
+                _ => "",
+            };
             ListItem::new(format!(
-                "{} [{}|{}] {}",
-                bot.name, bot.runtime_status, cfg, bot.pair
+                "{} [{}|{}]{restart} {} div:{} part:{partial}/dust:{dust}{}{}{}{}",
+                bot.name, bot.runtime_status, cfg, bot.pair, div, mkt, trend, proc, blocked
             ))
         })
         .collect::<Vec<_>>();
@@ -91,31 +241,161 @@ fn render_main(frame: &mut Frame, app: &mut App, area: Rect) {
         .block(Block::default().borders(Borders::ALL).title("Bots"))
         .highlight_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
         .highlight_symbol("-> ");
-    frame.render_stateful_widget(bot_list, columns[0], &mut app.bot_list_state);
+    frame.render_stateful_widget(bot_list, area, &mut app.bot_list_state);
+}
 
+fn render_bot_detail_and_actions(frame: &mut Frame, app: &mut App, columns: &[Rect]) {
     let detail_text = if let Some(bot) = app.selected_bot() {
         let log_path = bot
-            .log_path
+            .identity
+            .out_log
+            .as_ref()
+            .map(|p| format!("{} [{}]", p.display(), bot.identity.log_source.label()))
+            .unwrap_or_else(|| String::from("(no log file)"));
+        let error_log = bot
+            .identity
+            .error_log
             .as_ref()
-            .map(|p| p.as_str())
-            .unwrap_or("(no log file)");
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| String::from("-"));
+        let process_name = bot.identity.process_name.as_deref().unwrap_or("(unresolved)");
         let alert_hint = if app.snapshot.alerts.is_empty() {
             String::from("No active alerts")
         } else {
-            format!("{}", app.snapshot.alerts[0])
+            app.snapshot.alerts[0].to_string()
+        };
+        let process = bot.process.as_ref();
+        let runtime_line = match process.and_then(|p| p.pid) {
+            Some(pid) => format!("{} (pid {pid})", bot.runtime_status),
+            None => bot.runtime_status.clone(),
+        };
+        let process_detail = process
+            .map(|p| {
+                let opt = |v: Option<String>| v.unwrap_or_else(|| String::from("-"));
+                format!(
+                    "\n\nProcess:\n- CPU: {}  Memory: {}\n- Uptime: {}  Restarts: {} ({} unstable)\n- Node: {}\n- Exec: {}",
+                    opt(p.cpu_percent.map(|c| format!("{c:.1}%"))),
+                    opt(p.memory_bytes.map(runtime::format_bytes)),
+                    opt(p.uptime(runtime::now_ms()).map(runtime::format_duration)),
+                    opt(p.restarts.map(|r| r.to_string())),
+                    opt(p.unstable_restarts.map(|r| r.to_string())),
+                    opt(p.node_version.clone()),
+                    opt(p.exec_path.clone()),
+                )
+            })
+            .unwrap_or_default();
+        let divergence = match &bot.divergence {
+            Ok(d) => format!("{:.2}% RMS ({:.4} promille)", d.rms_percent, d.promille),
+            Err(reason) => format!("n/a ({reason})"),
+        };
+        let market = match &bot.market {
+            Ok(g) => format!(
+                "{:.6} ({} old, {})\n  {:.6} {} {:.6}\n  {:+.2}% from grid centre {:.6}{}",
+                g.price,
+                runtime::format_duration(std::time::Duration::from_millis(
+                    (runtime::now_ms() - g.as_of_ms).max(0) as u64
+                )),
+                g.source,
+                g.min_price,
+                g.bar(30),
+                g.max_price,
+                g.drift_percent,
+                g.grid_center,
+                if g.in_range() { "" } else { " - OUT OF RANGE" },
+            ),
+            Err(reason) => format!("n/a ({reason})"),
+        };
+        let config_state = match app.restart_tracker.state(&bot.name) {
+            None => String::from("n/a (not running)"),
+            Some(ConfigState::Current) => String::from("running config matches bots.json"),
+            Some(ConfigState::Unknown) => String::from(
+                "bots.json modified after start; the entry at start was not seen, restart to be sure",
+            ),
+            Some(ConfigState::RestartRequired(changes)) => {
+                let value = |v: &Option<serde_json::Value>| {
+                    v.as_ref().map_or_else(|| String::from("(unset)"), |v| v.to_string())
+                };
+                let lines = changes
+                    .iter()
+                    .map(|c| format!("\n  {}: {} -> {}", c.field, value(&c.before), value(&c.after)))
+                    .collect::<String>();
+                format!("RESTART-REQUIRED, entry changed since start:{lines}")
+            }
+        };
+        let spread = match spread::measure(&bot.grid) {
+            Some(r) => {
+                let target = bot.config.target_spread_percent();
+                let history = app
+                    .spread_tracker
+                    .history(&bot.name)
+                    .iter()
+                    .map(|(ts, p)| format!("{} {p:.2}%", &logline::format_epoch_ms(*ts)[11..16]))
+                    .collect::<Vec<_>>();
+                format!(
+                    "{:.2}% (target {target:.2}%, increment {:.2}%){}\n  best buy {:.8} / best sell {:.8}\n  history: {}",
+                    r.percent,
+                    bot.config.increment_percent(),
+                    app.spread_tracker
+                        .wide_since(&bot.name)
+                        .map(|since| format!(
+                            " - WIDE for {}",
+                            runtime::format_duration(since.elapsed())
+                        ))
+                        .unwrap_or_default(),
+                    r.best_buy,
+                    r.best_sell,
+                    if history.is_empty() { String::from("-") } else { history.join(" -> ") },
+                )
+            }
+            None => String::from("n/a (no active buy and sell in the persisted grid)"),
+        };
+        let trend = match &bot.trend {
+            Ok(t) => {
+                let crossovers = t
+                    .crossovers
+                    .iter()
+                    .map(|c| format!("{} -> {}", &logline::format_epoch_ms(c.ts_ms)[..16], c.to.label()))
+                    .collect::<Vec<_>>();
+                format!(
+                    "{} ({}; raw {} for {} bars, separation {:.2}%, confidence {})\n  centre AMA er {} fast {} slow {} ({}), price {:+.2}% from it\n  crossovers: {}",
+                    t.trend.label(),
+                    if t.trend == Trend::Neutral { "unconfirmed" } else { "confirmed" },
+                    t.raw.label(),
+                    t.bars_in_trend,
+                    t.separation_percent,
+                    t.confidence,
+                    t.slow_params.er,
+                    t.slow_params.fast,
+                    t.slow_params.slow,
+                    t.params_source,
+                    t.price_from_center_percent,
+                    if crossovers.is_empty() { String::from("none") } else { crossovers.join(", ") },
+                )
+            }
+            Err(reason) => format!("n/a ({reason})"),
         };
         format!(
-            "Selected: {}\nPair: {}\nConfig active: {}\nRuntime: {}\nWarnings: {}\nLog: {}\n\nLive ingestion:\n- PM2 status: {}\n- Tail lines loaded: {}\n- Alerts: {}\n- Latest alert: {}",
+            "Selected: {} (key {}, process {})\nPair: {}\nConfig active: {}\nConfig: {}\nRuntime: {}\nWarnings: {}\nLog: {}\nError log: {}\n\nLive ingestion:\n- Runtime backend: {} ({})\n- Tail lines loaded: {}\n- Grid divergence: {}\n- Spread: {}\n- Market: {}\n- Trend: {}\n- Alerts: {}\n- Latest alert: {}{}",
             bot.name,
+            bot.identity.bot_key,
+            process_name,
             bot.pair,
             bot.active,
-            bot.runtime_status,
+            config_state,
+            runtime_line,
             app.snapshot.warnings,
             log_path,
-            if app.snapshot.pm2_online { "yes" } else { "no" },
+            error_log,
+            app.snapshot.runtime.backend,
+            if app.snapshot.runtime.online { "online" } else { "offline" },
             bot.log_tail.len(),
+            divergence,
+            spread,
+            market,
+            trend,
             app.snapshot.alerts.len(),
-            alert_hint
+            alert_hint,
+            process_detail
         )
     } else {
         String::from("No bot entries found in profiles/bots.json")
@@ -137,6 +417,65 @@ fn render_main(frame: &mut Frame, app: &mut App, area: Rect) {
     frame.render_stateful_widget(actions_list, columns[2], &mut app.action_list_state);
 }
 
+fn render_log_view(frame: &mut Frame, app: &mut App, area: Rect) {
+    let log_colors = app.log_colors;
+    let Some(viewer) = app.log_view.as_mut() else {
+        return;
+    };
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(3)])
+        .split(area);
+    viewer.view.set_page_height(rows[0].height.saturating_sub(2) as usize);
+
+    let view = &viewer.view;
+    let lines = view
+        .visible
+        .iter()
+        .enumerate()
+        .skip(view.offset)
+        .take(view.page_height)
+        .map(|(pos, index)| {
+            let line = &viewer.lines[*index];
+            let mut styled = log_line(line, log_colors);
+            if viewer.is_match(line) {
+                let mut highlight = Style::default().bg(Color::Blue).fg(Color::White);
+                if viewer.current_match == Some(pos) {
+                    highlight = highlight.add_modifier(Modifier::BOLD);
+                }
+                for span in &mut styled.spans {
+                    span.style = span.style.patch(highlight);
+                }
+            }
+            styled
+        })
+        .collect::<Vec<_>>();
+
+    let title = format!(
+        "Log | {} | {} | {} | {} | {} | cat: {} | {}",
+        viewer.title,
+        viewer.path.display(),
+        view.position_label(),
+        if view.follow { "FOLLOW" } else { "PAUSED" },
+        view.levels_label(),
+        view.category.as_deref().unwrap_or("all"),
+        log_colors.label(),
+    );
+    let body = Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(title));
+    frame.render_widget(body, rows[0]);
+
+    let footer = match &viewer.input {
+        Some(LogInput::Search(q)) => format!("/{q}"),
+        Some(LogInput::JumpTo(t)) => format!("jump to (YYYY-MM-DD HH:MM[:SS] or HH:MM): {t}"),
+        None => format!(
+            "{}  |  q/Esc close | PgUp/PgDn j/k scroll | g/G top/bottom | f follow | / search n/N | 1-4 levels | c category | t jump to time | a colours",
+            if viewer.status.is_empty() { "ready" } else { viewer.status.as_str() }
+        ),
+    };
+    let footer = Paragraph::new(footer).block(Block::default().borders(Borders::ALL));
+    frame.render_widget(footer, rows[1]);
+}
+
 fn render_bottom(frame: &mut Frame, app: &App, area: Rect) {
     let columns = Layout::default()
         .direction(Direction::Horizontal)
@@ -144,23 +483,21 @@ fn render_bottom(frame: &mut Frame, app: &App, area: Rect) {
         .split(area);
 
     let output = Paragraph::new(format!(
-        "{}\n\nKeys: q quit | r refresh | j/k move | tab switch tab | x run action",
+        "{}\n\nKeys: q quit | r refresh | j/k move | tab switch tab | x run action | v view log | d reset bot orders\nPreview: l load selected bot | e/Enter edit field | PgUp/PgDn scroll",
         app.last_output
     ))
     .wrap(Wrap { trim: false })
     .block(Block::default().borders(Borders::ALL).title("Output"));
     frame.render_widget(output, columns[0]);
 
-    let log_lines = app
-        .selected_bot()
-        .map(|b| {
-            if b.log_tail.is_empty() {
-                String::from("(no log lines loaded)")
-            } else {
-                b.log_tail.join("\n")
-            }
-        })
-        .unwrap_or_else(|| String::from("(no bot selected)"));
+    let log_lines = match app.selected_bot() {
+        Some(b) if b.log_tail.is_empty() => vec![Line::from("(no log lines loaded)")],
+        Some(b) => logline::parse_lines(b.log_tail.iter().map(String::as_str))
+            .iter()
+            .map(|line| log_line(line, app.log_colors))
+            .collect(),
+        None => vec![Line::from("(no bot selected)")],
+    };
 
     let logs = Paragraph::new(log_lines)
         .wrap(Wrap { trim: false })
@@ -168,40 +505,165 @@ fn render_bottom(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(logs, columns[1]);
 }
 
+fn log_line(line: &LogLine, mode: LogColorMode) -> Line<'static> {
+    match mode {
+        LogColorMode::Source => ansi::to_line(&line.raw),
+        LogColorMode::Dashboard => {
+            let style = match line.level {
+                Some(Level::Error) => Style::default().fg(Color::Red),
+                Some(Level::Warn) => Style::default().fg(Color::Yellow),
+                Some(Level::Debug) => Style::default().fg(Color::DarkGray),
+                _ => Style::default(),
+            };
+            Line::styled(line.text.clone(), style)
+        }
+    }
+}
+
 fn render_modal(frame: &mut Frame, app: &App) {
     let Some(pending) = &app.pending_action else {
         return;
     };
 
-    let popup = centered_rect(70, 35, frame.size());
+    let popup = match pending {
+        PendingAction::Confirm { .. } => centered_rect(70, 35, frame.size()),
+        _ => centered_rect(80, 70, frame.size()),
+    };
     frame.render_widget(Clear, popup);
 
-    let body = match pending {
-        PendingAction::Confirm { action_index } => {
-            let action = &app.actions[*action_index];
-            format!(
-                "Action: {}\nRisk: confirm\n\nPress y to execute or n/esc to cancel.",
-                action.name
+    // The prompt sits in its own area at the bottom so a long plan cannot push
+    // it out of the popup.
+    let (body, prompt) = match pending {
+        PendingAction::Confirm { target } => (
+            vec![
+                Line::from(format!("Action: {}", app.target_name(target))),
+                Line::from("Risk: confirm"),
+            ],
+            vec![Line::from("Press y to execute or n/esc to cancel.")],
+        ),
+        PendingAction::Review { target, plan } => {
+            let mut lines = vec![
+                Line::from(format!("Action: {}", app.target_name(target))),
+                Line::from("Risk: danger (dry run, nothing deleted yet)"),
+                Line::from(""),
+            ];
+            lines.extend(deletion_plan_lines(plan));
+            (
+                lines,
+                vec![Line::from("Enter continues to confirmation, n/esc cancels.")],
             )
         }
-        PendingAction::Danger { action_index, typed } => {
-            let action = &app.actions[*action_index];
-            format!(
-                "Action: {}\nRisk: danger\n\nType DELETE and press Enter to continue.\nCurrent input: {}\nEsc cancels.",
-                action.name, typed
+        PendingAction::Danger {
+            target,
+            plan,
+            typed,
+        } => {
+            let mut lines = vec![
+                Line::from(format!("Action: {}", app.target_name(target))),
+                Line::from("Risk: danger"),
+                Line::from(""),
+            ];
+            lines.extend(deletion_plan_lines(plan));
+            (
+                lines,
+                vec![
+                    Line::from(format!(
+                        "Type {} and press Enter to continue.",
+                        target.confirm_token()
+                    )),
+                    Line::from(format!("Current input: {typed}")),
+                    Line::from("Esc cancels."),
+                ],
             )
         }
     };
 
-    let modal = Paragraph::new(body)
-        .wrap(Wrap { trim: true })
-        .block(
-            Block::default()
-                .title("Confirmation Required")
-                .borders(Borders::ALL)
-                .style(Style::default().fg(Color::White).bg(Color::Black)),
-        );
-    frame.render_widget(modal, popup);
+    let block = Block::default()
+        .title("Confirmation Required")
+        .borders(Borders::ALL)
+        .style(Style::default().fg(Color::White).bg(Color::Black));
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(1),
+            Constraint::Length(prompt.len() as u16),
+        ])
+        .split(inner);
+    frame.render_widget(Paragraph::new(body).wrap(Wrap { trim: true }), chunks[0]);
+    frame.render_widget(
+        Paragraph::new(prompt).style(Style::default().add_modifier(Modifier::BOLD)),
+        chunks[2],
+    );
+}
+
+/// Files kept in the modal; the directory summary still counts all of them.
+const MAX_PLAN_FILES: usize = 12;
+/// In-use warnings kept in the modal; the rest are counted.
+const MAX_PLAN_WARNINGS: usize = 5;
+
+fn deletion_plan_lines(plan: &dryrun::DeletionPlan) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    if let Some(note) = &plan.note {
+        lines.push(Line::from(note.clone()));
+    }
+    if plan.files.is_empty() {
+        if plan.note.is_none() {
+            lines.push(Line::from("Nothing to delete: no matching files."));
+        }
+        return lines;
+    }
+    let newest = plan
+        .newest_ms()
+        .map(logline::format_epoch_ms)
+        .unwrap_or_else(|| String::from("-"));
+    lines.push(Line::from(format!(
+        "Deletes {} files, {}, newest modified {newest} UTC",
+        plan.files.len(),
+        dryrun::format_size(plan.total_size()),
+    )));
+    for (dir, files, size) in plan.dirs() {
+        lines.push(Line::from(format!(
+            "  {}/: {files} files, {}",
+            dir.display(),
+            dryrun::format_size(size)
+        )));
+    }
+    let warn = Style::default().fg(Color::Red).add_modifier(Modifier::BOLD);
+    for file in plan.in_use().take(MAX_PLAN_WARNINGS) {
+        lines.push(Line::styled(
+            format!(
+                "WARNING: {} in use by {}",
+                file.path.display(),
+                file.in_use.join(", ")
+            ),
+            warn,
+        ));
+    }
+    let in_use = plan.in_use().count();
+    if in_use > MAX_PLAN_WARNINGS {
+        lines.push(Line::styled(
+            format!("WARNING: ... and {} more in use", in_use - MAX_PLAN_WARNINGS),
+            warn,
+        ));
+    }
+    lines.push(Line::from(""));
+    for file in plan.files.iter().take(MAX_PLAN_FILES) {
+        lines.push(Line::from(format!(
+            "  - {} ({})",
+            file.path.display(),
+            dryrun::format_size(file.size)
+        )));
+    }
+    if plan.files.len() > MAX_PLAN_FILES {
+        lines.push(Line::from(format!(
+            "  ... and {} more",
+            plan.files.len() - MAX_PLAN_FILES
+        )));
+    }
+    lines
 }
 
 fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
@@ -223,3 +685,21 @@ fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
         ])
         .split(vertical[1])[1]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tab_window_centres_the_selected_tab_when_the_bar_overflows() {
+        let titles = ["aaaa", "bbbb", "cccc", "dddd", "eeee", "ffff"];
+        // Every title takes 7 cells, the last one without its divider.
+        assert_eq!(tab_window(&titles, 3, 41), 0..6);
+        // 33 cells left once both markers are reserved: four titles.
+        assert_eq!(tab_window(&titles, 2, 40), 1..5);
+        assert_eq!(tab_window(&titles, 0, 40), 0..4);
+        assert_eq!(tab_window(&titles, 5, 40), 2..6);
+        // Too narrow for anything but the selected tab.
+        assert_eq!(tab_window(&titles, 2, 3), 2..3);
+    }
+}
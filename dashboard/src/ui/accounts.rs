@@ -0,0 +1,87 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Row, Table},
+    Frame,
+};
+
+use crate::app::App;
+
+pub(super) fn render(frame: &mut Frame, app: &App, area: Rect) {
+    let amount = |v: f64| format!("{v:.8}");
+    let mut rows = Vec::new();
+    for account in &app.snapshot.accounts {
+        rows.push(
+            Row::new(vec![
+                Cell::from(account.account.clone()),
+                Cell::from(format!("bots: {}", account.bots.join(", "))),
+            ])
+            .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        );
+        for asset in &account.assets {
+            let percent = asset.percent_total();
+            let over_chain = asset.chain_total.is_some_and(|c| asset.committed > c);
+            let style = if percent > 100.0 || over_chain {
+                Style::default().fg(Color::Red)
+            } else {
+                Style::default()
+            };
+            let shares = asset
+                .percent_shares
+                .iter()
+                .map(|(bot, p)| format!("{bot} {p}%"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            rows.push(
+                Row::new(vec![
+                    Cell::from(format!("  {}", asset.asset)),
+                    Cell::from(if shares.is_empty() {
+                        String::from("-")
+                    } else {
+                        format!("{percent:.0}% ({shares})")
+                    }),
+                    Cell::from(if asset.absolute > 0.0 {
+                        amount(asset.absolute)
+                    } else {
+                        String::from("-")
+                    }),
+                    Cell::from(amount(asset.committed)),
+                    Cell::from(amount(asset.virtual_reserved)),
+                    Cell::from(asset.chain_total.map(amount).unwrap_or_else(|| String::from("-"))),
+                ])
+                .style(style),
+            );
+        }
+    }
+    if rows.is_empty() {
+        rows.push(Row::new(vec![Cell::from("(no bots)")]));
+    }
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(24),
+            Constraint::Min(30),
+            Constraint::Length(18),
+            Constraint::Length(18),
+            Constraint::Length(18),
+            Constraint::Length(18),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            "Account / asset",
+            "botFunds % (active bots)",
+            "botFunds absolute",
+            "Committed",
+            "Virtual",
+            "Chain total",
+        ])
+        .style(Style::default().add_modifier(Modifier::BOLD)),
+    )
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Accounts | allocations per asset across bots sharing a preferredAccount"),
+    );
+    frame.render_widget(table, area);
+}
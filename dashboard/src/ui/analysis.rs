@@ -0,0 +1,131 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, Wrap},
+    Frame,
+};
+
+use crate::{analysis, app::App, grid};
+
+pub(super) fn render(frame: &mut Frame, app: &App, area: Rect) {
+    let rows_area = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(6), Constraint::Length(8)])
+        .split(area);
+    let state = &app.analysis;
+    let files = state
+        .tables
+        .iter()
+        .enumerate()
+        .map(|(i, t)| {
+            let name = match t {
+                Ok(table) => table.name(),
+                Err((path, _)) => path.display().to_string(),
+            };
+            if i == state.selected_table {
+                format!("[{name}]")
+            } else {
+                name
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let table = match state.table() {
+        None => {
+            let body = Paragraph::new(
+                "No result files found under analysis/ama_fitting (optimization_results_*.json) or analysis/bot_fitting (bot_fitting_results_*.json).",
+            )
+            .wrap(Wrap { trim: true })
+            .block(Block::default().borders(Borders::ALL).title("Analysis"));
+            frame.render_widget(body, area);
+            return;
+        }
+        Some(Err((path, err))) => {
+            let body = Paragraph::new(format!("{}: unreadable ({err})", path.display()))
+                .wrap(Wrap { trim: true })
+                .block(Block::default().borders(Borders::ALL).title(format!("Analysis | {files}")));
+            frame.render_widget(body, area);
+            return;
+        }
+        Some(Ok(table)) => table,
+    };
+
+    let order = if state.descending { "desc" } else { "asc" };
+    let mut header = vec![String::from("Set")];
+    header.extend(table.columns.iter().enumerate().map(|(i, c)| {
+        if state.sort_column == Some(i) {
+            format!("{c} ({order})")
+        } else {
+            c.to_string()
+        }
+    }));
+    let rows = state
+        .sorted_rows()
+        .into_iter()
+        .enumerate()
+        .map(|(pos, index)| {
+            let row = &table.rows[index];
+            let mut cells = vec![Cell::from(row.label.clone())];
+            cells.extend(row.values.iter().map(|v| {
+                Cell::from(match v {
+                    Some(v) if v.fract() == 0.0 && v.abs() < 1e9 => format!("{v:.0}"),
+                    Some(v) => format!("{v:.2}"),
+                    None => String::from("-"),
+                })
+            }));
+            let style = if pos == state.selected_row {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            Row::new(cells).style(style)
+        })
+        .collect::<Vec<_>>();
+    let mut widths = vec![Constraint::Length(26)];
+    widths.extend(table.columns.iter().map(|_| Constraint::Length(11)));
+    let widget = Table::new(rows, widths)
+        .header(Row::new(header).style(Style::default().add_modifier(Modifier::BOLD)))
+        .block(Block::default().borders(Borders::ALL).title(format!(
+            "Analysis | {} ({}) | {} | f file, s sort column, o order, j/k row, b bot | {files}",
+            table.name(),
+            table.kind.label(),
+            table.data_label,
+        )));
+    frame.render_widget(widget, rows_area[0]);
+
+    let compare = match (state.selected(), app.selected_bot()) {
+        (Some(row), Some(bot)) => {
+            let mut lines = vec![format!("{} vs live {} ({})", row.label, bot.name, bot.pair)];
+            for c in analysis::compare(row, &bot.config) {
+                lines.push(match (c.suggested, c.delta_percent()) {
+                    (Some(suggested), Some(delta)) => format!(
+                        "- {}: live {:.3} | suggested {:.3} ({delta:+.1}%)",
+                        c.name, c.live, suggested
+                    ),
+                    (Some(suggested), None) => {
+                        format!("- {}: live {:.3} | suggested {:.3}", c.name, c.live, suggested)
+                    }
+                    (None, _) => format!(
+                        "- {}: live {:.3} | no suggestion in this file",
+                        c.name, c.live
+                    ),
+                });
+            }
+            if let (Some(spread), Some(increment)) = (row.spread_percent, row.increment_percent) {
+                lines.push(format!(
+                    "- suggested spread is {:.2}x the increment (the bot requires at least {}x)",
+                    spread / increment,
+                    grid::MIN_SPREAD_FACTOR
+                ));
+            }
+            lines.join("\n")
+        }
+        (None, _) => String::from("(no result row selected)"),
+        (_, None) => String::from("(no bot to compare against)"),
+    };
+    let compare = Paragraph::new(compare)
+        .wrap(Wrap { trim: true })
+        .block(Block::default().borders(Borders::ALL).title("Compare with live config"));
+    frame.render_widget(compare, rows_area[1]);
+}
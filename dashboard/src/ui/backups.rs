@@ -0,0 +1,68 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Row, Table},
+    Frame,
+};
+
+use crate::{app::App, backups, dryrun, logline, runtime};
+
+pub(super) fn render(frame: &mut Frame, app: &App, area: Rect) {
+    let now_ms = runtime::now_ms();
+    let settings = &app.snapshot.settings;
+    let retention = backups::Retention::new(settings.backup_retention_count, settings.backup_retention_days);
+    let expired = backups::expired(&app.backups.archives, retention, now_ms);
+    let mut rows = app
+        .backups
+        .archives
+        .iter()
+        .enumerate()
+        .map(|(i, a)| {
+            let age = runtime::format_duration(std::time::Duration::from_millis((now_ms - a.created_ms).max(0) as u64));
+            let mut style = if expired.contains(&a) {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default()
+            };
+            if i == app.backups.selected {
+                style = style.add_modifier(Modifier::BOLD | Modifier::REVERSED);
+            }
+            Row::new(vec![
+                Cell::from(a.path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default()),
+                Cell::from(a.label.clone()),
+                Cell::from(dryrun::format_size(a.size)),
+                Cell::from(age),
+                Cell::from(logline::format_epoch_ms(a.created_ms)),
+            ])
+            .style(style)
+        })
+        .collect::<Vec<_>>();
+    if rows.is_empty() {
+        rows.push(Row::new(vec![Cell::from("(no archives in profiles/backups; Danger actions create them)")]));
+    }
+    let stopped = match backups::check_bots_stopped(&app.snapshot) {
+        Ok(()) => String::from("bots stopped, restore allowed"),
+        Err(err) => format!("restore blocked: {err}"),
+    };
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(64),
+            Constraint::Min(20),
+            Constraint::Length(10),
+            Constraint::Length(8),
+            Constraint::Length(24),
+        ],
+    )
+    .header(
+        Row::new(vec!["Archive", "Action", "Size", "Age", "Created (UTC)"])
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+    )
+    .block(Block::default().borders(Borders::ALL).title(format!(
+        "Backups | keep {} newest, prune older after {}d ({} due) | {stopped} | j/k select, x restore, p prune",
+        retention.keep,
+        settings.backup_retention_days,
+        expired.len(),
+    )));
+    frame.render_widget(table, area);
+}
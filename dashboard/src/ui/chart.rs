@@ -0,0 +1,186 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    symbols::Marker,
+    text::Span,
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph, Wrap},
+    Frame,
+};
+
+use super::render_bot_list;
+use crate::{app::App, chart::ChartData, grid::Side, logline, runtime};
+
+pub(super) fn render(frame: &mut Frame, app: &mut App, area: Rect) {
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
+        .split(area);
+    render_bot_list(frame, app, columns[0]);
+
+    let chart = &app.chart;
+    let (data, (start_ms, end_ms)) = match (&chart.data, chart.window()) {
+        (Ok(data), Some(window)) => (data, window),
+        (Err(reason), _) => {
+            let body = Paragraph::new(reason.clone())
+                .wrap(Wrap { trim: true })
+                .block(Block::default().borders(Borders::ALL).title("Chart"));
+            frame.render_widget(body, columns[1]);
+            return;
+        }
+        (Ok(data), None) => {
+            let body = Paragraph::new(format!("{}: nothing to chart", data.bot))
+                .block(Block::default().borders(Borders::ALL).title("Chart"));
+            frame.render_widget(body, columns[1]);
+            return;
+        }
+    };
+
+    // x is hours relative to the right edge of the window.
+    let hours = |ts: i64| (ts - end_ms) as f64 / 3_600_000.0;
+    let x_min = hours(start_ms);
+    let in_window = |ts: i64| (start_ms..=end_ms).contains(&ts);
+    let prices = data
+        .prices
+        .iter()
+        .filter(|(ts, _)| in_window(*ts))
+        .map(|(ts, p)| (hours(*ts), *p))
+        .collect::<Vec<_>>();
+    let fill_points = |side: Side| {
+        let fills = data
+            .fills
+            .iter()
+            .filter(|f| f.side == side && in_window(f.ts_ms))
+            .collect::<Vec<_>>();
+        let volume = fills.iter().map(|f| f.size).sum::<f64>();
+        let points = fills
+            .iter()
+            .map(|f| (hours(f.ts_ms), f.price))
+            .collect::<Vec<_>>();
+        (points, volume)
+    };
+    let (buy_fills, buy_volume) = fill_points(Side::Buy);
+    let (sell_fills, sell_volume) = fill_points(Side::Sell);
+    let level = |price: f64| [(x_min, price), (0.0, price)];
+    let buy_levels = data.buy_levels.iter().map(|p| level(*p)).collect::<Vec<_>>();
+    let sell_levels = data.sell_levels.iter().map(|p| level(*p)).collect::<Vec<_>>();
+    let bounds = if chart.show_bounds {
+        [data.min_price, data.max_price]
+            .into_iter()
+            .flatten()
+            .map(level)
+            .collect::<Vec<_>>()
+    } else {
+        vec![]
+    };
+
+    let (y_min, y_max) = chart_price_range(data, &prices, &buy_fills, &sell_fills, &bounds);
+    let mut datasets = vec![];
+    for (i, points) in bounds.iter().enumerate() {
+        datasets.push(
+            Dataset::default()
+                .name(if i == 0 { "min/max" } else { "" })
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(Color::Yellow))
+                .data(points),
+        );
+    }
+    for (levels, color, name) in [
+        (&buy_levels, Color::Green, "buy levels"),
+        (&sell_levels, Color::Red, "sell levels"),
+    ] {
+        for (i, points) in levels.iter().enumerate() {
+            let dataset = Dataset::default()
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(color))
+                .data(points);
+            datasets.push(if i == 0 { dataset.name(name) } else { dataset });
+        }
+    }
+    datasets.push(
+        Dataset::default()
+            .name(data.source_label.clone())
+            .marker(Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Cyan))
+            .data(&prices),
+    );
+    for (points, volume, color, name) in [
+        (&buy_fills, buy_volume, Color::LightGreen, "buy fills"),
+        (&sell_fills, sell_volume, Color::LightRed, "sell fills"),
+    ] {
+        if !points.is_empty() {
+            datasets.push(
+                Dataset::default()
+                    .name(format!("{name} ({}, size {volume:.4})", points.len()))
+                    .marker(Marker::Dot)
+                    .graph_type(GraphType::Scatter)
+                    .style(Style::default().fg(color).add_modifier(Modifier::BOLD))
+                    .data(points),
+            );
+        }
+    }
+
+    let time_label = |ms: i64| Span::raw(logline::format_epoch_ms(ms)[5..16].to_string());
+    let price_label = |p: f64| Span::raw(format!("{p:.6}"));
+    let title = format!(
+        "Chart | {} | {} | {} window{} | bounds {} | +/- zoom, h/l pan, 0 reset, s source, b bounds",
+        data.bot,
+        chart.source.label(),
+        runtime::format_duration(std::time::Duration::from_millis(chart.span_ms as u64)),
+        if chart.pan_ms > 0 { " (panned)" } else { "" },
+        if chart.show_bounds { "on" } else { "off" },
+    );
+    let widget = Chart::new(datasets)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .x_axis(
+            Axis::default()
+                .title("UTC")
+                .style(Style::default().fg(Color::Gray))
+                .bounds([x_min, 0.0])
+                .labels(vec![
+                    time_label(start_ms),
+                    time_label(start_ms + (end_ms - start_ms) / 2),
+                    time_label(end_ms),
+                ]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::Gray))
+                .bounds([y_min, y_max])
+                .labels(vec![
+                    price_label(y_min),
+                    price_label((y_min + y_max) / 2.0),
+                    price_label(y_max),
+                ]),
+        )
+        .hidden_legend_constraints((Constraint::Ratio(1, 3), Constraint::Ratio(1, 2)));
+    frame.render_widget(widget, columns[1]);
+}
+
+/// Price axis covering the visible data and the active levels, plus the bounds
+/// when shown, with a little headroom.
+fn chart_price_range(
+    data: &ChartData,
+    prices: &[(f64, f64)],
+    buy_fills: &[(f64, f64)],
+    sell_fills: &[(f64, f64)],
+    bounds: &[[(f64, f64); 2]],
+) -> (f64, f64) {
+    let values = prices
+        .iter()
+        .chain(buy_fills)
+        .chain(sell_fills)
+        .map(|(_, p)| *p)
+        .chain(data.buy_levels.iter().copied())
+        .chain(data.sell_levels.iter().copied())
+        .chain(bounds.iter().map(|b| b[0].1))
+        .filter(|p| p.is_finite());
+    let (lo, hi) = values.fold((f64::MAX, f64::MIN), |(lo, hi), p| (lo.min(p), hi.max(p)));
+    if lo > hi {
+        return (0.0, 1.0);
+    }
+    let pad = ((hi - lo) * 0.03).max(hi.abs() * 0.001);
+    (lo - pad, hi + pad)
+}
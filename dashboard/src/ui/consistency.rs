@@ -0,0 +1,67 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Row, Table},
+    Frame,
+};
+
+use crate::{app::App, consistency};
+
+pub(super) fn render(frame: &mut Frame, app: &App, area: Rect) {
+    let report = &app.snapshot.consistency;
+    let mut rows = report
+        .findings
+        .iter()
+        .enumerate()
+        .map(|(i, f)| {
+            let color = match f.direction {
+                consistency::Direction::Orphan => Color::Yellow,
+                consistency::Direction::Missing => Color::LightRed,
+            };
+            let mut style = Style::default().fg(color);
+            if i == app.consistency_row {
+                style = style.add_modifier(Modifier::BOLD | Modifier::REVERSED);
+            }
+            let cleanup = match &f.cleanup {
+                Some(c) => format!("[{}] {}", c.risk().label(), c.describe()),
+                None => String::from("-"),
+            };
+            Row::new(vec![
+                Cell::from(f.direction.label()),
+                Cell::from(f.kind),
+                Cell::from(f.subject.clone()),
+                Cell::from(f.detail.clone()),
+                Cell::from(cleanup),
+            ])
+            .style(style)
+        })
+        .collect::<Vec<_>>();
+    if rows.is_empty() {
+        rows.push(Row::new(vec![Cell::from("ok"), Cell::from("(every entry, orders file, log and process lines up)")]));
+    }
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(8),
+            Constraint::Length(12),
+            Constraint::Length(36),
+            Constraint::Min(30),
+            Constraint::Length(48),
+        ],
+    )
+    .header(
+        Row::new(vec!["", "Kind", "Subject", "Detail", "Cleanup"])
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+    )
+    .block(Block::default().borders(Borders::ALL).title(format!(
+        "Consistency | {} entries, {} orders files, {} logs, {} {} processes | {} orphan, {} missing | j/k select, x clean up",
+        report.entries,
+        report.orders_files,
+        report.log_files,
+        report.processes,
+        app.snapshot.runtime.backend,
+        report.count(consistency::Direction::Orphan),
+        report.count(consistency::Direction::Missing),
+    )));
+    frame.render_widget(table, area);
+}
@@ -0,0 +1,129 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, Wrap},
+    Frame,
+};
+
+use super::render_bot_list;
+use crate::{app::App, fills::AssetAmount, grid::Side, logline, runtime};
+
+pub(super) fn render(frame: &mut Frame, app: &mut App, area: Rect) {
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
+        .split(area);
+    render_bot_list(frame, app, columns[0]);
+
+    let report = match &app.cycles.data {
+        Ok(report) => report,
+        Err(reason) => {
+            let body = Paragraph::new(reason.clone())
+                .wrap(Wrap { trim: true })
+                .block(Block::default().borders(Borders::ALL).title("Cycles"));
+            frame.render_widget(body, columns[1]);
+            return;
+        }
+    };
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(8), Constraint::Min(3)])
+        .split(columns[1]);
+
+    let duration = |ms: f64| runtime::format_duration(std::time::Duration::from_millis(ms.max(0.0) as u64));
+    let opt = |v: Option<String>| v.unwrap_or_else(|| String::from("-"));
+    let quote = &report.quote;
+    let amounts = |fees: &[AssetAmount], precision: usize| {
+        (!fees.is_empty()).then(|| {
+            fees.iter()
+                .map(|f| format!("{:.precision$} {}", f.amount, f.asset))
+                .collect::<Vec<_>>()
+                .join(" + ")
+        })
+    };
+    let efficiency = report
+        .mean_profit_percent()
+        .filter(|_| report.increment_percent > 0.0)
+        .map(|p| p / report.increment_percent * 100.0);
+    let summary = format!(
+        "Cycles: {} from {} fills over {} ({} fills still open) | {} per day\nProfit per cycle: {} {quote} gross, {} {quote} after {quote} fees | mean duration {}\nProfit per cycle: {} vs incrementPercent {:.3}% ({} of theoretical)\nFee per cycle: {}",
+        report.cycles.len(),
+        report.fills,
+        duration(report.span_ms as f64),
+        report.open_fills,
+        opt(report.cycles_per_day().map(|c| format!("{c:.2}"))),
+        opt(report.mean_profit().map(|p| format!("{p:.8}"))),
+        opt(report.mean_net_profit().map(|p| format!("{p:.8}"))),
+        opt(report.mean_duration_ms().map(duration)),
+        opt(report.mean_profit_percent().map(|p| format!("{p:.3}%"))),
+        report.increment_percent,
+        opt(efficiency.map(|e| format!("{e:.0}%"))),
+        opt(amounts(&report.mean_fees(), 8)),
+    );
+    let summary = Paragraph::new(summary).wrap(Wrap { trim: true }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Round trips | {} | fills one grid level apart", report.bot)),
+    );
+    frame.render_widget(summary, rows[0]);
+
+    let mut table_rows = report
+        .cycles
+        .iter()
+        .map(|c| {
+            let percent = c.profit_percent();
+            Row::new(vec![
+                Cell::from(logline::format_epoch_ms(c.close_ms)[..16].to_string()),
+                Cell::from(match c.opened_by {
+                    Side::Buy => "buy -> sell",
+                    _ => "sell -> buy",
+                }),
+                Cell::from(format!("{:.8}", c.buy_price)),
+                Cell::from(format!("{:.8}", c.sell_price)),
+                Cell::from(format!("{:.6}", c.size)),
+                Cell::from(format!("{:.8}", c.profit())),
+                Cell::from(format!("{percent:.3}%")),
+                Cell::from(duration(c.duration_ms() as f64)),
+                Cell::from(opt(amounts(&c.fees, 6))),
+            ])
+            .style(if percent < report.increment_percent * 0.5 {
+                Style::default().fg(Color::Yellow)
+            } else {
+                Style::default()
+            })
+        })
+        .collect::<Vec<_>>();
+    if table_rows.is_empty() {
+        table_rows.push(Row::new(vec![Cell::from("(no completed cycles)")]));
+    }
+    let table = Table::new(
+        table_rows,
+        [
+            Constraint::Length(17),
+            Constraint::Length(12),
+            Constraint::Length(14),
+            Constraint::Length(14),
+            Constraint::Length(12),
+            Constraint::Length(14),
+            Constraint::Length(9),
+            Constraint::Length(10),
+            Constraint::Min(12),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            String::from("Closed"),
+            String::from("Cycle"),
+            String::from("Buy"),
+            String::from("Sell"),
+            String::from("Size"),
+            format!("Profit {quote}"),
+            String::from("Profit%"),
+            String::from("Duration"),
+            String::from("Fee"),
+        ])
+        .style(Style::default().add_modifier(Modifier::BOLD)),
+    )
+    .block(Block::default().borders(Borders::ALL).title("Cycles (newest first)"));
+    frame.render_widget(table, rows[1]);
+}
@@ -0,0 +1,109 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, List, ListItem, Row, Table},
+    Frame,
+};
+
+use crate::app::App;
+
+pub(super) fn render(frame: &mut Frame, app: &App, area: Rect) {
+    let exposure = &app.snapshot.exposure;
+    let reference = &app.exposure_reference;
+    let parts = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(6), Constraint::Length(exposure.rates.len() as u16 + 2)])
+        .split(area);
+
+    let amount = |v: f64| format!("{v:.8}");
+    let valued = exposure
+        .assets
+        .iter()
+        .map(|a| exposure.convert(&a.asset, reference).map(|(price, route)| (a.total() * price, route)))
+        .collect::<Vec<_>>();
+    let fleet_value = valued.iter().flatten().map(|(v, _)| v).sum::<f64>();
+    let mut rows = exposure
+        .assets
+        .iter()
+        .zip(&valued)
+        .map(|(asset, value)| {
+            let (value_cell, share_cell, route) = match value {
+                Some((v, route)) => (
+                    amount(*v),
+                    if fleet_value > 0.0 {
+                        format!("{:.1}%", v / fleet_value * 100.0)
+                    } else {
+                        String::from("-")
+                    },
+                    route.clone(),
+                ),
+                None => (String::from("no price"), String::from("-"), String::from("-")),
+            };
+            Row::new(vec![
+                Cell::from(asset.asset.clone()),
+                Cell::from(format!("{}: {}", asset.bots.len(), asset.bots.join(", "))),
+                Cell::from(amount(asset.committed_buy)),
+                Cell::from(amount(asset.committed_sell)),
+                Cell::from(amount(asset.virtual_buy)),
+                Cell::from(amount(asset.virtual_sell)),
+                Cell::from(amount(asset.total())),
+                Cell::from(value_cell),
+                Cell::from(share_cell),
+                Cell::from(route),
+            ])
+            .style(if value.is_none() {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default()
+            })
+        })
+        .collect::<Vec<_>>();
+    if rows.is_empty() {
+        rows.push(Row::new(vec![Cell::from("(no persisted grids)")]));
+    }
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(10),
+            Constraint::Min(20),
+            Constraint::Length(18),
+            Constraint::Length(18),
+            Constraint::Length(18),
+            Constraint::Length(18),
+            Constraint::Length(18),
+            Constraint::Length(18),
+            Constraint::Length(7),
+            Constraint::Length(28),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            String::from("Asset"),
+            String::from("Bots"),
+            String::from("Committed buy"),
+            String::from("Committed sell"),
+            String::from("Virtual buy"),
+            String::from("Virtual sell"),
+            String::from("Total"),
+            format!("Value ({reference})"),
+            String::from("Share"),
+            String::from("Priced via"),
+        ])
+        .style(Style::default().add_modifier(Modifier::BOLD)),
+    )
+    .block(Block::default().borders(Borders::ALL).title(format!(
+        "Exposure | persisted grids across all bots, valued in {reference} ({} total) | c reference",
+        amount(fleet_value)
+    )));
+    frame.render_widget(table, parts[0]);
+
+    let rates = exposure
+        .rates
+        .iter()
+        .map(|r| ListItem::new(format!("1 {} = {:.8} {}  ({})", r.base, r.price, r.quote, r.source)))
+        .collect::<Vec<_>>();
+    frame.render_widget(
+        List::new(rates).block(Block::default().borders(Borders::ALL).title("Prices")),
+        parts[1],
+    );
+}
@@ -0,0 +1,154 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, Wrap},
+    Frame,
+};
+
+use super::render_bot_list;
+use crate::{app::App, logline, runtime};
+
+pub(super) fn render(frame: &mut Frame, app: &mut App, area: Rect) {
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
+        .split(area);
+    render_bot_list(frame, app, columns[0]);
+
+    let Some(bot) = app.selected_bot() else {
+        let empty = Paragraph::new("(no bot selected)")
+            .block(Block::default().borders(Borders::ALL).title("Fees"));
+        frame.render_widget(empty, columns[1]);
+        return;
+    };
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(5),
+            Constraint::Length(6),
+            Constraint::Min(3),
+        ])
+        .split(columns[1]);
+
+    let history = app.fee_tracker.owed_history(&bot.name);
+    let trend = match history.as_slice() {
+        [.., (_, before), (_, last)] if last > before => "rising",
+        [.., (_, before), (_, last)] if last < before => "falling (settled)",
+        [_] => "unchanged since start",
+        _ => "-",
+    };
+    let owed = format!(
+        "btsFeesOwed: {} BTS ({trend})\nseen: {}",
+        bot.bts_fees_owed
+            .map(|o| format!("{o:.8}"))
+            .unwrap_or_else(|| String::from("- (not in orders file)")),
+        if history.is_empty() {
+            String::from("-")
+        } else {
+            history
+                .iter()
+                .map(|(ts, v)| format!("{} {v:.5}", &logline::format_epoch_ms(*ts)[11..16]))
+                .collect::<Vec<_>>()
+                .join(" -> ")
+        },
+    );
+    frame.render_widget(
+        Paragraph::new(owed).wrap(Wrap { trim: true }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Fees | {} | orders file", bot.name)),
+        ),
+        rows[0],
+    );
+
+    let amounts = |map: &std::collections::BTreeMap<String, f64>| {
+        if map.is_empty() {
+            String::from("-")
+        } else {
+            map.iter()
+                .map(|(asset, v)| format!("{v:.8} {asset}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        }
+    };
+    let threshold = app.snapshot.settings.fee_proceeds_alert_percent;
+    let ledger = app.fee_tracker.ledger(&bot.name);
+    let window_rows = ledger
+        .map(|l| l.windows(runtime::now_ms()))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|window| {
+            let share = window.totals.share(&app.snapshot.exposure);
+            let over = matches!(share, Ok(Some(s)) if s > threshold);
+            Row::new(vec![
+                Cell::from(if window.partial {
+                    format!("{}*", window.label)
+                } else {
+                    window.label.to_string()
+                }),
+                Cell::from(window.totals.maker_fills.to_string()),
+                Cell::from(amounts(&window.totals.fees)),
+                Cell::from(amounts(&window.totals.proceeds)),
+                Cell::from(match share {
+                    Ok(Some(s)) => format!("{s:.2}%"),
+                    Ok(None) => String::from("-"),
+                    Err(missing) => format!("alert inactive: {missing}"),
+                }),
+            ])
+            .style(if over {
+                Style::default().fg(Color::Red)
+            } else {
+                Style::default()
+            })
+        })
+        .collect::<Vec<_>>();
+    let table = Table::new(
+        window_rows,
+        [
+            Constraint::Length(6),
+            Constraint::Length(12),
+            Constraint::Min(20),
+            Constraint::Min(20),
+            Constraint::Min(18),
+        ],
+    )
+    .header(
+        Row::new(vec!["Window", "Maker fills", "Fees", "Proceeds", "Fees / proceeds"])
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+    )
+    .block(Block::default().borders(Borders::ALL).title(format!(
+        "Cumulative [FEES] (* = starts before the oldest line read) | alert above {threshold:.1}% of proceeds over 24h"
+    )));
+    frame.render_widget(table, rows[1]);
+
+    let fee_rows = ledger
+        .map(|l| l.fees.iter().rev().collect::<Vec<_>>())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|fee| {
+            Row::new(vec![
+                Cell::from(logline::format_epoch_ms(fee.ts_ms)[..19].to_string()),
+                Cell::from(fee.fills.to_string()),
+                Cell::from(format!("{:.8}", fee.per_fill)),
+                Cell::from(fee.asset.clone()),
+                Cell::from(format!("{:.8}", fee.total)),
+            ])
+        })
+        .collect::<Vec<_>>();
+    let fee_table = Table::new(
+        fee_rows,
+        [
+            Constraint::Length(20),
+            Constraint::Length(12),
+            Constraint::Length(14),
+            Constraint::Length(8),
+            Constraint::Length(14),
+        ],
+    )
+    .header(
+        Row::new(vec!["Time", "Maker fills", "Fee per fill", "Asset", "Total"])
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+    )
+    .block(Block::default().borders(Borders::ALL).title("[FEES] lines (newest first)"));
+    frame.render_widget(fee_table, rows[2]);
+}
@@ -0,0 +1,203 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, Wrap},
+    Frame,
+};
+
+use super::render_bot_list;
+use crate::{app::App, grid::Side, partials, runtime};
+
+pub(super) fn render(frame: &mut Frame, app: &mut App, area: Rect) {
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
+        .split(area);
+    render_bot_list(frame, app, columns[0]);
+
+    let threshold = app.snapshot.settings.divergence_alert_rms_percent;
+    let Some(bot) = app.selected_bot() else {
+        let empty = Paragraph::new("(no bot selected)")
+            .block(Block::default().borders(Borders::ALL).title("Grid Divergence"));
+        frame.render_widget(empty, columns[1]);
+        return;
+    };
+
+    let partials = app.partial_tracker.partials(&bot.name);
+    let sections = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(11),
+            Constraint::Length(partials.len().clamp(1, 8) as u16 + 3),
+        ])
+        .split(columns[1]);
+    render_partials(frame, app, partials, sections[1]);
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(8), Constraint::Min(3)])
+        .split(sections[0]);
+
+    let div = match &bot.divergence {
+        Ok(div) => div,
+        Err(reason) => {
+            let body = Paragraph::new(format!("{}: divergence unavailable ({reason})", bot.name))
+                .wrap(Wrap { trim: true })
+                .block(Block::default().borders(Borders::ALL).title("Grid Divergence"));
+            frame.render_widget(body, sections[0]);
+            return;
+        }
+    };
+
+    let status_style = if div.rms_percent > threshold {
+        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::Green)
+    };
+    let worst = div
+        .worst_slot
+        .as_ref()
+        .map(|(id, pct)| format!("{id} ({pct:.2}%)"))
+        .unwrap_or_else(|| String::from("-"));
+    let summary = format!(
+        "RMS size divergence: {:.2}% (threshold {:.1}%) | {:.4} promille\nWorst slot: {}\nReference price: {:.8} | bounds [{:.8}, {:.8}] | gap slots: {}\nIdeal slots: buy {}+{} virtual, sell {}+{} virtual\nUnmatched: persisted {} / ideal {} | role mismatches: {} | state mismatches: {} | partial excluded: {}",
+        div.rms_percent,
+        threshold,
+        div.promille,
+        worst,
+        div.ideal.reference_price,
+        div.ideal.min_price,
+        div.ideal.max_price,
+        div.ideal.gap_slots,
+        div.ideal.count(Side::Buy, true),
+        div.ideal.count(Side::Buy, false),
+        div.ideal.count(Side::Sell, true),
+        div.ideal.count(Side::Sell, false),
+        div.unmatched_persisted,
+        div.unmatched_ideal,
+        div.role_mismatches,
+        div.state_mismatches,
+        div.partial_excluded,
+    );
+    let summary = Paragraph::new(summary)
+        .style(status_style)
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Grid Divergence | {}", bot.name)),
+        );
+    frame.render_widget(summary, rows[0]);
+
+    let mut slots = div.slots.iter().collect::<Vec<_>>();
+    slots.sort_by(|a, b| b.size_error.abs().total_cmp(&a.size_error.abs()));
+    let table_rows = slots
+        .into_iter()
+        .map(|slot| {
+            let style = if !slot.counted {
+                Style::default().fg(Color::Blue)
+            } else if slot.size_error.abs() * 100.0 > threshold {
+                Style::default().fg(Color::Red)
+            } else {
+                Style::default()
+            };
+            let ideal_role = match slot.ideal_side {
+                Some(side) if slot.ideal_active => format!("{} active", side.label()),
+                Some(side) => format!("{} virtual", side.label()),
+                None => String::from("(unmatched)"),
+            };
+            Row::new(vec![
+                Cell::from(slot.id.clone()),
+                Cell::from(format!(
+                    "{} {}",
+                    slot.side.map(Side::label).unwrap_or("?"),
+                    slot.state
+                )),
+                Cell::from(ideal_role),
+                Cell::from(format!("{:.8}", slot.price)),
+                Cell::from(format!("{:+.3}%", slot.price_error_percent)),
+                Cell::from(format!("{:.6}", slot.size)),
+                Cell::from(format!("{:.6}", slot.ideal_size)),
+                Cell::from(format!("{:+.2}%", slot.size_error * 100.0)),
+            ])
+            .style(style)
+        })
+        .collect::<Vec<_>>();
+    let table = Table::new(
+        table_rows,
+        [
+            Constraint::Length(10),
+            Constraint::Length(15),
+            Constraint::Length(15),
+            Constraint::Length(16),
+            Constraint::Length(10),
+            Constraint::Length(14),
+            Constraint::Length(14),
+            Constraint::Length(10),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            "Slot", "Persisted", "Ideal", "Price", "dPrice", "Size", "Ideal size", "dSize",
+        ])
+        .style(Style::default().add_modifier(Modifier::BOLD)),
+    )
+    .block(Block::default().borders(Borders::ALL).title("Per-Slot (worst first)"));
+    frame.render_widget(table, rows[1]);
+}
+
+fn render_partials(frame: &mut Frame, app: &App, partials: &[partials::PartialSlot], area: Rect) {
+    let now_ms = runtime::now_ms();
+    let opt = |v: Option<f64>, fmt: &dyn Fn(f64) -> String| v.map(fmt).unwrap_or_else(|| String::from("-"));
+    let mut rows = partials
+        .iter()
+        .map(|p| {
+            let age = runtime::format_duration(std::time::Duration::from_millis(
+                (now_ms - p.partial_since_ms).max(0) as u64,
+            ));
+            Row::new(vec![
+                Cell::from(p.id.clone()),
+                Cell::from(p.side.map(Side::label).unwrap_or("?")),
+                Cell::from(format!("{:.8}", p.price)),
+                Cell::from(opt(p.original, &|o| {
+                    format!("{o:.6}{}", if p.original_seen { "" } else { " (ideal)" })
+                })),
+                Cell::from(format!("{:.6}", p.remaining)),
+                Cell::from(opt(p.ideal, &|i| format!("{i:.6}"))),
+                Cell::from(opt(p.fill_percent(), &|f| format!("{f:.1}%"))),
+                Cell::from(if p.transition_seen { age } else { format!(">= {age}") }),
+                Cell::from(if p.dust { "DUST" } else { "" }),
+            ])
+            .style(if p.dust {
+                Style::default().fg(Color::Red)
+            } else {
+                Style::default().fg(Color::Yellow)
+            })
+        })
+        .collect::<Vec<_>>();
+    if rows.is_empty() {
+        rows.push(Row::new(vec![Cell::from("(no partial slots)")]));
+    }
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(10),
+            Constraint::Length(5),
+            Constraint::Length(16),
+            Constraint::Length(22),
+            Constraint::Length(14),
+            Constraint::Length(14),
+            Constraint::Length(8),
+            Constraint::Length(14),
+            Constraint::Length(5),
+        ],
+    )
+    .header(
+        Row::new(vec!["Slot", "Side", "Price", "Original", "Remaining", "Ideal", "Filled", "Partial for", ""])
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+    )
+    .block(Block::default().borders(Borders::ALL).title(format!(
+        "Partial slots (oldest first) | dust below {:.1}% of ideal size",
+        app.snapshot.settings.dust_threshold_percent
+    )));
+    frame.render_widget(table, area);
+}
@@ -0,0 +1,179 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, List, ListItem, Paragraph, Row, Table, Wrap},
+    Frame,
+};
+
+use crate::{
+    app::App,
+    grid::Side,
+    preview::{self, PreviewField},
+};
+
+pub(super) fn render(frame: &mut Frame, app: &mut App, area: Rect) {
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+        .split(area);
+    let left = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(PreviewField::ALL.len() as u16 + 2),
+            Constraint::Min(4),
+        ])
+        .split(columns[0]);
+
+    let preview = &app.preview;
+    let field_items = PreviewField::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let value = match &preview.editing {
+                Some(typed) if i == preview.selected => format!("{typed}_"),
+                _ => preview.values[i].clone(),
+            };
+            let style = if i == preview.selected {
+                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            ListItem::new(format!("{:<24} {}", field.label(), value)).style(style)
+        })
+        .collect::<Vec<_>>();
+    let form = List::new(field_items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Preview Config ({})", preview.source)),
+    );
+    frame.render_widget(form, left[0]);
+
+    let projected = preview.compute();
+    let totals = match &projected {
+        Ok(grid) => {
+            let sum = |side: Side, active: bool| {
+                grid.slots
+                    .iter()
+                    .filter(|s| s.side == side && s.active == active)
+                    .map(|s| s.size)
+                    .sum::<f64>()
+            };
+            let best_buy = grid
+                .slots
+                .iter()
+                .filter(|s| s.side == Side::Buy)
+                .map(|s| s.price)
+                .fold(f64::NAN, f64::max);
+            let best_sell = grid
+                .slots
+                .iter()
+                .filter(|s| s.side == Side::Sell)
+                .map(|s| s.price)
+                .fold(f64::NAN, f64::min);
+            format!(
+                "Levels: {} in [{:.8}, {:.8}]\nSpread gap: {} slots ({:.3}%)\nSell: {} active {:.6} + {} virtual {:.6} ({})\nBuy:  {} active {:.6} + {} virtual {:.6} ({})\nTotal sell {:.6} | total buy {:.6}",
+                grid.slots.len(),
+                grid.min_price,
+                grid.max_price,
+                grid.gap_slots,
+                (best_sell / best_buy - 1.0) * 100.0,
+                grid.count(Side::Sell, true),
+                sum(Side::Sell, true),
+                grid.count(Side::Sell, false),
+                sum(Side::Sell, false),
+                preview.size_unit(Side::Sell),
+                grid.count(Side::Buy, true),
+                sum(Side::Buy, true),
+                grid.count(Side::Buy, false),
+                sum(Side::Buy, false),
+                preview.size_unit(Side::Buy),
+                sum(Side::Sell, true) + sum(Side::Sell, false),
+                sum(Side::Buy, true) + sum(Side::Buy, false),
+            )
+        }
+        Err(reason) => format!("Cannot project grid: {reason}"),
+    };
+    let totals = Paragraph::new(totals)
+        .wrap(Wrap { trim: true })
+        .block(Block::default().borders(Borders::ALL).title("Totals (preview only, nothing on chain)"));
+    frame.render_widget(totals, left[1]);
+
+    let Ok(grid) = projected else {
+        let empty = Paragraph::new("(no ladder)")
+            .block(Block::default().borders(Borders::ALL).title("Projected Ladder"));
+        frame.render_widget(empty, columns[1]);
+        return;
+    };
+
+    let slots = preview::ladder(&grid);
+    let visible = columns[1].height.saturating_sub(3) as usize;
+    app.preview.set_page_height(visible);
+    let start = app.preview.first_row(&slots);
+    // Buy and sell sizes are in different assets, so bars scale per side.
+    let max_size = |side: Side| {
+        slots
+            .iter()
+            .filter(|s| s.side == side)
+            .map(|s| s.size)
+            .fold(0.0, f64::max)
+    };
+    let (max_buy, max_sell) = (max_size(Side::Buy), max_size(Side::Sell));
+
+    let rows = slots
+        .iter()
+        .skip(start)
+        .take(visible)
+        .map(|slot| {
+            let color = match slot.side {
+                Side::Sell => Color::Red,
+                Side::Buy => Color::Green,
+                Side::Spread => Color::Yellow,
+            };
+            let style = if slot.active || slot.side == Side::Spread {
+                Style::default().fg(color)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            let role = match (slot.side, slot.active) {
+                (Side::Spread, _) => String::from("spread"),
+                (side, true) => format!("{} active", side.label()),
+                (side, false) => format!("{} virtual", side.label()),
+            };
+            let side_max = if slot.side == Side::Sell { max_sell } else { max_buy };
+            let bar_len = if side_max > 0.0 {
+                (slot.size / side_max * 20.0).round() as usize
+            } else {
+                0
+            };
+            Row::new(vec![
+                Cell::from(format!("slot-{}", slot.index)),
+                Cell::from(format!("{:.8}", slot.price)),
+                Cell::from(role),
+                Cell::from(format!("{:.6}", slot.size)),
+                Cell::from("█".repeat(bar_len)),
+            ])
+            .style(style)
+        })
+        .collect::<Vec<_>>();
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(10),
+            Constraint::Length(16),
+            Constraint::Length(13),
+            Constraint::Length(14),
+            Constraint::Min(10),
+        ],
+    )
+    .header(
+        Row::new(vec!["Slot", "Price", "Role", "Size", ""])
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+    )
+    .block(Block::default().borders(Borders::ALL).title(format!(
+        "Projected Ladder (rows {}-{} of {}, PgUp/PgDn)",
+        start + 1,
+        (start + visible).min(slots.len()),
+        slots.len()
+    )));
+    frame.render_widget(table, columns[1]);
+}
@@ -0,0 +1,128 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, Wrap},
+    Frame,
+};
+
+use crate::{app::App, infra::Health, runtime};
+
+pub(super) fn render(frame: &mut Frame, app: &App, area: Rect) {
+    let rows_area = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(6), Constraint::Min(5)])
+        .split(area);
+
+    let now_ms = runtime::now_ms();
+    let rows = app
+        .snapshot
+        .infra
+        .components
+        .iter()
+        .map(|c| {
+            let color = match c.health {
+                Health::Ok => Color::Green,
+                Health::Warn => Color::Yellow,
+                Health::Down => Color::Red,
+                Health::Unknown => Color::DarkGray,
+            };
+            let uptime = c
+                .process
+                .as_ref()
+                .and_then(|p| p.uptime(now_ms))
+                .map(runtime::format_duration)
+                .unwrap_or_else(|| String::from("-"));
+            Row::new(vec![
+                Cell::from(c.name),
+                Cell::from(c.role),
+                Cell::from(c.status.clone()),
+                Cell::from(c.health.label()).style(Style::default().fg(color)),
+                Cell::from(uptime),
+                Cell::from(c.detail.clone()),
+            ])
+        })
+        .collect::<Vec<_>>();
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(15),
+            Constraint::Length(19),
+            Constraint::Length(10),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Min(20),
+        ],
+    )
+    .header(
+        Row::new(vec!["Process", "Role", "Status", "Health", "Uptime", "Detail"])
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+    )
+    .block(Block::default().borders(Borders::ALL).title("Infrastructure"));
+    frame.render_widget(table, rows_area[0]);
+
+    let mut lines = Vec::new();
+    match &app.cred_probe.latest {
+        Some(probe) => lines.push(format!(
+            "Cred daemon probe: {} - {} | ready file: {} | socket: {} | reply: {}",
+            probe.health.label(),
+            probe.detail,
+            probe
+                .ready_age
+                .map(|age| format!("{} old", runtime::format_duration(age)))
+                .unwrap_or_else(|| String::from("missing")),
+            if probe.connected {
+                "accepts"
+            } else if probe.socket_exists {
+                "refuses"
+            } else {
+                "missing"
+            },
+            probe
+                .latency
+                .map(|l| format!("{}ms", l.as_millis()))
+                .unwrap_or_else(|| String::from("-")),
+        )),
+        None => lines.push(String::from("Cred daemon probe: pending")),
+    }
+    match &app.snapshot.infra.updater_run {
+        Some(run) => lines.push(format!(
+            "Updater last run: {} -> {} ({})",
+            run.started_at,
+            run.result.label(),
+            run.last_message
+        )),
+        None => lines.push(String::from("Updater last run: none recorded")),
+    }
+    for c in &app.snapshot.infra.components {
+        let log = c
+            .log_path
+            .as_ref()
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| String::from("(no log file)"));
+        lines.push(format!("{} log: {log}", c.name));
+    }
+    lines.push(String::new());
+    let blocked = app
+        .snapshot
+        .bots
+        .iter()
+        .filter(|b| !b.blocked_by.is_empty())
+        .collect::<Vec<_>>();
+    if blocked.is_empty() {
+        lines.push(String::from("No bots blocked by system processes."));
+    } else {
+        lines.push(String::from("Blocked bots:"));
+        for bot in blocked {
+            lines.push(format!(
+                "- {} [{}] waits on {}",
+                bot.name,
+                bot.runtime_status,
+                bot.blocked_by.join(", ")
+            ));
+        }
+    }
+    let body = Paragraph::new(lines.join("\n"))
+        .wrap(Wrap { trim: false })
+        .block(Block::default().borders(Borders::ALL).title("Dependencies"));
+    frame.render_widget(body, rows_area[1]);
+}
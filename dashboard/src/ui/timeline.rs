@@ -0,0 +1,62 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use super::log_line;
+use crate::app::App;
+
+const BOT_COLORS: [Color; 6] = [
+    Color::Cyan,
+    Color::Magenta,
+    Color::Green,
+    Color::Yellow,
+    Color::LightBlue,
+    Color::LightRed,
+];
+
+pub(super) fn render(frame: &mut Frame, app: &mut App, area: Rect) {
+    let log_colors = app.log_colors;
+    let timeline = &mut app.timeline;
+    timeline.view.set_page_height(area.height.saturating_sub(2) as usize);
+    let name_width = timeline.bots.iter().map(|b| b.len()).max().unwrap_or(4).min(16);
+
+    let view = &timeline.view;
+    let lines = view
+        .visible
+        .iter()
+        .skip(view.offset)
+        .take(view.page_height)
+        .map(|index| {
+            let event = &timeline.events[*index];
+            let name = timeline.bots.get(event.bot).map(String::as_str).unwrap_or("?");
+            let mut spans = vec![Span::styled(
+                format!("{name:<name_width$.name_width$} "),
+                Style::default()
+                    .fg(BOT_COLORS[event.bot % BOT_COLORS.len()])
+                    .add_modifier(Modifier::BOLD),
+            )];
+            spans.extend(log_line(&event.line, log_colors).spans);
+            Line::from(spans)
+        })
+        .collect::<Vec<_>>();
+
+    let title = format!(
+        "Fleet Timeline | {} | {} | bot: {} | {} | cat: {} | since {} | b bot, c category, g/G, PgUp/PgDn",
+        view.position_label(),
+        if view.follow { "FOLLOW" } else { "PAUSED" },
+        timeline
+            .bot_filter
+            .and_then(|b| timeline.bots.get(b))
+            .map(String::as_str)
+            .unwrap_or("all"),
+        view.levels_label(),
+        view.category.as_deref().unwrap_or("all"),
+        timeline.window_start.as_deref().unwrap_or("start of logs"),
+    );
+    let body = Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(title));
+    frame.render_widget(body, area);
+}